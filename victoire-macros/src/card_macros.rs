@@ -13,13 +13,28 @@
 #[macro_export]
 macro_rules! declare_card {
     ($struct_name:ident) => {
-        #[derive(Clone, Serialize, Deserialize)]
-        pub struct $struct_name;
+        $crate::declare_card!($struct_name, "");
     };
     ($struct_name:ident, $doc:tt) => {
         #[doc = $doc]
-        #[derive(Clone, Serialize, Deserialize)]
-        pub struct $struct_name;
+        #[derive(Clone, Default, Serialize, Deserialize)]
+        pub struct $struct_name {
+            #[serde(default, skip_serializing_if = "Option::is_none")]
+            id: Option<CardId>,
+        }
+
+        /// A copy of the card that has not entered a game yet
+        #[allow(non_upper_case_globals)]
+        pub const $struct_name: $struct_name = $struct_name { id: None };
+
+        impl Identified for $struct_name {
+            fn id(&self) -> Option<CardId> {
+                self.id
+            }
+            fn set_id(&mut self, id: CardId) {
+                self.id = Some(id);
+            }
+        }
     };
 }
 
@@ -96,7 +111,8 @@ macro_rules! name {
     };
 }
 
-/// Set the card's cost
+/// Set the card's cost, optionally with Potions or Debt as well as coins,
+/// e.g. `card_cost!(2, potions = 1)`
 ///
 /// ```
 /// # use victoire::cards::prelude::*;
@@ -117,6 +133,16 @@ macro_rules! card_cost {
             Cost::new($coins)
         }
     };
+    ($coins:expr, potions = $potions:expr) => {
+        fn cost(&self) -> Cost {
+            Cost::new($coins).with_potions($potions)
+        }
+    };
+    ($coins:expr, debt = $debt:expr) => {
+        fn cost(&self) -> Cost {
+            Cost::new($coins).with_debt($debt)
+        }
+    };
 }
 
 /// Sets a card's types
@@ -143,7 +169,8 @@ macro_rules! types {
     };
 }
 
-/// Sets a treasure card's coin value to some fixed amount
+/// Sets a treasure card's coin value to some fixed amount, optionally with
+/// Potions, e.g. `treasure_value!(0, potions = 1)`
 ///
 /// For example, Gold could be declared as follows:
 /// ```
@@ -165,6 +192,11 @@ macro_rules! treasure_value {
             Value::new($coins)
         }
     };
+    ($coins:expr, potions = $potions:expr) => {
+        fn treasure_value(&self) -> Value {
+            Value::new($coins).with_potions($potions)
+        }
+    };
 }
 
 /// Sets a victory/curse card's point value to some fixed amount
@@ -231,13 +263,15 @@ macro_rules! victory_points {
 #[macro_export]
 macro_rules! basic_on_play_effects {
     (cards=$cards:expr, actions=$actions:expr, buys=$buys:expr, coins=$coins:expr) => {
-        fn effects_on_play(&self, game: &mut Game, player_index: usize, callbacks: &dyn Callbacks) {
-            let player = &mut game.players[player_index];
+        fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+            game.draw_cards(player_index, $cards)?;
 
-            player.draw_cards($cards);
+            let player = game.player_mut(player_index)?;
             player.add_actions($actions);
             player.add_buys($buys);
             player.add_coins($coins);
+
+            Ok(())
         }
     };
 }
@@ -2,9 +2,15 @@
 
 use std::io;
 
-use crate::types::{Card, CardList, Supply};
+use crate::types::{Card, CardList, Supply, TokenKind};
 
+use async_trait::async_trait;
 use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+/// How many invalid answers a player can give to the same question before
+/// the engine gives up with [`Error::TooManyInvalidAnswers`](crate::error::Error::TooManyInvalidAnswers)
+pub const MAX_INVALID_ANSWERS: usize = 100;
 
 /// Trait for getting input from players while card effects are occurring
 pub trait Callbacks: Send + Sync {
@@ -14,16 +20,32 @@ pub trait Callbacks: Send + Sync {
         player_index: usize,
         supply: &Supply,
     ) -> Option<Box<dyn Card>>;
-    /// Prompt the player for one or more cards from their hand
-    fn choose_cards_from_hand(&self, count: &ChoiceCountOptions, message: &str) -> Vec<usize>;
-    /// Prompt the player for one or more cards from their discard
-    fn choose_cards_from_discard(&self, count: &ChoiceCountOptions, message: &str) -> Vec<usize>;
-    /// Prompt the player for one or more cards from the trash
-    fn choose_cards_from_trash(&self, count: &ChoiceCountOptions, message: &str) -> Vec<usize>;
-    /// Prompt the player for one or more cards from a list of options
+    /// Prompt the given player for one or more cards from their hand
+    fn choose_cards_from_hand(
+        &self,
+        player_index: usize,
+        count: &ChoiceCountOptions,
+        message: &str,
+    ) -> Vec<usize>;
+    /// Prompt the given player for one or more cards from their discard
+    fn choose_cards_from_discard(
+        &self,
+        player_index: usize,
+        count: &ChoiceCountOptions,
+        message: &str,
+    ) -> Vec<usize>;
+    /// Prompt the given player for one or more cards from the trash
+    fn choose_cards_from_trash(
+        &self,
+        player_index: usize,
+        count: &ChoiceCountOptions,
+        message: &str,
+    ) -> Vec<usize>;
+    /// Prompt the given player for one or more cards from a list of options
     fn choose_cards_from_selection(
         &self,
-        count: usize,
+        player_index: usize,
+        count: &ChoiceCountOptions,
         card_choices: &CardList,
         message: &str,
     ) -> Vec<usize>;
@@ -36,9 +58,209 @@ pub trait Callbacks: Send + Sync {
         count: &ChoiceCountOptions,
         prompt: &str,
     ) -> Vec<usize>;
+    /// Prompt the given player to choose from a list of options, e.g. for
+    /// "choose one" effects
+    fn choose_options(
+        &self,
+        player_index: usize,
+        count: &ChoiceCountOptions,
+        options: &[&str],
+        prompt: &str,
+    ) -> Vec<usize>;
+    /// Prompt the given player for the next treasure to play from their
+    /// hand during their buy phase. Plays all of them by default
+    fn choose_treasure(&self, player_index: usize) -> TreasureChoice {
+        let _ = player_index;
+        TreasureChoice::PlayAll
+    }
+    /// Prompt the given player for how many of their `available` tokens of
+    /// the given kind to spend. Spends none by default
+    fn choose_tokens_to_spend(
+        &self,
+        player_index: usize,
+        kind: TokenKind,
+        available: usize,
+    ) -> usize {
+        let _ = (player_index, kind, available);
+        0
+    }
+}
+
+/// Asynchronous version of [`Callbacks`], for clients that have to wait on
+/// something like a network connection for their answers
+///
+/// Used by the `_async` methods on [`Game`](crate::types::Game), e.g.
+/// [`Game::turn_async`](crate::types::Game::turn_async).
+#[async_trait]
+pub trait AsyncCallbacks: Send + Sync {
+    /// Prompt the given player for a card from the supply
+    async fn choose_card_from_supply(
+        &self,
+        player_index: usize,
+        supply: &Supply,
+    ) -> Option<Box<dyn Card>>;
+    /// Prompt the given player for one or more cards from their hand
+    async fn choose_cards_from_hand(
+        &self,
+        player_index: usize,
+        count: &ChoiceCountOptions,
+        message: &str,
+    ) -> Vec<usize>;
+    /// Prompt the given player for one or more cards from their discard
+    async fn choose_cards_from_discard(
+        &self,
+        player_index: usize,
+        count: &ChoiceCountOptions,
+        message: &str,
+    ) -> Vec<usize>;
+    /// Prompt the given player for one or more cards from the trash
+    async fn choose_cards_from_trash(
+        &self,
+        player_index: usize,
+        count: &ChoiceCountOptions,
+        message: &str,
+    ) -> Vec<usize>;
+    /// Prompt the given player for one or more cards from a list of options
+    async fn choose_cards_from_selection(
+        &self,
+        player_index: usize,
+        count: &ChoiceCountOptions,
+        card_choices: &CardList,
+        message: &str,
+    ) -> Vec<usize>;
+    /// Prompt the given player with a yes/no question
+    async fn yes_or_no(&self, player_index: usize, prompt: &str) -> bool;
+    /// Prompt player for one or more player indices
+    async fn choose_players(
+        &self,
+        player_index: usize,
+        count: &ChoiceCountOptions,
+        prompt: &str,
+    ) -> Vec<usize>;
+    /// Prompt the given player to choose from a list of options, e.g. for
+    /// "choose one" effects
+    async fn choose_options(
+        &self,
+        player_index: usize,
+        count: &ChoiceCountOptions,
+        options: &[&str],
+        prompt: &str,
+    ) -> Vec<usize>;
+    /// Prompt the given player for the next treasure to play from their
+    /// hand during their buy phase. Plays all of them by default
+    async fn choose_treasure(&self, player_index: usize) -> TreasureChoice {
+        let _ = player_index;
+        TreasureChoice::PlayAll
+    }
+    /// Prompt the given player for how many of their `available` tokens of
+    /// the given kind to spend. Spends none by default
+    async fn choose_tokens_to_spend(
+        &self,
+        player_index: usize,
+        kind: TokenKind,
+        available: usize,
+    ) -> usize {
+        let _ = (player_index, kind, available);
+        0
+    }
+}
+
+/// Adapter for using synchronous [`Callbacks`] where [`AsyncCallbacks`] are
+/// expected
+///
+/// The wrapped callbacks are called directly, so they shouldn't block for
+/// long, e.g. bots or pre-recorded answers.
+#[derive(Clone, Debug, Default)]
+pub struct SyncCallbacks<C>(pub C);
+
+#[async_trait]
+impl<C: Callbacks> AsyncCallbacks for SyncCallbacks<C> {
+    async fn choose_card_from_supply(
+        &self,
+        player_index: usize,
+        supply: &Supply,
+    ) -> Option<Box<dyn Card>> {
+        self.0.choose_card_from_supply(player_index, supply)
+    }
+
+    async fn choose_cards_from_hand(
+        &self,
+        player_index: usize,
+        count: &ChoiceCountOptions,
+        message: &str,
+    ) -> Vec<usize> {
+        self.0.choose_cards_from_hand(player_index, count, message)
+    }
+
+    async fn choose_cards_from_discard(
+        &self,
+        player_index: usize,
+        count: &ChoiceCountOptions,
+        message: &str,
+    ) -> Vec<usize> {
+        self.0
+            .choose_cards_from_discard(player_index, count, message)
+    }
+
+    async fn choose_cards_from_trash(
+        &self,
+        player_index: usize,
+        count: &ChoiceCountOptions,
+        message: &str,
+    ) -> Vec<usize> {
+        self.0.choose_cards_from_trash(player_index, count, message)
+    }
+
+    async fn choose_cards_from_selection(
+        &self,
+        player_index: usize,
+        count: &ChoiceCountOptions,
+        card_choices: &CardList,
+        message: &str,
+    ) -> Vec<usize> {
+        self.0
+            .choose_cards_from_selection(player_index, count, card_choices, message)
+    }
+
+    async fn yes_or_no(&self, player_index: usize, prompt: &str) -> bool {
+        self.0.yes_or_no(player_index, prompt)
+    }
+
+    async fn choose_players(
+        &self,
+        player_index: usize,
+        count: &ChoiceCountOptions,
+        prompt: &str,
+    ) -> Vec<usize> {
+        self.0.choose_players(player_index, count, prompt)
+    }
+
+    async fn choose_options(
+        &self,
+        player_index: usize,
+        count: &ChoiceCountOptions,
+        options: &[&str],
+        prompt: &str,
+    ) -> Vec<usize> {
+        self.0.choose_options(player_index, count, options, prompt)
+    }
+
+    async fn choose_treasure(&self, player_index: usize) -> TreasureChoice {
+        self.0.choose_treasure(player_index)
+    }
+
+    async fn choose_tokens_to_spend(
+        &self,
+        player_index: usize,
+        kind: TokenKind,
+        available: usize,
+    ) -> usize {
+        self.0.choose_tokens_to_spend(player_index, kind, available)
+    }
 }
 
 /// How many items the player can choose
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ChoiceCountOptions {
     /// The player must choose exactly `count` items from the given choices
     Exact {
@@ -54,6 +276,20 @@ pub enum ChoiceCountOptions {
     Any,
 }
 
+/// What to do next while playing treasures in the buy phase
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TreasureChoice {
+    /// Play the treasure at the given index in the player's hand
+    Play {
+        /// The index of the treasure in the player's hand
+        hand_index: usize,
+    },
+    /// Play every treasure left in the player's hand
+    PlayAll,
+    /// Stop playing treasures and move on to buying cards
+    Stop,
+}
+
 /// An example implementation of [`Callbacks`] for testing purposes
 #[derive(Clone)]
 pub struct TestClient;
@@ -85,7 +321,12 @@ impl Callbacks for TestClient {
         Some(card.clone())
     }
 
-    fn choose_cards_from_hand(&self, count: &ChoiceCountOptions, message: &str) -> Vec<usize> {
+    fn choose_cards_from_hand(
+        &self,
+        player_index: usize,
+        count: &ChoiceCountOptions,
+        message: &str,
+    ) -> Vec<usize> {
         let mut input = String::new();
         let mut output = vec![];
         let prompt = "Enter a card index from your hand, or -1 to stop:";
@@ -126,7 +367,6 @@ impl Callbacks for TestClient {
                 }
             }
             ChoiceCountOptions::Any => {
-                let mut j = 0;
                 while i >= 0 {
                     #[allow(clippy::cast_sign_loss)] // i must be >= 0 here
                     output.push(i as usize);
@@ -136,7 +376,6 @@ impl Callbacks for TestClient {
                         .expect("error: unable to read user input");
                     input = input.trim_end().to_owned();
                     i = input.parse::<isize>().unwrap();
-                    j += 1;
                 }
             }
         }
@@ -144,17 +383,28 @@ impl Callbacks for TestClient {
         output
     }
 
-    fn choose_cards_from_discard(&self, count: &ChoiceCountOptions, message: &str) -> Vec<usize> {
+    fn choose_cards_from_discard(
+        &self,
+        player_index: usize,
+        count: &ChoiceCountOptions,
+        message: &str,
+    ) -> Vec<usize> {
         todo!()
     }
 
-    fn choose_cards_from_trash(&self, count: &ChoiceCountOptions, message: &str) -> Vec<usize> {
+    fn choose_cards_from_trash(
+        &self,
+        player_index: usize,
+        count: &ChoiceCountOptions,
+        message: &str,
+    ) -> Vec<usize> {
         todo!()
     }
 
     fn choose_cards_from_selection(
         &self,
-        count: usize,
+        player_index: usize,
+        count: &ChoiceCountOptions,
         card_choices: &CardList,
         message: &str,
     ) -> Vec<usize> {
@@ -179,4 +429,95 @@ impl Callbacks for TestClient {
     ) -> Vec<usize> {
         todo!()
     }
+
+    fn choose_options(
+        &self,
+        player_index: usize,
+        count: &ChoiceCountOptions,
+        options: &[&str],
+        prompt: &str,
+    ) -> Vec<usize> {
+        println!("{prompt}");
+        for (i, option) in options.iter().enumerate() {
+            println!("{i}: {option}");
+        }
+
+        let prompt = match count {
+            ChoiceCountOptions::Exact { count } => {
+                format!("Enter {count} option indices separated by spaces:")
+            }
+            ChoiceCountOptions::UpTo { max } => {
+                format!("Enter up to {max} option indices separated by spaces:")
+            }
+            ChoiceCountOptions::Any => "Enter option indices separated by spaces:".to_owned(),
+        };
+
+        while let Some(input) = read_input(&prompt) {
+            let indices: Option<Vec<usize>> = input
+                .split_whitespace()
+                .map(|i| i.parse::<usize>().ok().filter(|&i| i < options.len()))
+                .collect();
+            let valid_count = |len: usize| match count {
+                ChoiceCountOptions::Exact { count } => len == *count,
+                ChoiceCountOptions::UpTo { max } => len <= *max,
+                ChoiceCountOptions::Any => true,
+            };
+
+            match indices {
+                Some(indices) if valid_count(indices.len()) => return indices,
+                _ => println!("{input:?} is not a valid choice"),
+            }
+        }
+
+        vec![]
+    }
+
+    fn choose_treasure(&self, player_index: usize) -> TreasureChoice {
+        let prompt = "Enter a treasure index from your hand, (a)ll, or -1 to stop:";
+        // Stop playing treasures once there is no more input
+        while let Some(input) = read_input(prompt) {
+            if input.starts_with('a') {
+                return TreasureChoice::PlayAll;
+            }
+
+            if input == "-1" {
+                break;
+            }
+
+            match input.parse::<usize>() {
+                Ok(hand_index) => return TreasureChoice::Play { hand_index },
+                Err(_) => println!("{input:?} is not a hand index"),
+            }
+        }
+
+        TreasureChoice::Stop
+    }
+
+    fn choose_tokens_to_spend(
+        &self,
+        player_index: usize,
+        kind: TokenKind,
+        available: usize,
+    ) -> usize {
+        let prompt = format!("How many of your {available} {kind:?} do you want to spend?");
+        while let Some(input) = read_input(&prompt) {
+            match input.parse::<usize>() {
+                Ok(spend) if spend <= available => return spend,
+                _ => println!("Enter a number from 0 to {available}"),
+            }
+        }
+
+        0
+    }
+}
+
+/// Prints `prompt` and reads a line from stdin, without the line ending.
+/// Returns `None` if stdin is closed or can't be read
+fn read_input(prompt: &str) -> Option<String> {
+    println!("{prompt}");
+    let mut input = String::new();
+    match io::stdin().read_line(&mut input) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(input.trim_end().to_owned()),
+    }
 }
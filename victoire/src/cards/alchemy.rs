@@ -0,0 +1,36 @@
+//! Cards from the Alchemy expansion
+//!
+//! Potion is in [`base`](super::base), and is added to the supply by
+//! [`Game::generate_supply`] when a kingdom card costs one.
+
+#![allow(clippy::wildcard_imports)]
+
+use super::prelude::*;
+
+/// The kingdom cards from Alchemy that are implemented so far
+pub fn kingdom_cards() -> CardList {
+    card_vec![Possession]
+}
+
+// Possession
+// The player to your left takes an extra turn after this one, in which you
+// make all decisions for them. Any cards or Debt they would gain on that turn,
+// you gain instead. Any cards of theirs that are trashed are set aside and
+// returned to their discard pile at the end of the turn
+declare_card!(
+    Possession,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Possession)"
+);
+#[typetag::serde]
+impl Card for Possession {
+    name!("Possession");
+    card_cost!(6, potions = 1);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        let left = (player_index + 1) % game.player_count();
+        game.take_extra_turn(ExtraTurn::controlled(left, player_index));
+
+        Ok(())
+    }
+}
@@ -24,6 +24,28 @@ basic_treasure!(
     "[Wiki link](http://wiki.dominionstrategy.com/index.php/Gold)"
 );
 
+basic_treasure!(
+    Platinum,
+    "Platinum",
+    cost = 9,
+    value = 5,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Platinum)"
+);
+
+// Potion
+// Gives a Potion to spend on cards that cost one
+declare_card!(
+    Potion,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Potion)"
+);
+#[typetag::serde]
+impl Card for Potion {
+    name!("Potion");
+    card_cost!(4);
+    types!(vec![Treasure]);
+    treasure_value!(0, potions = 1);
+}
+
 basic_victory!(
     Estate,
     "Estate",
@@ -46,6 +68,14 @@ basic_victory!(
     "[Wiki link](http://wiki.dominionstrategy.com/index.php/Province)"
 );
 
+basic_victory!(
+    Colony,
+    "Colony",
+    cost = 11,
+    points = 10,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Colony)"
+);
+
 basic_curse!(
     BasicCurse,
     "BasicCurse",
@@ -0,0 +1,45 @@
+//! Cards from the Dark Ages expansion
+//!
+//! Cards that use Ruins, Shelters or Spoils are not included yet, since the
+//! supply doesn't have those piles.
+
+#![allow(clippy::wildcard_imports)]
+
+use super::base::*;
+use super::dominion::gain_if_available;
+use super::prelude::*;
+
+/// The kingdom cards from Dark Ages that are implemented so far
+pub fn kingdom_cards() -> CardList {
+    card_vec![Feodum]
+}
+
+// Feodum
+// Worth 1 VP per 3 Silvers you have (round down), when you trash this, gain 2
+// Silvers
+declare_card!(
+    Feodum,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Feodum)"
+);
+#[typetag::serde]
+impl Card for Feodum {
+    name!("Feodum");
+    card_cost!(4);
+    types!(vec![Victory]);
+
+    fn victory_points(&self, player: &Player) -> isize {
+        let silvers = player
+            .all_cards()
+            .filter(|card| card.name() == Silver.name())
+            .count();
+        (silvers / 3) as isize
+    }
+
+    fn effects_on_trash(&self, game: &mut Game, player_index: usize) -> Result {
+        for _ in 0..2 {
+            gain_if_available(game, player_index, Box::new(Silver), Zone::Discard)?;
+        }
+
+        Ok(())
+    }
+}
@@ -4,33 +4,357 @@
 
 #![allow(clippy::wildcard_imports)]
 
+use itertools::Itertools;
+
 use super::base::*;
 use super::prelude::*;
 
+/// The kingdom cards in the 2nd edition of the base set
+pub fn kingdom_cards() -> CardList {
+    card_vec![
+        Artisan,
+        Bandit,
+        Bureaucrat,
+        Cellar,
+        Chapel,
+        CouncilRoom,
+        Festival,
+        Gardens,
+        Harbinger,
+        Laboratory,
+        Library,
+        Market,
+        Merchant,
+        Militia,
+        Mine,
+        Moat,
+        Moneylender,
+        Poacher,
+        Remodel,
+        Sentry,
+        Smithy,
+        ThroneRoom,
+        Vassal,
+        Village,
+        Witch,
+        Workshop
+    ]
+}
+
+/// Asks the player for a card from the supply to gain, carrying on with
+/// `then`, where the choice is checked with [`chosen_gain`]
+pub(crate) fn choose_gain(game: &mut Game, player_index: usize, then: Then) {
+    game.ask(Ask::supply(player_index, "Choose a card to gain"), then);
+}
+
+/// The card the player chose to gain, or `None` if they chose nothing. A card
+/// that `allowed` doesn't accept, or that is no longer in the supply, is an
+/// invalid answer, so they are asked again, up to [`MAX_INVALID_ANSWERS`]
+/// times
+pub(crate) fn chosen_gain(
+    game: &Game,
+    reply: &Reply,
+    allowed: impl Fn(&dyn Card) -> bool,
+) -> Result<Option<Box<dyn Card>>> {
+    let Some(card) = reply.card() else {
+        return Ok(None);
+    };
+    let in_supply = game
+        .supply
+        .get(card.name())
+        .is_some_and(|entry| entry.count > 0);
+    if in_supply && allowed(&*card) {
+        Ok(Some(card))
+    } else {
+        Err(Error::InvalidAnswer)
+    }
+}
+
+/// Gains a copy of a card to one of the player's zones, or nothing if its
+/// supply pile is empty, e.g. Witch's Curse once the Curses have run out.
+/// Any other error is returned
+pub(crate) fn gain_if_available(
+    game: &mut Game,
+    player_index: usize,
+    card: Box<dyn Card>,
+    to: Zone,
+) -> Result {
+    match game.gain_to(player_index, card, to) {
+        Err(Error::EmptyPile { .. }) => Ok(()),
+        result => result,
+    }
+}
+
+/// Asks the player for exactly `count` cards from their hand, carrying on
+/// with `then`, where the choice is read with [`chosen_from_hand`]
+pub(crate) fn choose_exact_from_hand(
+    game: &mut Game,
+    player_index: usize,
+    count: usize,
+    message: &str,
+    then: Then,
+) {
+    let count = ChoiceCountOptions::Exact { count };
+    game.ask(Ask::hand(player_index, count, message), then);
+}
+
+/// The `count` cards the player chose from their hand, or their whole hand
+/// if it is smaller. Any other answer is an invalid answer, so they are
+/// asked again, up to [`MAX_INVALID_ANSWERS`] times
+pub(crate) fn chosen_from_hand(
+    game: &Game,
+    player_index: usize,
+    count: usize,
+    reply: &Reply,
+) -> Result<Vec<usize>> {
+    let hand_size = game.player(player_index)?.hand.len();
+    let indexes = chosen_up_to_from_hand(game, player_index, count, reply)?;
+    if indexes.len() == count.min(hand_size) {
+        Ok(indexes)
+    } else {
+        Err(Error::InvalidAnswer)
+    }
+}
+
+/// The up to `max` different cards the player chose from their hand. Cards
+/// that aren't there, repeats or too many cards are an invalid answer, as
+/// for [`chosen_from_hand`]
+pub(crate) fn chosen_up_to_from_hand(
+    game: &Game,
+    player_index: usize,
+    max: usize,
+    reply: &Reply,
+) -> Result<Vec<usize>> {
+    let hand_size = game.player(player_index)?.hand.len();
+    let indexes = reply.indexes();
+    if indexes.len() <= max && indexes.iter().all_unique() && indexes.iter().all(|&i| i < hand_size)
+    {
+        Ok(indexes.to_vec())
+    } else {
+        Err(Error::InvalidAnswer)
+    }
+}
+
+/// Asks the player for any number of the given cards, carrying on with
+/// `then`, where the choice is read with [`chosen_from_selection`]
+pub(crate) fn choose_from_selection(
+    game: &mut Game,
+    player_index: usize,
+    cards: &CardList,
+    message: &str,
+    then: Then,
+) {
+    let count = ChoiceCountOptions::UpTo { max: cards.len() };
+    game.ask(Ask::selection(player_index, count, cards, message), then);
+}
+
+/// The different cards the player chose out of `count` offered. Anything
+/// else is an invalid answer, as for [`chosen_from_hand`]
+pub(crate) fn chosen_from_selection(reply: &Reply, count: usize) -> Result<Vec<usize>> {
+    let indexes = reply.indexes();
+    if indexes.iter().all_unique() && indexes.iter().all(|&i| i < count) {
+        Ok(indexes.to_vec())
+    } else {
+        Err(Error::InvalidAnswer)
+    }
+}
+
+/// Moves cards that were revealed or taken from the top of the player's deck
+/// off their [set aside](Mat::SetAside) mat, in the order given
+pub(crate) fn move_set_aside(
+    game: &mut Game,
+    player_index: usize,
+    cards: CardList,
+    to: Zone,
+) -> Result {
+    for card in cards {
+        game.move_card(player_index, Mat::SetAside.into(), to, Selector::Card(card))?;
+    }
+
+    Ok(())
+}
+
+/// Puts set aside cards back on top of the player's deck in the order they
+/// choose, carrying on with `then`, whose step should finish with
+/// [`put_back_chosen`]. There's nothing to choose for a single card, so it
+/// is put back straight away
+pub(crate) fn put_back_in_order(
+    game: &mut Game,
+    player_index: usize,
+    cards: CardList,
+    then: Then,
+) -> Result {
+    if cards.len() > 1 {
+        choose_from_selection(
+            game,
+            player_index,
+            &cards,
+            "Choose the order to put cards back on your deck, top first",
+            then.with_cards(cards.clone()),
+        );
+        Ok(())
+    } else {
+        move_set_aside(game, player_index, cards, Zone::Deck)
+    }
+}
+
+/// Puts the cards passed along by [`put_back_in_order`] back on the deck,
+/// first choice on top. Cards the player didn't mention keep their order
+/// below those
+pub(crate) fn put_back_chosen(game: &mut Game, player_index: usize, resume: Resume) -> Result {
+    let mut cards = resume.cards;
+    let top = chosen_from_selection(&resume.reply, cards.len())?;
+    let mut ordered = take_selected(&mut cards, &top);
+    ordered.append(&mut cards);
+
+    ordered.reverse();
+    move_set_aside(game, player_index, ordered, Zone::Deck)
+}
+
+/// Asks the player for an action card from their hand to play `times` times,
+/// e.g. for Throne Room, carrying on with `then`, whose step should finish
+/// with [`play_chosen_action`]
+pub(crate) fn play_action_times(game: &mut Game, player_index: usize, times: usize, then: Then) {
+    let count = ChoiceCountOptions::UpTo { max: 1 };
+    let prompt = format!("Choose a card to play {times} times");
+    game.ask(Ask::hand(player_index, count, &prompt), then);
+}
+
+/// Plays the action the player chose for [`play_action_times`] `times`
+/// times. Choosing nothing plays nothing, and choosing a card that isn't an
+/// action is an invalid answer, so they are asked again
+pub(crate) fn play_chosen_action(
+    game: &mut Game,
+    player_index: usize,
+    times: usize,
+    reply: &Reply,
+) -> Result {
+    let Some(card_index) = reply.index() else {
+        return Ok(());
+    };
+
+    if !game
+        .player(player_index)?
+        .hand
+        .get(card_index)
+        .is_some_and(|card| card.is_action())
+    {
+        return Err(Error::InvalidAnswer);
+    }
+
+    let card = game
+        .move_card(
+            player_index,
+            Zone::Hand,
+            Zone::InPlay,
+            Selector::Index(card_index),
+        )?
+        .remove(0);
+    for _ in 0..times {
+        game.action_effects(player_index, &*card)?;
+    }
+
+    Ok(())
+}
+
+/// Moves this exact copy of the card out of the player's play area, e.g. for
+/// a card that trashes itself. Returns whether it was still there to be moved
+pub(crate) fn move_from_play(
+    game: &mut Game,
+    player_index: usize,
+    card: &(dyn Card + 'static),
+    to: Zone,
+) -> Result<bool> {
+    let selector = Selector::Card(dyn_clone::clone_box(card));
+    match game.move_card(player_index, Zone::InPlay, to, selector) {
+        Ok(_) => Ok(true),
+        Err(Error::CardNotFound { .. }) => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// Trashes the card from the player's play area if it is still there.
+/// Returns whether it was trashed
+pub(crate) fn trash_from_play(
+    game: &mut Game,
+    player_index: usize,
+    card: &(dyn Card + 'static),
+) -> Result<bool> {
+    move_from_play(game, player_index, card, Zone::Trash)
+}
+
+/// Removes the cards at the given indexes, returning them in the order given.
+/// The indexes must be valid and unique
+pub(crate) fn take_selected(cards: &mut CardList, indexes: &[usize]) -> CardList {
+    let selected = indexes.iter().map(|&i| cards[i].clone()).collect();
+
+    let mut index = 0;
+    cards.retain(|_| {
+        let keep = !indexes.contains(&index);
+        index += 1;
+        keep
+    });
+
+    selected
+}
+
 declare_card!(
     Artisan,
     "[Wiki link](http://wiki.dominionstrategy.com/index.php/Artisan)"
 );
+impl Artisan {
+    const GAIN: usize = 0;
+    const TOPDECK: usize = 1;
+}
+
 #[typetag::serde]
 impl Card for Artisan {
     name!("Artisan");
     card_cost!(6);
     types!(vec![Action]);
-    fn effects_on_play(&self, game: &mut Game, player_index: usize, callbacks: &dyn Callbacks) {
-        let card = callbacks
-            .choose_card_from_supply(player_index, &game.supply)
-            .unwrap();
-        let result = game.gain_to_hand(player_index, card, callbacks);
-        if result.is_err() {
-            // TODO: get new card
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        choose_gain(
+            game,
+            player_index,
+            Then::card(self, player_index, Self::GAIN),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        match resume.step {
+            Self::GAIN => {
+                if let Some(card) = chosen_gain(game, &resume.reply, |c| {
+                    game.effective_cost(c, player_index) <= Cost::new(5)
+                })? {
+                    game.gain_to_hand(player_index, card)?;
+                }
+
+                choose_exact_from_hand(
+                    game,
+                    player_index,
+                    1,
+                    "Choose a card to place on top of your deck",
+                    Then::card(self, player_index, Self::TOPDECK),
+                );
+                Ok(())
+            }
+            Self::TOPDECK => {
+                let Some(&card_index) =
+                    chosen_from_hand(game, player_index, 1, &resume.reply)?.first()
+                else {
+                    return Ok(());
+                };
+                game.move_card(
+                    player_index,
+                    Zone::Hand,
+                    Zone::Deck,
+                    Selector::Index(card_index),
+                )?;
+                Ok(())
+            }
+            _ => Ok(()),
         }
-        let card_index = callbacks.choose_cards_from_hand(
-            &ChoiceCountOptions::Exact { count: 1 },
-            "Choose a card to place on top of your deck",
-        )[0];
-        let card = game.remove_from_hand(player_index, card_index);
-        let player = game.get_player_mut(player_index).unwrap();
-        player.deck.push_front(card);
     }
 }
 
@@ -38,41 +362,86 @@ declare_card!(
     Bandit,
     "[Wiki link](http://wiki.dominionstrategy.com/index.php/Bandit)"
 );
+impl Bandit {
+    const TRASH: usize = 0;
+
+    /// The revealed treasures other than Copper, which can be trashed
+    fn trash_choices(cards: &CardList) -> Vec<usize> {
+        cards
+            .iter()
+            .positions(|card| card.is_treasure() && card.name() != "Copper")
+            .collect()
+    }
+
+    /// Trashes the revealed card at `trash_index`, if any, and discards the
+    /// rest
+    fn trash_and_discard(
+        game: &mut Game,
+        player_index: usize,
+        mut cards: CardList,
+        trash_index: Option<usize>,
+    ) -> Result {
+        if let Some(index) = trash_index {
+            let card = cards.remove(index);
+            move_set_aside(game, player_index, vec![card], Zone::Trash)?;
+        }
+
+        move_set_aside(game, player_index, cards, Zone::Discard)
+    }
+}
+
 #[typetag::serde]
 impl Card for Bandit {
     name!("Bandit");
     card_cost!(5);
     types!(vec![Action, Attack]);
 
-    fn effects_on_play(&self, game: &mut Game, player_index: usize, callbacks: &dyn Callbacks) {
-        let _ = game.gain(player_index, Box::new(Gold), callbacks);
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        gain_if_available(game, player_index, Box::new(Gold), Zone::Discard)
     }
 
     fn attack_target(&self) -> Option<AttackTarget> {
         Some(EveryoneElse)
     }
 
-    fn attack_effects(&self, game: &mut Game, player_index: usize, callbacks: &dyn Callbacks) {
-        let mut cards = game.reveal(player_index, 2);
-        let trash_choices: Vec<usize> = (0..2)
-            .filter(|i| cards[*i].is_treasure() && cards[*i].name() != "Copper")
-            .collect();
-        match trash_choices.len() {
-            1 => {
-                cards.remove(trash_choices[0]);
-            }
-            2 => {
-                let index =
-                    callbacks.choose_cards_from_selection(1, &cards, "Choose a card to trash.")[0];
-                cards.remove(index);
-            }
-            _ => {}
+    fn attack_effects(&self, game: &mut Game, player_index: usize) -> Result {
+        let cards = match game.reveal(player_index, 2) {
+            Ok(cards) => cards,
+            Err(Error::EmptyDeck) => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        let trash_choices = Self::trash_choices(&cards);
+        if trash_choices.len() < 2 {
+            let trash_index = trash_choices.first().copied();
+            return Self::trash_and_discard(game, player_index, cards, trash_index);
         }
 
-        let player = game.get_player_mut(player_index).unwrap();
-        for card in cards {
-            player.discard.push_back(card);
+        game.ask(
+            Ask::selection(
+                player_index,
+                ChoiceCountOptions::Exact { count: 1 },
+                &cards,
+                "Choose a card to trash.",
+            ),
+            Then::card(self, player_index, Self::TRASH).with_cards(cards),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::TRASH {
+            return Ok(());
         }
+
+        // If the player doesn't choose one of the treasures, trash the first
+        // one
+        let trash_choices = Self::trash_choices(&resume.cards);
+        let trash_index = resume
+            .reply
+            .index()
+            .filter(|i| trash_choices.contains(i))
+            .or(trash_choices.first().copied());
+        Self::trash_and_discard(game, player_index, resume.cards, trash_index)
     }
 }
 
@@ -80,7 +449,75 @@ declare_card!(
     Bureaucrat,
     "[Wiki link](http://wiki.dominionstrategy.com/index.php/Bureaucrat)"
 );
-placeholder_effects!(Bureaucrat, "Bureaucrat", 4);
+impl Bureaucrat {
+    const TOPDECK: usize = 0;
+
+    /// Puts the victory card at `index` in the player's hand onto their deck
+    /// and reveals it
+    fn topdeck(game: &mut Game, player_index: usize, index: usize) -> Result {
+        let card = game
+            .move_card(player_index, Zone::Hand, Zone::Deck, Selector::Index(index))?
+            .remove(0);
+        game.reveal_cards(player_index, vec![card])
+    }
+}
+
+#[typetag::serde]
+impl Card for Bureaucrat {
+    name!("Bureaucrat");
+    card_cost!(4);
+    types!(vec![Action, Attack]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        gain_if_available(game, player_index, Box::new(Silver), Zone::Deck)
+    }
+
+    fn attack_target(&self) -> Option<AttackTarget> {
+        Some(EveryoneElse)
+    }
+
+    fn attack_effects(&self, game: &mut Game, player_index: usize) -> Result {
+        let hand = &game.player(player_index)?.hand;
+        let victory_cards: Vec<usize> = hand.iter().positions(|card| card.is_victory()).collect();
+
+        // Players with no victory cards reveal their hand instead
+        match victory_cards.len() {
+            0 => {
+                let cards = hand.iter().cloned().collect();
+                game.reveal_cards(player_index, cards)
+            }
+            1 => Self::topdeck(game, player_index, victory_cards[0]),
+            _ => {
+                choose_exact_from_hand(
+                    game,
+                    player_index,
+                    1,
+                    "Choose a Victory card to put onto your deck",
+                    Then::card(self, player_index, Self::TOPDECK),
+                );
+                Ok(())
+            }
+        }
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::TOPDECK {
+            return Ok(());
+        }
+
+        let hand = &game.player(player_index)?.hand;
+        let victory_cards: Vec<usize> = hand.iter().positions(|card| card.is_victory()).collect();
+        let Some(&first) = victory_cards.first() else {
+            return Ok(());
+        };
+        let index = resume
+            .reply
+            .index()
+            .filter(|i| victory_cards.contains(i))
+            .unwrap_or(first);
+        Self::topdeck(game, player_index, index)
+    }
+}
 
 // Cellar
 // +1 Action, discard any number of cards, then draw that many
@@ -88,23 +525,40 @@ declare_card!(
     Cellar,
     "[Wiki link](http://wiki.dominionstrategy.com/index.php/Cellar)"
 );
+impl Cellar {
+    const DISCARD: usize = 0;
+}
+
 #[typetag::serde]
 impl Card for Cellar {
     name!("Cellar");
     card_cost!(2);
     types!(vec![Action]);
-    fn effects_on_play(&self, game: &mut Game, player_index: usize, callbacks: &dyn Callbacks) {
-        let player = &mut game.players[player_index];
-        let indexes: Vec<usize> = callbacks.choose_cards_from_hand(
-            &ChoiceCountOptions::UpTo {
-                max: player.hand.len(),
-            },
-            "Choose cards to discard",
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        let player = game.player_mut(player_index)?;
+        player.add_actions(1);
+
+        let count = ChoiceCountOptions::UpTo {
+            max: player.hand.len(),
+        };
+        game.ask(
+            Ask::hand(player_index, count, "Choose cards to discard"),
+            Then::card(self, player_index, Self::DISCARD),
         );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::DISCARD {
+            return Ok(());
+        }
+
+        let hand_size = game.player(player_index)?.hand.len();
+        let indexes = chosen_up_to_from_hand(game, player_index, hand_size, &resume.reply)?;
         let count = indexes.len();
+        game.discard_from_hand(player_index, indexes)?;
 
-        player.discard_given_indexes(indexes);
-        player.draw_cards(count);
+        game.draw_cards(player_index, count)
     }
 }
 
@@ -112,19 +566,35 @@ declare_card!(
     Chapel,
     "[Wiki link](http://wiki.dominionstrategy.com/index.php/Chapel)"
 );
+impl Chapel {
+    const TRASH: usize = 0;
+}
+
 #[typetag::serde]
 impl Card for Chapel {
     name!("Chapel");
     card_cost!(2);
     types!(vec![Action]);
 
-    fn effects_on_play(&self, game: &mut Game, player_index: usize, callbacks: &dyn Callbacks) {
-        let player = &mut game.players[player_index];
-        let indexes: Vec<usize> = callbacks.choose_cards_from_hand(
-            &ChoiceCountOptions::UpTo { max: 4 },
-            "Choose up to 4 cards to trash",
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        game.ask(
+            Ask::hand(
+                player_index,
+                ChoiceCountOptions::UpTo { max: 4 },
+                "Choose up to 4 cards to trash",
+            ),
+            Then::card(self, player_index, Self::TRASH),
         );
-        player.trash_given_indexes(indexes, &mut game.trash);
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::TRASH {
+            return Ok(());
+        }
+
+        let indexes = chosen_up_to_from_hand(game, player_index, 4, &resume.reply)?;
+        game.trash_from_hand(player_index, indexes)
     }
 }
 
@@ -139,18 +609,18 @@ impl Card for CouncilRoom {
     name!("Council Room");
     card_cost!(5);
     types!(vec![Action]);
-    fn effects_on_play(&self, game: &mut Game, player_index: usize, _callbacks: &dyn Callbacks) {
-        let player = &mut game.players[player_index];
-        player.draw_cards(4);
-        player.add_buys(1);
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        game.draw_cards(player_index, 4)?;
+        game.player_mut(player_index)?.add_buys(1);
 
         let player_count = game.players.len();
 
         for i in 1..player_count {
             let index = (i + player_index) % player_count;
-            let player = &mut game.players[index];
-            player.draw_cards(1);
+            game.draw_cards(index, 1)?;
         }
+
+        Ok(())
     }
 }
 
@@ -180,7 +650,7 @@ impl Card for Gardens {
 
     //integer division should be fine
     fn victory_points(&self, player: &Player) -> isize {
-        ((player.deck.len() + player.hand.len() + player.discard.len()) / 10) as isize
+        (player.all_cards().count() / 10) as isize
     }
 }
 
@@ -188,22 +658,50 @@ declare_card!(
     Harbinger,
     "[Wiki link](http://wiki.dominionstrategy.com/index.php/Harbinger)"
 );
+impl Harbinger {
+    const TOPDECK: usize = 0;
+}
+
 #[typetag::serde]
 impl Card for Harbinger {
     name!("Harbinger");
     card_cost!(3);
     types!(vec![Action]);
-    fn effects_on_play(&self, game: &mut Game, player_index: usize, callbacks: &dyn Callbacks) {
-        let player = &mut game.players[player_index];
-        player.add_actions(1);
-        player.draw_cards(1);
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        game.player_mut(player_index)?.add_actions(1);
+        game.draw_cards(player_index, 1)?;
 
-        let indexes = callbacks.choose_cards_from_discard(
-            &ChoiceCountOptions::Exact { count: 1 },
-            "Choose a card from your discard to put onto your deck.",
+        game.ask(
+            Ask::discard(
+                player_index,
+                ChoiceCountOptions::UpTo { max: 1 },
+                "Choose a card from your discard to put onto your deck.",
+            ),
+            Then::card(self, player_index, Self::TOPDECK),
         );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::TOPDECK {
+            return Ok(());
+        }
 
-        player.move_given_indexes_discard_to_hand(indexes);
+        // Ask again if the player picks a card that isn't there
+        let discard_size = game.player(player_index)?.discard.len();
+        let index = match resume.reply.indexes() {
+            [] => return Ok(()),
+            &[index] if index < discard_size => index,
+            _ => return Err(Error::InvalidAnswer),
+        };
+
+        game.move_card(
+            player_index,
+            Zone::Discard,
+            Zone::Deck,
+            Selector::Index(index),
+        )?;
+        Ok(())
     }
 }
 
@@ -223,26 +721,63 @@ declare_card!(
     Library,
     "[Wiki link](http://wiki.dominionstrategy.com/index.php/Library)"
 );
+impl Library {
+    const SKIP: usize = 0;
+
+    /// Draws until the player has 7 cards in hand, stopping to ask whether
+    /// to set aside each action. `skipped` are the actions set aside so far
+    fn draw(&self, game: &mut Game, player_index: usize, skipped: CardList) -> Result {
+        while game.player(player_index)?.hand.len() < 7 {
+            let player = game.player(player_index)?;
+            if player.deck.is_empty() && player.discard.is_empty() {
+                break;
+            }
+
+            game.draw_cards(player_index, 1)?;
+
+            // Actions may be set aside instead of being kept in hand
+            if let Some(card) = game.player(player_index)?.hand.back() {
+                if card.is_action() {
+                    game.ask(
+                        Ask::yes_or_no(player_index, &format!("Set aside {card}?")),
+                        Then::card(self, player_index, Self::SKIP).with_cards(skipped),
+                    );
+                    return Ok(());
+                }
+            }
+        }
+
+        move_set_aside(game, player_index, skipped, Zone::Discard)
+    }
+}
+
 #[typetag::serde]
 impl Card for Library {
     name!("Library");
     card_cost!(5);
     types!(vec![Action]);
 
-    fn effects_on_play(&self, game: &mut Game, player_index: usize, callbacks: &dyn Callbacks) {
-        let player = &mut game.players[player_index];
-        let mut extras = CardDeck::new();
-        while player.hand.len() < 7 {
-            if player.deck.front().unwrap().is_action() {
-                if callbacks.yes_or_no(player.player_number, "Skip?") {
-                    extras.push_back(player.deck.pop_front().unwrap());
-                    continue;
-                }
-            } else {
-                player.draw_cards(1);
-            }
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        self.draw(game, player_index, CardList::new())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::SKIP {
+            return Ok(());
+        }
+
+        let mut skipped = resume.cards;
+        if resume.reply.yes() {
+            let index = game.player(player_index)?.hand.len() - 1;
+            skipped.extend(game.move_card(
+                player_index,
+                Zone::Hand,
+                Mat::SetAside.into(),
+                Selector::Index(index),
+            )?);
         }
-        player.discard.append(&mut extras);
+
+        self.draw(game, player_index, skipped)
     }
 }
 
@@ -269,13 +804,22 @@ impl Card for Merchant {
     name!("Merchant");
     card_cost!(3);
     types!(vec![Action]);
-    fn effects_on_play(&self, game: &mut Game, player_index: usize, _: &dyn Callbacks) {
-        let p = game.players.get_mut(player_index).unwrap();
-        p.add_actions(1);
-        p.draw_cards(1);
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        game.draw_cards(player_index, 1)?;
+
+        game.player_mut(player_index)?.add_actions(1);
+
+        // The bonus is added when the first Silver is played
+        game.add_modifier(Modifier::new(
+            player_index,
+            ModifierKind::FirstTreasureBonus {
+                treasure: Silver.name().to_string(),
+                coins: 1,
+            },
+            Lifetime::ThisTurn,
+        ));
 
-        //TODO: add method on game
-        p.state.merchant_bonus += 1;
+        Ok(())
     }
 }
 
@@ -283,13 +827,114 @@ declare_card!(
     Militia,
     "[Wiki link](http://wiki.dominionstrategy.com/index.php/Militia)"
 );
-placeholder_effects!(Militia, "Militia", 4);
+impl Militia {
+    const DISCARD: usize = 0;
+}
+
+#[typetag::serde]
+impl Card for Militia {
+    name!("Militia");
+    card_cost!(4);
+    types!(vec![Action, Attack]);
+    basic_on_play_effects!(cards = 0, actions = 0, buys = 0, coins = 2);
+
+    fn attack_target(&self) -> Option<AttackTarget> {
+        Some(EveryoneElse)
+    }
+
+    fn attack_effects(&self, game: &mut Game, player_index: usize) -> Result {
+        let hand_size = game.player(player_index)?.hand.len();
+        if hand_size <= 3 {
+            return Ok(());
+        }
+
+        choose_exact_from_hand(
+            game,
+            player_index,
+            hand_size - 3,
+            "Choose cards to discard down to 3",
+            Then::card(self, player_index, Self::DISCARD),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::DISCARD {
+            return Ok(());
+        }
+
+        let count = game.player(player_index)?.hand.len().saturating_sub(3);
+        let indexes = chosen_from_hand(game, player_index, count, &resume.reply)?;
+        game.discard_from_hand(player_index, indexes)
+    }
+}
 
 declare_card!(
     Mine,
     "[Wiki link](http://wiki.dominionstrategy.com/index.php/Mine)"
 );
-placeholder_effects!(Mine, "Mine", 5);
+impl Mine {
+    const TRASH: usize = 0;
+    const GAIN: usize = 1;
+}
+
+#[typetag::serde]
+impl Card for Mine {
+    name!("Mine");
+    card_cost!(5);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        game.ask(
+            Ask::hand(
+                player_index,
+                ChoiceCountOptions::UpTo { max: 1 },
+                "Choose a treasure to trash",
+            ),
+            Then::card(self, player_index, Self::TRASH),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        match resume.step {
+            Self::TRASH => {
+                // Choosing nothing does nothing, but a card that isn't a
+                // treasure is an invalid answer
+                let chosen = chosen_up_to_from_hand(game, player_index, 1, &resume.reply)?;
+                let Some(&index) = chosen.first() else {
+                    return Ok(());
+                };
+
+                let trashed = game.player(player_index)?.hand[index].clone();
+                if !trashed.is_treasure() {
+                    return Err(Error::InvalidAnswer);
+                }
+
+                game.trash_from_hand(player_index, vec![index])?;
+
+                let then = Then::card(self, player_index, Self::GAIN);
+                choose_gain(game, player_index, then.with_cards(vec![trashed]));
+                Ok(())
+            }
+            Self::GAIN => {
+                let Some(trashed) = resume.cards.first() else {
+                    return Ok(());
+                };
+                let max_cost = game.effective_cost(&**trashed, player_index) + Cost::new(3);
+                let allowed = |card: &dyn Card| {
+                    card.is_treasure() && game.effective_cost(card, player_index) <= max_cost
+                };
+                if let Some(card) = chosen_gain(game, &resume.reply, allowed)? {
+                    game.gain_to_hand(player_index, card)?;
+                }
+
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
 
 declare_card!(
     Moat,
@@ -300,18 +945,12 @@ impl Card for Moat {
     name!("Moat");
     card_cost!(2);
     types!(vec![Action, Reaction]);
-    fn effects_on_play(&self, game: &mut Game, player_index: usize, _callbacks: &dyn Callbacks) {
-        let p = game.players.get_mut(player_index).unwrap();
-        p.draw_cards(2);
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        game.draw_cards(player_index, 2)
     }
 
-    fn reaction_effects(&self, game: &mut Game, player_index: usize, callbacks: &dyn Callbacks) {
-        // TODO: Fix this to make it a choice per attack, rather than making
-        // the player completely immune until their next turn
-        if callbacks.yes_or_no(player_index, "Use moat?") {
-            let p = game.players.get_mut(player_index).unwrap();
-            p.state.immune = true;
-        }
+    fn reaction_effects(&self, game: &mut Game, player_index: usize) -> Result {
+        game.block_attack(player_index)
     }
 
     fn reaction_trigger(&self) -> Option<ReactionTrigger> {
@@ -323,25 +962,218 @@ declare_card!(
     Moneylender,
     "[Wiki link](http://wiki.dominionstrategy.com/index.php/Moneylender)"
 );
-placeholder_effects!(Moneylender, "Moneylender", 4);
+impl Moneylender {
+    const TRASH: usize = 0;
+
+    /// Where the player's first Copper is in their hand, if they have one
+    fn copper(game: &Game, player_index: usize) -> Result<Option<usize>> {
+        Ok(game
+            .player(player_index)?
+            .hand
+            .iter()
+            .position(|card| card.name() == Copper.name()))
+    }
+}
+
+#[typetag::serde]
+impl Card for Moneylender {
+    name!("Moneylender");
+    card_cost!(4);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        if Self::copper(game, player_index)?.is_some() {
+            game.ask(
+                Ask::yes_or_no(player_index, "Trash a Copper for +3 coins?"),
+                Then::card(self, player_index, Self::TRASH),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::TRASH || !resume.reply.yes() {
+            return Ok(());
+        }
+
+        if let Some(index) = Self::copper(game, player_index)? {
+            game.trash_from_hand(player_index, vec![index])?;
+            game.player_mut(player_index)?.add_coins(3);
+        }
+
+        Ok(())
+    }
+}
 
 declare_card!(
     Poacher,
     "[Wiki link](http://wiki.dominionstrategy.com/index.php/Poacher)"
 );
-placeholder_effects!(Poacher, "Poacher", 4);
+impl Poacher {
+    const DISCARD: usize = 0;
+}
+
+#[typetag::serde]
+impl Card for Poacher {
+    name!("Poacher");
+    card_cost!(4);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        game.draw_cards(player_index, 1)?;
+        let player = game.player_mut(player_index)?;
+        player.add_actions(1);
+        player.add_coins(1);
+
+        // Discard a card per empty supply pile
+        let empty_piles = game.supply.empty_piles();
+        if empty_piles == 0 {
+            return Ok(());
+        }
+
+        choose_exact_from_hand(
+            game,
+            player_index,
+            empty_piles,
+            "Choose cards to discard",
+            Then::card(self, player_index, Self::DISCARD),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::DISCARD {
+            return Ok(());
+        }
+
+        let empty_piles = game.supply.empty_piles();
+        let indexes = chosen_from_hand(game, player_index, empty_piles, &resume.reply)?;
+        game.discard_from_hand(player_index, indexes)
+    }
+}
 
 declare_card!(
     Remodel,
     "[Wiki link](http://wiki.dominionstrategy.com/index.php/Remodel)"
 );
-placeholder_effects!(Remodel, "Remodel", 4);
+impl Remodel {
+    const TRASH: usize = 0;
+    const GAIN: usize = 1;
+}
+
+#[typetag::serde]
+impl Card for Remodel {
+    name!("Remodel");
+    card_cost!(4);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        choose_exact_from_hand(
+            game,
+            player_index,
+            1,
+            "Choose a card to trash",
+            Then::card(self, player_index, Self::TRASH),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        match resume.step {
+            Self::TRASH => {
+                let Some(&index) = chosen_from_hand(game, player_index, 1, &resume.reply)?.first()
+                else {
+                    return Ok(());
+                };
+
+                let trashed = game.player(player_index)?.hand[index].clone();
+                game.trash_from_hand(player_index, vec![index])?;
+
+                let then = Then::card(self, player_index, Self::GAIN);
+                choose_gain(game, player_index, then.with_cards(vec![trashed]));
+                Ok(())
+            }
+            Self::GAIN => {
+                let Some(trashed) = resume.cards.first() else {
+                    return Ok(());
+                };
+                let max_cost = game.effective_cost(&**trashed, player_index) + Cost::new(2);
+                if let Some(card) = chosen_gain(game, &resume.reply, |c| {
+                    game.effective_cost(c, player_index) <= max_cost
+                })? {
+                    game.gain(player_index, card)?;
+                }
+
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
 
 declare_card!(
     Sentry,
     "[Wiki link](http://wiki.dominionstrategy.com/index.php/Sentry)"
 );
-placeholder_effects!(Sentry, "Sentry", 5);
+impl Sentry {
+    const TRASH: usize = 0;
+    const DISCARD: usize = 1;
+    const ORDER: usize = 2;
+}
+
+#[typetag::serde]
+impl Card for Sentry {
+    name!("Sentry");
+    card_cost!(5);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        game.draw_cards(player_index, 1)?;
+        game.player_mut(player_index)?.add_actions(1);
+
+        let cards = game.take_from_deck(player_index, 2)?;
+        choose_from_selection(
+            game,
+            player_index,
+            &cards,
+            "Choose cards to trash",
+            Then::card(self, player_index, Self::TRASH).with_cards(cards.clone()),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        match resume.step {
+            Self::TRASH => {
+                let mut cards = resume.cards;
+                let trash = chosen_from_selection(&resume.reply, cards.len())?;
+                let trashed = take_selected(&mut cards, &trash);
+                move_set_aside(game, player_index, trashed, Zone::Trash)?;
+
+                choose_from_selection(
+                    game,
+                    player_index,
+                    &cards,
+                    "Choose cards to discard",
+                    Then::card(self, player_index, Self::DISCARD).with_cards(cards.clone()),
+                );
+                Ok(())
+            }
+            Self::DISCARD => {
+                let mut cards = resume.cards;
+                let discard = chosen_from_selection(&resume.reply, cards.len())?;
+                let discarded = take_selected(&mut cards, &discard);
+                move_set_aside(game, player_index, discarded, Zone::Discard)?;
+
+                let then = Then::card(self, player_index, Self::ORDER);
+                put_back_in_order(game, player_index, cards, then)
+            }
+            Self::ORDER => put_back_chosen(game, player_index, resume),
+            _ => Ok(()),
+        }
+    }
+}
 
 basic_action!(
     Smithy,
@@ -358,35 +1190,32 @@ declare_card!(
     ThroneRoom,
     "[Wiki link](http://wiki.dominionstrategy.com/index.php/Throne_Room)"
 );
+impl ThroneRoom {
+    const PLAY: usize = 0;
+}
+
 #[typetag::serde]
 impl Card for ThroneRoom {
     name!("Throne Room");
     card_cost!(4);
     types!(vec![Action]);
 
-    fn effects_on_play(&self, game: &mut Game, player_index: usize, callbacks: &dyn Callbacks) {
-        let card_index = callbacks.choose_cards_from_hand(
-            &ChoiceCountOptions::UpTo { max: 1 },
-            "Choose card to play twice",
-        )[0];
-
-        let player = &mut game.players[player_index];
-        let mut card = player.hand.remove(card_index).unwrap();
-
-        while !card.is_action() {
-            let card_index = callbacks.choose_cards_from_hand(
-                &ChoiceCountOptions::UpTo { max: 1 },
-                "Choose card to play twice",
-            )[0];
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        play_action_times(
+            game,
+            player_index,
+            2,
+            Then::card(self, player_index, Self::PLAY),
+        );
+        Ok(())
+    }
 
-            card = player.hand.remove(card_index).unwrap();
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::PLAY {
+            return Ok(());
         }
 
-        game.action_effects(player_index, &*card, callbacks);
-        game.action_effects(player_index, &*card, callbacks);
-
-        let player = &mut game.players[player_index];
-        player.in_play.push_back(card);
+        play_chosen_action(game, player_index, 2, &resume.reply)
     }
 }
 
@@ -394,7 +1223,61 @@ declare_card!(
     Vassal,
     "[Wiki link](http://wiki.dominionstrategy.com/index.php/Vassal)"
 );
-placeholder_effects!(Vassal, "Vassal", 3);
+impl Vassal {
+    const PLAY: usize = 0;
+}
+
+#[typetag::serde]
+impl Card for Vassal {
+    name!("Vassal");
+    card_cost!(3);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        game.player_mut(player_index)?.add_coins(2);
+
+        // The card is discarded first, whether or not it is played
+        let Some(card) = game
+            .move_card(player_index, Zone::Deck, Zone::Discard, Selector::Top(1))?
+            .pop()
+        else {
+            return Ok(());
+        };
+
+        // Playing the discarded action doesn't use up an action
+        if card.is_action() {
+            game.ask(
+                Ask::yes_or_no(player_index, &format!("Play {card}?")),
+                Then::card(self, player_index, Self::PLAY).with_cards(vec![card]),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::PLAY || !resume.reply.yes() {
+            return Ok(());
+        }
+        let Some(card) = resume.cards.into_iter().next() else {
+            return Ok(());
+        };
+
+        // It can only be played if it is still in the discard pile
+        match game.move_card(
+            player_index,
+            Zone::Discard,
+            Zone::InPlay,
+            Selector::Card(card.clone()),
+        ) {
+            Err(Error::CardNotFound { .. }) => Ok(()),
+            result => {
+                result?;
+                game.action_effects(player_index, &*card)
+            }
+        }
+    }
+}
 
 basic_action!(
     Village,
@@ -422,8 +1305,8 @@ impl Card for Witch {
         Some(EveryoneElse)
     }
 
-    fn attack_effects(&self, game: &mut Game, player_index: usize, callbacks: &dyn Callbacks) {
-        let _ = game.gain(player_index, Box::new(BasicCurse), callbacks);
+    fn attack_effects(&self, game: &mut Game, player_index: usize) -> Result {
+        gain_if_available(game, player_index, Box::new(BasicCurse), Zone::Discard)
     }
 }
 
@@ -431,13 +1314,36 @@ declare_card!(
     Workshop,
     "[Wiki link](http://wiki.dominionstrategy.com/index.php/Workshop)"
 );
+impl Workshop {
+    const GAIN: usize = 0;
+}
+
 #[typetag::serde]
 impl Card for Workshop {
     name!("Workshop");
     card_cost!(3);
     types!(vec![Action]);
 
-    fn effects_on_play(&self, game: &mut Game, player_index: usize, callbacks: &dyn Callbacks) {
-        todo!();
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        choose_gain(
+            game,
+            player_index,
+            Then::card(self, player_index, Self::GAIN),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::GAIN {
+            return Ok(());
+        }
+
+        if let Some(card) = chosen_gain(game, &resume.reply, |c| {
+            game.effective_cost(c, player_index) <= Cost::new(4)
+        })? {
+            game.gain(player_index, card)?;
+        }
+
+        Ok(())
     }
 }
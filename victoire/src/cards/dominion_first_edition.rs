@@ -0,0 +1,333 @@
+//! Cards from the original Dominion set that were removed in the 2nd edition
+
+#![allow(clippy::wildcard_imports)]
+
+use itertools::Itertools;
+
+use super::dominion::*;
+use super::prelude::*;
+
+/// The kingdom cards in the 1st edition of the base set
+pub fn kingdom_cards() -> CardList {
+    card_vec![
+        Adventurer,
+        Bureaucrat,
+        Cellar,
+        Chancellor,
+        Chapel,
+        CouncilRoom,
+        Feast,
+        Festival,
+        Gardens,
+        Laboratory,
+        Library,
+        Market,
+        Militia,
+        Mine,
+        Moat,
+        Moneylender,
+        Remodel,
+        Smithy,
+        Spy,
+        Thief,
+        ThroneRoom,
+        Village,
+        Witch,
+        Woodcutter,
+        Workshop
+    ]
+}
+
+// Adventurer
+// Reveal cards from your deck until you reveal 2 treasures. Put those into
+// your hand and discard the other revealed cards
+declare_card!(
+    Adventurer,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Adventurer)"
+);
+#[typetag::serde]
+impl Card for Adventurer {
+    name!("Adventurer");
+    card_cost!(6);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        let mut treasures = CardList::new();
+        let mut others = CardList::new();
+
+        // Revealed cards are set aside, so they aren't shuffled back in if
+        // the deck runs out
+        while treasures.len() < 2 {
+            let card = match game.reveal(player_index, 1) {
+                Ok(mut cards) => cards.remove(0),
+                Err(Error::EmptyDeck) => break,
+                Err(e) => return Err(e),
+            };
+
+            if card.is_treasure() {
+                treasures.push(card);
+            } else {
+                others.push(card);
+            }
+        }
+
+        move_set_aside(game, player_index, treasures, Zone::Hand)?;
+        move_set_aside(game, player_index, others, Zone::Discard)
+    }
+}
+
+// Chancellor
+// +2 coins, you may immediately put your deck into your discard pile
+declare_card!(
+    Chancellor,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Chancellor)"
+);
+impl Chancellor {
+    const DISCARD_DECK: usize = 0;
+}
+
+#[typetag::serde]
+impl Card for Chancellor {
+    name!("Chancellor");
+    card_cost!(3);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        game.player_mut(player_index)?.add_coins(2);
+
+        game.ask(
+            Ask::yes_or_no(player_index, "Put your deck into your discard pile?"),
+            Then::card(self, player_index, Self::DISCARD_DECK),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step == Self::DISCARD_DECK && resume.reply.yes() {
+            game.move_card(player_index, Zone::Deck, Zone::Discard, Selector::All)?;
+        }
+
+        Ok(())
+    }
+}
+
+// Feast
+// Trash this card, gain a card costing up to 5 coins
+declare_card!(
+    Feast,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Feast)"
+);
+impl Feast {
+    const GAIN: usize = 0;
+}
+
+#[typetag::serde]
+impl Card for Feast {
+    name!("Feast");
+    card_cost!(4);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        // Played a second time (e.g. with Throne Room), it has already been
+        // trashed, but the card is still gained
+        trash_from_play(game, player_index, self)?;
+
+        choose_gain(
+            game,
+            player_index,
+            Then::card(self, player_index, Self::GAIN),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::GAIN {
+            return Ok(());
+        }
+
+        if let Some(card) = chosen_gain(game, &resume.reply, |c| {
+            game.effective_cost(c, player_index) <= Cost::new(5)
+        })? {
+            game.gain(player_index, card)?;
+        }
+
+        Ok(())
+    }
+}
+
+// Spy
+// +1 card, +1 action, each player (including you) reveals the top card of
+// their deck and either discards it or puts it back, your choice
+declare_card!(
+    Spy,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Spy)"
+);
+impl Spy {
+    const DISCARD: usize = 0;
+
+    /// Reveals the top card of the target's deck and asks the spy whether it
+    /// is discarded or put back
+    fn spy_on(&self, game: &mut Game, spy: usize, target: usize) -> Result {
+        let card = match game.reveal(target, 1) {
+            Ok(mut cards) => cards.remove(0),
+            Err(Error::EmptyDeck) => return Ok(()),
+            Err(e) => return Err(e),
+        };
+
+        let prompt = format!("Discard {card} from the top of player {target}'s deck?");
+        game.ask(
+            Ask::yes_or_no(spy, &prompt),
+            Then::card(self, target, Self::DISCARD).with_cards(vec![card]),
+        );
+        Ok(())
+    }
+}
+
+#[typetag::serde]
+impl Card for Spy {
+    name!("Spy");
+    card_cost!(4);
+    types!(vec![Action, Attack]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        game.draw_cards(player_index, 1)?;
+        game.player_mut(player_index)?.add_actions(1);
+
+        self.spy_on(game, player_index, player_index)
+    }
+
+    fn attack_target(&self) -> Option<AttackTarget> {
+        Some(EveryoneElse)
+    }
+
+    fn attack_effects(&self, game: &mut Game, player_index: usize) -> Result {
+        self.spy_on(game, game.current_turn, player_index)
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::DISCARD {
+            return Ok(());
+        }
+
+        let to = if resume.reply.yes() {
+            Zone::Discard
+        } else {
+            Zone::Deck
+        };
+
+        move_set_aside(game, player_index, resume.cards, to)
+    }
+}
+
+// Thief
+// Each other player reveals the top 2 cards of their deck. If they revealed
+// any treasures, they trash one that you choose. You may gain any of the
+// trashed treasures. They discard the other revealed cards
+declare_card!(
+    Thief,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Thief)"
+);
+impl Thief {
+    const TRASH: usize = 0;
+    const GAIN: usize = 1;
+
+    /// Trashes the revealed card at `trash_index`, if any, asking the thief
+    /// whether to gain it. The rest are discarded
+    fn trash(
+        &self,
+        game: &mut Game,
+        player_index: usize,
+        mut cards: CardList,
+        trash_index: Option<usize>,
+    ) -> Result {
+        let Some(index) = trash_index else {
+            return move_set_aside(game, player_index, cards, Zone::Discard);
+        };
+
+        let card = cards.remove(index);
+        let prompt = format!("Gain the trashed {card}?");
+        move_set_aside(game, player_index, vec![card], Zone::Trash)?;
+
+        game.ask(
+            Ask::yes_or_no(game.current_turn, &prompt),
+            Then::card(self, player_index, Self::GAIN).with_cards(cards),
+        );
+        Ok(())
+    }
+}
+
+#[typetag::serde]
+impl Card for Thief {
+    name!("Thief");
+    card_cost!(4);
+    types!(vec![Action, Attack]);
+
+    fn attack_target(&self) -> Option<AttackTarget> {
+        Some(EveryoneElse)
+    }
+
+    fn attack_effects(&self, game: &mut Game, player_index: usize) -> Result {
+        let cards = match game.reveal(player_index, 2) {
+            Ok(cards) => cards,
+            Err(Error::EmptyDeck) => return Ok(()),
+            Err(e) => return Err(e),
+        };
+
+        let treasures: Vec<usize> = cards.iter().positions(|card| card.is_treasure()).collect();
+        if treasures.len() < 2 {
+            let trash_index = treasures.first().copied();
+            return self.trash(game, player_index, cards, trash_index);
+        }
+
+        game.ask(
+            Ask::selection(
+                game.current_turn,
+                ChoiceCountOptions::Exact { count: 1 },
+                &cards,
+                "Choose a treasure to trash",
+            ),
+            Then::card(self, player_index, Self::TRASH).with_cards(cards),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        match resume.step {
+            Self::TRASH => {
+                // If the thief doesn't choose one of the treasures, trash the
+                // first one
+                let treasures: Vec<usize> = resume
+                    .cards
+                    .iter()
+                    .positions(|card| card.is_treasure())
+                    .collect();
+                let trash_index = resume
+                    .reply
+                    .index()
+                    .filter(|i| treasures.contains(i))
+                    .or(treasures.first().copied());
+                self.trash(game, player_index, resume.cards, trash_index)
+            }
+            Self::GAIN => {
+                if resume.reply.yes() {
+                    // The card just trashed is on top of the trash
+                    game.gain_from_trash(game.current_turn, game.trash.len() - 1)?;
+                }
+
+                move_set_aside(game, player_index, resume.cards, Zone::Discard)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+basic_action!(
+    Woodcutter,
+    "Woodcutter",
+    cost = 3,
+    cards = 0,
+    actions = 0,
+    buys = 1,
+    coins = 2,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Woodcutter)"
+);
@@ -0,0 +1,88 @@
+//! Cards from the Hinterlands expansion (2nd edition)
+
+#![allow(clippy::wildcard_imports)]
+
+use super::base::*;
+use super::dominion::gain_if_available;
+use super::prelude::*;
+
+/// The kingdom cards from Hinterlands that are implemented so far
+pub fn kingdom_cards() -> CardList {
+    card_vec![Highway, Tunnel]
+}
+
+// Highway
+// +1 card, +1 action, while this is in play, cards cost 1 coin less
+declare_card!(
+    Highway,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Highway)"
+);
+#[typetag::serde]
+impl Card for Highway {
+    name!("Highway");
+    card_cost!(5);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        game.draw_cards(player_index, 1)?;
+        game.player_mut(player_index)?.add_actions(1);
+
+        // The reduction lasts while this copy is in play, and playing it
+        // again, e.g. with Throne Room, doesn't add a second one
+        let lifetime = match self.id() {
+            Some(id) => Lifetime::WhileInPlay(id),
+            None => Lifetime::ThisTurn,
+        };
+        if game
+            .modifiers_for(player_index)
+            .all(|m| m.lifetime != lifetime)
+        {
+            game.add_modifier(Modifier::new(
+                player_index,
+                ModifierKind::CostReduction {
+                    coins: 1,
+                    card_type: None,
+                },
+                lifetime,
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+// Tunnel
+// 2 VP, when you discard this other than during clean-up, you may reveal it
+// to gain a Gold
+declare_card!(
+    Tunnel,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Tunnel)"
+);
+impl Tunnel {
+    const REVEAL: usize = 0;
+}
+
+#[typetag::serde]
+impl Card for Tunnel {
+    name!("Tunnel");
+    card_cost!(3);
+    types!(vec![Victory, Reaction]);
+    victory_points!(2);
+
+    fn effects_on_discard(&self, game: &mut Game, player_index: usize) -> Result {
+        game.ask(
+            Ask::yes_or_no(player_index, "Reveal Tunnel to gain a Gold?"),
+            Then::card(self, player_index, Self::REVEAL),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::REVEAL || !resume.reply.yes() {
+            return Ok(());
+        }
+
+        game.reveal_cards(player_index, vec![dyn_clone::clone_box(self)])?;
+        gain_if_available(game, player_index, Box::new(Gold), Zone::Discard)
+    }
+}
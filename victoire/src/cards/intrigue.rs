@@ -0,0 +1,1446 @@
+//! Cards from the Intrigue expansion (2nd edition)
+
+#![allow(clippy::wildcard_imports)]
+
+use itertools::Itertools;
+
+use super::base::*;
+use super::dominion::{
+    choose_exact_from_hand, choose_gain, chosen_from_hand, chosen_gain, chosen_up_to_from_hand,
+    gain_if_available, move_set_aside, put_back_chosen, put_back_in_order, trash_from_play,
+};
+use super::prelude::*;
+
+/// The kingdom cards in the 2nd edition of Intrigue
+pub fn kingdom_cards() -> CardList {
+    card_vec![
+        Baron,
+        Bridge,
+        Conspirator,
+        Courtier,
+        Courtyard,
+        Diplomat,
+        Duke,
+        Harem,
+        Ironworks,
+        Lurker,
+        Masquerade,
+        Mill,
+        MiningVillage,
+        Minion,
+        Nobles,
+        Patrol,
+        Pawn,
+        Replace,
+        SecretPassage,
+        ShantyTown,
+        Steward,
+        Swindler,
+        Torturer,
+        TradingPost,
+        Upgrade,
+        WishingWell
+    ]
+}
+
+/// Asks the player to choose `count` different options, carrying on with
+/// `then`, where the choice is read with [`chosen_options`]
+fn choose_different_options(
+    game: &mut Game,
+    player_index: usize,
+    count: usize,
+    options: &[&str],
+    prompt: &str,
+    then: Then,
+) {
+    let count = ChoiceCountOptions::Exact { count };
+    game.ask(Ask::options(player_index, count, options, prompt), then);
+}
+
+/// The `count` different options the player chose out of `option_count`.
+/// Any other answer is an invalid answer, as for [`chosen_from_hand`]
+fn chosen_options(reply: &Reply, count: usize, option_count: usize) -> Result<Vec<usize>> {
+    let count = count.min(option_count);
+    let chosen = reply.indexes();
+
+    if chosen.len() == count
+        && chosen.iter().all_unique()
+        && chosen.iter().all(|&i| i < option_count)
+    {
+        Ok(chosen.to_vec())
+    } else {
+        Err(Error::InvalidAnswer)
+    }
+}
+
+/// Asks the player to choose one of the options, carrying on with `then`,
+/// where the choice is read with [`chosen_option`]
+fn choose_option(game: &mut Game, player_index: usize, options: &[&str], prompt: &str, then: Then) {
+    choose_different_options(game, player_index, 1, options, prompt, then);
+}
+
+/// The option the player chose out of `option_count`, as for
+/// [`chosen_options`]
+fn chosen_option(reply: &Reply, option_count: usize) -> Result<usize> {
+    match reply.indexes() {
+        &[index] if index < option_count => Ok(index),
+        _ => Err(Error::InvalidAnswer),
+    }
+}
+
+// Baron
+// +1 buy, you may discard an Estate for +4 coins. If you don't, gain an Estate
+declare_card!(
+    Baron,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Baron)"
+);
+impl Baron {
+    const DISCARD: usize = 0;
+
+    fn estate(game: &Game, player_index: usize) -> Result<Option<usize>> {
+        Ok(game
+            .player(player_index)?
+            .hand
+            .iter()
+            .position(|c| c.name() == Estate.name()))
+    }
+}
+
+#[typetag::serde]
+impl Card for Baron {
+    name!("Baron");
+    card_cost!(4);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        game.player_mut(player_index)?.add_buys(1);
+
+        if Self::estate(game, player_index)?.is_some() {
+            game.ask(
+                Ask::yes_or_no(player_index, "Discard an Estate for +4 coins?"),
+                Then::card(self, player_index, Self::DISCARD),
+            );
+            return Ok(());
+        }
+
+        gain_if_available(game, player_index, Box::new(Estate), Zone::Discard)
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::DISCARD {
+            return Ok(());
+        }
+
+        if resume.reply.yes() {
+            if let Some(index) = Self::estate(game, player_index)? {
+                game.discard_from_hand(player_index, vec![index])?;
+                game.player_mut(player_index)?.add_coins(4);
+                return Ok(());
+            }
+        }
+
+        gain_if_available(game, player_index, Box::new(Estate), Zone::Discard)
+    }
+}
+
+// Bridge
+// +1 buy, +1 coin, this turn cards cost 1 coin less
+declare_card!(
+    Bridge,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Bridge)"
+);
+#[typetag::serde]
+impl Card for Bridge {
+    name!("Bridge");
+    card_cost!(4);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        let player = game.player_mut(player_index)?;
+        player.add_buys(1);
+        player.add_coins(1);
+        game.add_modifier(Modifier::new(
+            player_index,
+            ModifierKind::CostReduction {
+                coins: 1,
+                card_type: None,
+            },
+            Lifetime::ThisTurn,
+        ));
+
+        Ok(())
+    }
+}
+
+// Conspirator
+// +2 coins, if you've played 3 or more actions this turn (counting this),
+// +1 card and +1 action
+declare_card!(
+    Conspirator,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Conspirator)"
+);
+#[typetag::serde]
+impl Card for Conspirator {
+    name!("Conspirator");
+    card_cost!(4);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        let player = game.player_mut(player_index)?;
+        player.add_coins(2);
+
+        if player.state.actions_played >= 3 {
+            player.add_actions(1);
+            game.draw_cards(player_index, 1)?;
+        }
+
+        Ok(())
+    }
+}
+
+// Courtier
+// Reveal a card from your hand. For each type it has, choose one different
+// option: +1 action, +1 buy, +3 coins, or gain a Gold
+declare_card!(
+    Courtier,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Courtier)"
+);
+impl Courtier {
+    const REVEAL: usize = 0;
+    const CHOOSE: usize = 1;
+    const OPTIONS: [&'static str; 4] = ["+1 action", "+1 buy", "+3 coins", "Gain a Gold"];
+}
+
+#[typetag::serde]
+impl Card for Courtier {
+    name!("Courtier");
+    card_cost!(5);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        choose_exact_from_hand(
+            game,
+            player_index,
+            1,
+            "Choose a card to reveal",
+            Then::card(self, player_index, Self::REVEAL),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        match resume.step {
+            Self::REVEAL => {
+                let Some(&index) = chosen_from_hand(game, player_index, 1, &resume.reply)?.first()
+                else {
+                    return Ok(());
+                };
+
+                let card = game.player(player_index)?.hand[index].clone();
+                let type_count = card.types().len();
+                game.reveal_cards(player_index, vec![card.clone()])?;
+
+                choose_different_options(
+                    game,
+                    player_index,
+                    type_count,
+                    &Self::OPTIONS,
+                    "Choose one option for each type of the revealed card",
+                    Then::card(self, player_index, Self::CHOOSE).with_cards(vec![card]),
+                );
+                Ok(())
+            }
+            Self::CHOOSE => {
+                let type_count = resume.cards.first().map_or(0, |card| card.types().len());
+                for option in chosen_options(&resume.reply, type_count, Self::OPTIONS.len())? {
+                    let player = game.player_mut(player_index)?;
+                    match option {
+                        0 => player.add_actions(1),
+                        1 => player.add_buys(1),
+                        2 => player.add_coins(3),
+                        _ => {
+                            gain_if_available(game, player_index, Box::new(Gold), Zone::Discard)?;
+                        }
+                    }
+                }
+
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+// Courtyard
+// +3 cards, put a card from your hand onto your deck
+declare_card!(
+    Courtyard,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Courtyard)"
+);
+impl Courtyard {
+    const TOPDECK: usize = 0;
+}
+
+#[typetag::serde]
+impl Card for Courtyard {
+    name!("Courtyard");
+    card_cost!(2);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        game.draw_cards(player_index, 3)?;
+
+        choose_exact_from_hand(
+            game,
+            player_index,
+            1,
+            "Choose a card to put onto your deck",
+            Then::card(self, player_index, Self::TOPDECK),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::TOPDECK {
+            return Ok(());
+        }
+
+        if let Some(&index) = chosen_from_hand(game, player_index, 1, &resume.reply)?.first() {
+            game.move_card(player_index, Zone::Hand, Zone::Deck, Selector::Index(index))?;
+        }
+
+        Ok(())
+    }
+}
+
+// Diplomat
+// +2 cards, if you have 5 or fewer cards in hand after drawing, +2 actions.
+// When another player plays an attack, you may first reveal this from a hand
+// of 5 or more cards, to draw 2 cards then discard 3
+declare_card!(
+    Diplomat,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Diplomat)"
+);
+impl Diplomat {
+    const DISCARD: usize = 0;
+}
+
+#[typetag::serde]
+impl Card for Diplomat {
+    name!("Diplomat");
+    card_cost!(4);
+    types!(vec![Action, Reaction]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        game.draw_cards(player_index, 2)?;
+
+        let player = game.player_mut(player_index)?;
+        if player.hand.len() <= 5 {
+            player.add_actions(2);
+        }
+
+        Ok(())
+    }
+
+    fn reaction_effects(&self, game: &mut Game, player_index: usize) -> Result {
+        if game.player(player_index)?.hand.len() < 5 {
+            return Ok(());
+        }
+
+        game.draw_cards(player_index, 2)?;
+        choose_exact_from_hand(
+            game,
+            player_index,
+            3,
+            "Choose cards to discard",
+            Then::card(self, player_index, Self::DISCARD),
+        );
+        Ok(())
+    }
+
+    fn reaction_trigger(&self) -> Option<ReactionTrigger> {
+        Some(OtherPlayerPlaysAttack)
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::DISCARD {
+            return Ok(());
+        }
+
+        let indexes = chosen_from_hand(game, player_index, 3, &resume.reply)?;
+        game.discard_from_hand(player_index, indexes)
+    }
+}
+
+// Duke
+// Worth 1 VP per Duchy you have
+declare_card!(
+    Duke,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Duke)"
+);
+#[typetag::serde]
+impl Card for Duke {
+    name!("Duke");
+    card_cost!(5);
+    types!(vec![Victory]);
+
+    fn victory_points(&self, player: &Player) -> isize {
+        player
+            .all_cards()
+            .filter(|c| c.name() == Duchy.name())
+            .count() as isize
+    }
+}
+
+// Harem
+// Worth 2 coins and 2 VP
+declare_card!(
+    Harem,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Harem)"
+);
+#[typetag::serde]
+impl Card for Harem {
+    name!("Harem");
+    card_cost!(6);
+    types!(vec![Treasure, Victory]);
+    treasure_value!(2);
+    victory_points!(2);
+}
+
+// Ironworks
+// Gain a card costing up to 4 coins. If it is an Action, +1 action; a
+// Treasure, +1 coin; a Victory card, +1 card
+declare_card!(
+    Ironworks,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Ironworks)"
+);
+impl Ironworks {
+    const GAIN: usize = 0;
+    const BONUS: usize = 1;
+}
+
+#[typetag::serde]
+impl Card for Ironworks {
+    name!("Ironworks");
+    card_cost!(4);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        choose_gain(
+            game,
+            player_index,
+            Then::card(self, player_index, Self::GAIN),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        match resume.step {
+            Self::GAIN => {
+                let Some(card) = chosen_gain(game, &resume.reply, |c| {
+                    game.effective_cost(c, player_index) <= Cost::new(4)
+                })?
+                else {
+                    return Ok(());
+                };
+
+                game.gain(player_index, card.clone())?;
+
+                // The bonus comes once the card has been gained
+                let then = Then::card(self, player_index, Self::BONUS);
+                game.then(then.with_cards(vec![card]));
+                Ok(())
+            }
+            Self::BONUS => {
+                let Some(card) = resume.cards.first() else {
+                    return Ok(());
+                };
+
+                let player = game.player_mut(player_index)?;
+                if card.is_action() {
+                    player.add_actions(1);
+                }
+                if card.is_treasure() {
+                    player.add_coins(1);
+                }
+                if card.is_victory() {
+                    game.draw_cards(player_index, 1)?;
+                }
+
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+// Lurker
+// +1 action, choose one: trash an Action card from the supply, or gain an
+// Action card from the trash
+declare_card!(
+    Lurker,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Lurker)"
+);
+impl Lurker {
+    const CHOOSE: usize = 0;
+    const TRASH: usize = 1;
+    const GAIN: usize = 2;
+    const OPTIONS: [&'static str; 2] = [
+        "Trash an Action card from the supply",
+        "Gain an Action card from the trash",
+    ];
+}
+
+#[typetag::serde]
+impl Card for Lurker {
+    name!("Lurker");
+    card_cost!(2);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        game.player_mut(player_index)?.add_actions(1);
+
+        choose_option(
+            game,
+            player_index,
+            &Self::OPTIONS,
+            "Choose one",
+            Then::card(self, player_index, Self::CHOOSE),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        match resume.step {
+            Self::CHOOSE => {
+                if chosen_option(&resume.reply, Self::OPTIONS.len())? == 0 {
+                    choose_gain(
+                        game,
+                        player_index,
+                        Then::card(self, player_index, Self::TRASH),
+                    );
+                } else {
+                    game.ask(
+                        Ask::trash(
+                            player_index,
+                            ChoiceCountOptions::UpTo { max: 1 },
+                            "Choose an Action card to gain",
+                        ),
+                        Then::card(self, player_index, Self::GAIN),
+                    );
+                }
+
+                Ok(())
+            }
+            Self::TRASH => {
+                if let Some(card) = chosen_gain(game, &resume.reply, |c| c.is_action())? {
+                    game.trash_from_supply(player_index, card)?;
+                }
+
+                Ok(())
+            }
+            Self::GAIN => {
+                let index = match resume.reply.indexes() {
+                    [] => return Ok(()),
+                    &[index] if game.trash.get(index).is_some_and(|c| c.is_action()) => index,
+                    _ => return Err(Error::InvalidAnswer),
+                };
+
+                game.gain_from_trash(player_index, index)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+// Masquerade
+// +2 cards. Each player with any cards in hand passes one to the next such
+// player to their left, at once. Then you may trash a card from your hand
+declare_card!(
+    Masquerade,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Masquerade)"
+);
+impl Masquerade {
+    const TRASH: usize = 0;
+    /// Each player's choice of card to pass is its own step, starting from
+    /// this one with the player who played Masquerade
+    const PASS: usize = 1;
+
+    /// Asks the player `offset` places to the left of the one who played
+    /// Masquerade for the card to pass. `passed` are the cards chosen so far
+    fn choose_pass(&self, game: &mut Game, player_index: usize, offset: usize, passed: CardList) {
+        let index = (player_index + offset) % game.player_count();
+        choose_exact_from_hand(
+            game,
+            index,
+            1,
+            "Choose a card to pass to your left",
+            Then::card(self, player_index, Self::PASS + offset).with_cards(passed),
+        );
+    }
+
+    /// Passes the chosen cards once everyone has chosen. The players passing
+    /// cards are the ones with any cards in hand, as nothing has moved since
+    /// they chose
+    fn pass(game: &mut Game, player_index: usize, passed: CardList) -> Result {
+        let player_count = game.player_count();
+        let mut senders = Vec::new();
+        for i in 0..player_count {
+            let index = (player_index + i) % player_count;
+            if !game.player(index)?.hand.is_empty() {
+                senders.push(index);
+            }
+        }
+
+        for (i, (&index, card)) in senders.iter().zip(passed).enumerate() {
+            let receiver = senders[(i + 1) % senders.len()];
+            game.move_card_to(
+                index,
+                Zone::Hand,
+                Destination::new(receiver, Zone::Hand),
+                Selector::Card(card),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[typetag::serde]
+impl Card for Masquerade {
+    name!("Masquerade");
+    card_cost!(3);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        game.draw_cards(player_index, 2)?;
+
+        // Everyone chooses before any cards are passed
+        self.choose_pass(game, player_index, 0, CardList::new());
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step == Self::TRASH {
+            let indexes = chosen_up_to_from_hand(game, player_index, 1, &resume.reply)?;
+            return game.trash_from_hand(player_index, indexes);
+        }
+
+        let offset = resume.step - Self::PASS;
+        let index = (player_index + offset) % game.player_count();
+        let mut passed = resume.cards;
+        if let Some(&card_index) = chosen_from_hand(game, index, 1, &resume.reply)?.first() {
+            passed.push(game.player(index)?.hand[card_index].clone());
+        }
+
+        if offset + 1 < game.player_count() {
+            self.choose_pass(game, player_index, offset + 1, passed);
+            return Ok(());
+        }
+
+        Self::pass(game, player_index, passed)?;
+        game.ask(
+            Ask::hand(
+                player_index,
+                ChoiceCountOptions::UpTo { max: 1 },
+                "Choose a card to trash",
+            ),
+            Then::card(self, player_index, Self::TRASH),
+        );
+        Ok(())
+    }
+}
+
+// Mill
+// +1 card, +1 action, you may discard 2 cards for +2 coins. Worth 1 VP
+declare_card!(
+    Mill,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Mill)"
+);
+impl Mill {
+    const DISCARD: usize = 0;
+}
+
+#[typetag::serde]
+impl Card for Mill {
+    name!("Mill");
+    card_cost!(4);
+    types!(vec![Action, Victory]);
+    victory_points!(1);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        game.draw_cards(player_index, 1)?;
+        game.player_mut(player_index)?.add_actions(1);
+
+        game.ask(
+            Ask::hand(
+                player_index,
+                ChoiceCountOptions::UpTo { max: 2 },
+                "You may discard 2 cards for +2 coins",
+            ),
+            Then::card(self, player_index, Self::DISCARD),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::DISCARD {
+            return Ok(());
+        }
+
+        let indexes = chosen_up_to_from_hand(game, player_index, 2, &resume.reply)?;
+
+        // Discarding only 1 card doesn't give any coins
+        let count = indexes.len();
+        game.discard_from_hand(player_index, indexes)?;
+        if count == 2 {
+            game.player_mut(player_index)?.add_coins(2);
+        }
+
+        Ok(())
+    }
+}
+
+// Mining Village
+// +1 card, +2 actions, you may trash this for +2 coins
+declare_card!(
+    MiningVillage,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Mining_Village)"
+);
+impl MiningVillage {
+    const TRASH: usize = 0;
+}
+
+#[typetag::serde]
+impl Card for MiningVillage {
+    name!("Mining Village");
+    card_cost!(4);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        game.draw_cards(player_index, 1)?;
+        game.player_mut(player_index)?.add_actions(2);
+
+        game.ask(
+            Ask::yes_or_no(player_index, "Trash Mining Village for +2 coins?"),
+            Then::card(self, player_index, Self::TRASH),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step == Self::TRASH
+            && resume.reply.yes()
+            && trash_from_play(game, player_index, self)?
+        {
+            game.player_mut(player_index)?.add_coins(2);
+        }
+
+        Ok(())
+    }
+}
+
+// Minion
+// +1 action, choose one: +2 coins; or discard your hand, +4 cards, and each
+// other player with at least 5 cards in hand discards their hand and draws 4
+declare_card!(
+    Minion,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Minion)"
+);
+impl Minion {
+    const CHOOSE: usize = 0;
+    const OPTIONS: [&'static str; 2] = ["+2 coins", "Discard your hand and draw 4 cards"];
+}
+
+#[typetag::serde]
+impl Card for Minion {
+    name!("Minion");
+    card_cost!(5);
+    types!(vec![Action, Attack]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        game.player_mut(player_index)?.add_actions(1);
+
+        choose_option(
+            game,
+            player_index,
+            &Self::OPTIONS,
+            "Choose one",
+            Then::card(self, player_index, Self::CHOOSE),
+        );
+        Ok(())
+    }
+
+    fn attack_target(&self) -> Option<AttackTarget> {
+        Some(EveryoneElse)
+    }
+
+    fn attack_effects(&self, game: &mut Game, player_index: usize) -> Result {
+        if game.player(player_index)?.hand.len() < 5 {
+            return Ok(());
+        }
+
+        game.move_card(player_index, Zone::Hand, Zone::Discard, Selector::All)?;
+        game.draw_cards(player_index, 4)
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::CHOOSE {
+            return Ok(());
+        }
+
+        if chosen_option(&resume.reply, Self::OPTIONS.len())? == 0 {
+            // Only the second option attacks
+            let player = game.player_mut(player_index)?;
+            player.add_coins(2);
+            player.state.skip_attack = true;
+            return Ok(());
+        }
+
+        game.move_card(player_index, Zone::Hand, Zone::Discard, Selector::All)?;
+        game.draw_cards(player_index, 4)
+    }
+}
+
+// Nobles
+// Choose one: +3 cards or +2 actions. Worth 2 VP
+declare_card!(
+    Nobles,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Nobles)"
+);
+impl Nobles {
+    const CHOOSE: usize = 0;
+    const OPTIONS: [&'static str; 2] = ["+3 cards", "+2 actions"];
+}
+
+#[typetag::serde]
+impl Card for Nobles {
+    name!("Nobles");
+    card_cost!(6);
+    types!(vec![Action, Victory]);
+    victory_points!(2);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        choose_option(
+            game,
+            player_index,
+            &Self::OPTIONS,
+            "Choose one",
+            Then::card(self, player_index, Self::CHOOSE),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::CHOOSE {
+            return Ok(());
+        }
+
+        if chosen_option(&resume.reply, Self::OPTIONS.len())? == 0 {
+            game.draw_cards(player_index, 3)
+        } else {
+            game.player_mut(player_index)?.add_actions(2);
+            Ok(())
+        }
+    }
+}
+
+// Patrol
+// +3 cards, reveal the top 4 cards of your deck. Put the Victory cards and
+// Curses into your hand, and the rest back in any order
+declare_card!(
+    Patrol,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Patrol)"
+);
+impl Patrol {
+    const ORDER: usize = 0;
+}
+
+#[typetag::serde]
+impl Card for Patrol {
+    name!("Patrol");
+    card_cost!(5);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        game.draw_cards(player_index, 3)?;
+
+        let cards = match game.reveal(player_index, 4) {
+            Ok(cards) => cards,
+            Err(Error::EmptyDeck) => return Ok(()),
+            Err(e) => return Err(e),
+        };
+
+        let (kept, rest): (CardList, CardList) = cards
+            .into_iter()
+            .partition(|c| c.is_victory() || c.is_curse());
+        move_set_aside(game, player_index, kept, Zone::Hand)?;
+
+        let then = Then::card(self, player_index, Self::ORDER);
+        put_back_in_order(game, player_index, rest, then)
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::ORDER {
+            return Ok(());
+        }
+
+        put_back_chosen(game, player_index, resume)
+    }
+}
+
+// Pawn
+// Choose two different options: +1 card, +1 action, +1 buy, +1 coin
+declare_card!(
+    Pawn,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Pawn)"
+);
+impl Pawn {
+    const CHOOSE: usize = 0;
+    const OPTIONS: [&'static str; 4] = ["+1 card", "+1 action", "+1 buy", "+1 coin"];
+}
+
+#[typetag::serde]
+impl Card for Pawn {
+    name!("Pawn");
+    card_cost!(2);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        choose_different_options(
+            game,
+            player_index,
+            2,
+            &Self::OPTIONS,
+            "Choose two",
+            Then::card(self, player_index, Self::CHOOSE),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::CHOOSE {
+            return Ok(());
+        }
+
+        for option in chosen_options(&resume.reply, 2, Self::OPTIONS.len())? {
+            let player = game.player_mut(player_index)?;
+            match option {
+                0 => game.draw_cards(player_index, 1)?,
+                1 => player.add_actions(1),
+                2 => player.add_buys(1),
+                _ => player.add_coins(1),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Replace
+// Trash a card from your hand. Gain a card costing up to 2 coins more than
+// it. If the gained card is an Action or Treasure, put it onto your deck; if
+// it is a Victory card, each other player gains a Curse
+declare_card!(
+    Replace,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Replace)"
+);
+impl Replace {
+    const TRASH: usize = 0;
+    const GAIN: usize = 1;
+}
+
+#[typetag::serde]
+impl Card for Replace {
+    name!("Replace");
+    card_cost!(5);
+    types!(vec![Action, Attack]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        // Without a Victory card to gain, nobody gets a Curse
+        game.player_mut(player_index)?.state.skip_attack = true;
+
+        choose_exact_from_hand(
+            game,
+            player_index,
+            1,
+            "Choose a card to trash",
+            Then::card(self, player_index, Self::TRASH),
+        );
+        Ok(())
+    }
+
+    fn attack_target(&self) -> Option<AttackTarget> {
+        Some(EveryoneElse)
+    }
+
+    fn attack_effects(&self, game: &mut Game, player_index: usize) -> Result {
+        gain_if_available(game, player_index, Box::new(BasicCurse), Zone::Discard)
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        match resume.step {
+            Self::TRASH => {
+                let Some(&index) = chosen_from_hand(game, player_index, 1, &resume.reply)?.first()
+                else {
+                    return Ok(());
+                };
+
+                let trashed = game.player(player_index)?.hand[index].clone();
+                game.trash_from_hand(player_index, vec![index])?;
+
+                let then = Then::card(self, player_index, Self::GAIN);
+                choose_gain(game, player_index, then.with_cards(vec![trashed]));
+                Ok(())
+            }
+            Self::GAIN => {
+                let Some(trashed) = resume.cards.first() else {
+                    return Ok(());
+                };
+                let max_cost = game.effective_cost(&**trashed, player_index) + Cost::new(2);
+                let Some(card) = chosen_gain(game, &resume.reply, |c| {
+                    game.effective_cost(c, player_index) <= max_cost
+                })?
+                else {
+                    return Ok(());
+                };
+
+                if card.is_victory() {
+                    game.player_mut(player_index)?.state.skip_attack = false;
+                }
+
+                if card.is_action() || card.is_treasure() {
+                    game.gain_to_deck_top(player_index, card)
+                } else {
+                    game.gain(player_index, card)
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+// Secret Passage
+// +2 cards, +1 action, take a card from your hand and put it into your deck
+//
+// Cards can only be put on the top or the bottom of the deck, rather than
+// anywhere in it
+declare_card!(
+    SecretPassage,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Secret_Passage)"
+);
+impl SecretPassage {
+    const CHOOSE: usize = 0;
+    const PLACE: usize = 1;
+}
+
+#[typetag::serde]
+impl Card for SecretPassage {
+    name!("Secret Passage");
+    card_cost!(4);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        game.draw_cards(player_index, 2)?;
+        game.player_mut(player_index)?.add_actions(1);
+
+        choose_exact_from_hand(
+            game,
+            player_index,
+            1,
+            "Choose a card to put into your deck",
+            Then::card(self, player_index, Self::CHOOSE),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        match resume.step {
+            Self::CHOOSE => {
+                let Some(&index) = chosen_from_hand(game, player_index, 1, &resume.reply)?.first()
+                else {
+                    return Ok(());
+                };
+
+                let card = game.player(player_index)?.hand[index].clone();
+                game.ask(
+                    Ask::yes_or_no(player_index, "Put it on top of your deck?"),
+                    Then::card(self, player_index, Self::PLACE).with_cards(vec![card]),
+                );
+                Ok(())
+            }
+            Self::PLACE => {
+                let Some(card) = resume.cards.into_iter().next() else {
+                    return Ok(());
+                };
+
+                let to = Destination::new(player_index, Zone::Deck);
+                let to = if resume.reply.yes() { to } else { to.bottom() };
+                game.move_card_to(player_index, Zone::Hand, to, Selector::Card(card))?;
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+// Shanty Town
+// +2 actions, reveal your hand. If you have no Action cards in hand, +2 cards
+declare_card!(
+    ShantyTown,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Shanty_Town)"
+);
+#[typetag::serde]
+impl Card for ShantyTown {
+    name!("Shanty Town");
+    card_cost!(3);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        let player = game.player_mut(player_index)?;
+        player.add_actions(2);
+
+        let hand: CardList = player.hand.iter().cloned().collect();
+        let no_actions = !hand.iter().any(|c| c.is_action());
+        game.reveal_cards(player_index, hand)?;
+
+        if no_actions {
+            game.draw_cards(player_index, 2)?;
+        }
+
+        Ok(())
+    }
+}
+
+// Steward
+// Choose one: +2 cards; +2 coins; or trash 2 cards from your hand
+declare_card!(
+    Steward,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Steward)"
+);
+impl Steward {
+    const CHOOSE: usize = 0;
+    const TRASH: usize = 1;
+    const OPTIONS: [&'static str; 3] = ["+2 cards", "+2 coins", "Trash 2 cards from your hand"];
+}
+
+#[typetag::serde]
+impl Card for Steward {
+    name!("Steward");
+    card_cost!(3);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        choose_option(
+            game,
+            player_index,
+            &Self::OPTIONS,
+            "Choose one",
+            Then::card(self, player_index, Self::CHOOSE),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        match resume.step {
+            Self::CHOOSE => match chosen_option(&resume.reply, Self::OPTIONS.len())? {
+                0 => game.draw_cards(player_index, 2),
+                1 => {
+                    game.player_mut(player_index)?.add_coins(2);
+                    Ok(())
+                }
+                _ => {
+                    choose_exact_from_hand(
+                        game,
+                        player_index,
+                        2,
+                        "Choose cards to trash",
+                        Then::card(self, player_index, Self::TRASH),
+                    );
+                    Ok(())
+                }
+            },
+            Self::TRASH => {
+                let indexes = chosen_from_hand(game, player_index, 2, &resume.reply)?;
+                game.trash_from_hand(player_index, indexes)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+// Swindler
+// +2 coins, each other player trashes the top card of their deck and gains a
+// card with the same cost that you choose
+declare_card!(
+    Swindler,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Swindler)"
+);
+impl Swindler {
+    const GAIN: usize = 0;
+}
+
+#[typetag::serde]
+impl Card for Swindler {
+    name!("Swindler");
+    card_cost!(3);
+    types!(vec![Action, Attack]);
+    basic_on_play_effects!(cards = 0, actions = 0, buys = 0, coins = 2);
+
+    fn attack_target(&self) -> Option<AttackTarget> {
+        Some(EveryoneElse)
+    }
+
+    fn attack_effects(&self, game: &mut Game, player_index: usize) -> Result {
+        let card = match game.reveal(player_index, 1) {
+            Ok(mut cards) => cards.remove(0),
+            Err(Error::EmptyDeck) => return Ok(()),
+            Err(e) => return Err(e),
+        };
+
+        move_set_aside(game, player_index, vec![card.clone()], Zone::Trash)?;
+
+        // The swindler chooses, but the victim gains the card
+        let then = Then::card(self, player_index, Self::GAIN).with_cards(vec![card]);
+        choose_gain(game, game.current_turn, then);
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::GAIN {
+            return Ok(());
+        }
+        let Some(trashed) = resume.cards.first() else {
+            return Ok(());
+        };
+
+        let cost = game.effective_cost(&**trashed, player_index);
+        if let Some(card) = chosen_gain(game, &resume.reply, |c| {
+            game.effective_cost(c, player_index) == cost
+        })? {
+            game.gain(player_index, card)?;
+        }
+
+        Ok(())
+    }
+}
+
+// Torturer
+// +3 cards, each other player either discards 2 cards or gains a Curse to
+// their hand, their choice
+declare_card!(
+    Torturer,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Torturer)"
+);
+impl Torturer {
+    const CHOOSE: usize = 0;
+    const DISCARD: usize = 1;
+    const OPTIONS: [&'static str; 2] = ["Discard 2 cards", "Gain a Curse to your hand"];
+}
+
+#[typetag::serde]
+impl Card for Torturer {
+    name!("Torturer");
+    card_cost!(5);
+    types!(vec![Action, Attack]);
+    basic_on_play_effects!(cards = 3, actions = 0, buys = 0, coins = 0);
+
+    fn attack_target(&self) -> Option<AttackTarget> {
+        Some(EveryoneElse)
+    }
+
+    fn attack_effects(&self, game: &mut Game, player_index: usize) -> Result {
+        choose_option(
+            game,
+            player_index,
+            &Self::OPTIONS,
+            "Choose one",
+            Then::card(self, player_index, Self::CHOOSE),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        match resume.step {
+            Self::CHOOSE => {
+                if chosen_option(&resume.reply, Self::OPTIONS.len())? == 0 {
+                    choose_exact_from_hand(
+                        game,
+                        player_index,
+                        2,
+                        "Choose cards to discard",
+                        Then::card(self, player_index, Self::DISCARD),
+                    );
+                    Ok(())
+                } else {
+                    gain_if_available(game, player_index, Box::new(BasicCurse), Zone::Hand)
+                }
+            }
+            Self::DISCARD => {
+                let indexes = chosen_from_hand(game, player_index, 2, &resume.reply)?;
+                game.discard_from_hand(player_index, indexes)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+// Trading Post
+// Trash 2 cards from your hand. If you did, gain a Silver to your hand
+declare_card!(
+    TradingPost,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Trading_Post)"
+);
+impl TradingPost {
+    const TRASH: usize = 0;
+}
+
+#[typetag::serde]
+impl Card for TradingPost {
+    name!("Trading Post");
+    card_cost!(5);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        choose_exact_from_hand(
+            game,
+            player_index,
+            2,
+            "Choose cards to trash",
+            Then::card(self, player_index, Self::TRASH),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::TRASH {
+            return Ok(());
+        }
+
+        let indexes = chosen_from_hand(game, player_index, 2, &resume.reply)?;
+        let trashed = indexes.len();
+        game.trash_from_hand(player_index, indexes)?;
+
+        if trashed == 2 {
+            gain_if_available(game, player_index, Box::new(Silver), Zone::Hand)?;
+        }
+
+        Ok(())
+    }
+}
+
+// Upgrade
+// +1 card, +1 action, trash a card from your hand. Gain a card costing
+// exactly 1 coin more than it
+declare_card!(
+    Upgrade,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Upgrade)"
+);
+impl Upgrade {
+    const TRASH: usize = 0;
+    const GAIN: usize = 1;
+}
+
+#[typetag::serde]
+impl Card for Upgrade {
+    name!("Upgrade");
+    card_cost!(5);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        game.draw_cards(player_index, 1)?;
+        game.player_mut(player_index)?.add_actions(1);
+
+        choose_exact_from_hand(
+            game,
+            player_index,
+            1,
+            "Choose a card to trash",
+            Then::card(self, player_index, Self::TRASH),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        match resume.step {
+            Self::TRASH => {
+                let Some(&index) = chosen_from_hand(game, player_index, 1, &resume.reply)?.first()
+                else {
+                    return Ok(());
+                };
+
+                let trashed = game.player(player_index)?.hand[index].clone();
+                game.trash_from_hand(player_index, vec![index])?;
+
+                let then = Then::card(self, player_index, Self::GAIN);
+                choose_gain(game, player_index, then.with_cards(vec![trashed]));
+                Ok(())
+            }
+            Self::GAIN => {
+                let Some(trashed) = resume.cards.first() else {
+                    return Ok(());
+                };
+                let cost = game.effective_cost(&**trashed, player_index) + Cost::new(1);
+                if let Some(card) = chosen_gain(game, &resume.reply, |c| {
+                    game.effective_cost(c, player_index) == cost
+                })? {
+                    game.gain(player_index, card)?;
+                }
+
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+// Wishing Well
+// +1 card, +1 action, name a card, then reveal the top card of your deck. If
+// you named it, put it into your hand
+declare_card!(
+    WishingWell,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Wishing_Well)"
+);
+impl WishingWell {
+    const NAME: usize = 0;
+}
+
+#[typetag::serde]
+impl Card for WishingWell {
+    name!("Wishing Well");
+    card_cost!(3);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        game.draw_cards(player_index, 1)?;
+        game.player_mut(player_index)?.add_actions(1);
+
+        // Cards are named by choosing them from the supply
+        game.ask(
+            Ask::supply(player_index, "Name a card"),
+            Then::card(self, player_index, Self::NAME),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::NAME {
+            return Ok(());
+        }
+
+        let card = match game.reveal(player_index, 1) {
+            Ok(mut cards) => cards.remove(0),
+            Err(Error::EmptyDeck) => return Ok(()),
+            Err(e) => return Err(e),
+        };
+
+        let named = resume.reply.card();
+        let to = if named.is_some_and(|named| named.name() == card.name()) {
+            Zone::Hand
+        } else {
+            Zone::Deck
+        };
+
+        move_set_aside(game, player_index, vec![card], to)
+    }
+}
@@ -1,3 +1,38 @@
+pub mod alchemy;
 pub mod base;
+pub mod dark_ages;
 pub mod dominion;
+pub mod dominion_first_edition;
+pub mod hinterlands;
+pub mod intrigue;
 pub mod prelude;
+pub mod prosperity;
+pub mod seaside;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::{Card, CardList};
+
+/// Editions of the base set, which have different kingdom cards
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Edition {
+    First,
+    #[default]
+    Second,
+}
+
+impl Edition {
+    /// The kingdom cards in this edition of the base set
+    pub fn kingdom_cards(self) -> CardList {
+        match self {
+            Edition::First => dominion_first_edition::kingdom_cards(),
+            Edition::Second => dominion::kingdom_cards(),
+        }
+    }
+
+    /// Checks whether a card is one of this edition's kingdom cards
+    pub fn contains(self, card: &dyn Card) -> bool {
+        self.kingdom_cards().iter().any(|c| c.name() == card.name())
+    }
+}
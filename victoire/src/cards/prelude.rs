@@ -2,12 +2,18 @@ pub use victoire_macros::*;
 
 pub use serde::{Deserialize, Serialize};
 
-pub use crate::callbacks::{Callbacks, ChoiceCountOptions};
+pub use crate::callbacks::{Callbacks, ChoiceCountOptions, MAX_INVALID_ANSWERS};
+pub use crate::decisions::{Ask, Reply, Resume, Then};
+pub use crate::error::{Error, Result};
+pub use crate::events::GameEvent;
 pub use crate::types::card::{
     AttackTarget::{self, *},
     CardType::{self, *},
-    Cost,
+    Cost, Gain,
     ReactionTrigger::{self, *},
     Value,
 };
-pub use crate::types::{Card, CardDeck, CardList, Game, Player};
+pub use crate::types::{
+    Card, CardDeck, CardId, CardList, Destination, ExtraTurn, Game, Identified, Lifetime, Mat,
+    Modifier, ModifierKind, Player, Selector, Zone,
+};
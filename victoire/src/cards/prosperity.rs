@@ -0,0 +1,1003 @@
+//! Cards from the Prosperity expansion
+//!
+//! Platinum and Colony are in [`base`](super::base), and are added to the
+//! supply by [`Game::generate_supply_with_colonies`]. Cards whose cost
+//! depends on what is in play are not included yet.
+
+#![allow(clippy::wildcard_imports)]
+
+use itertools::Itertools;
+
+use super::base::*;
+use super::dominion::{
+    choose_exact_from_hand, choose_gain, chosen_from_hand, chosen_gain, chosen_up_to_from_hand,
+    gain_if_available, move_set_aside, play_action_times, play_chosen_action, put_back_chosen,
+    put_back_in_order,
+};
+use super::prelude::*;
+
+/// The kingdom cards from Prosperity that are implemented so far
+pub fn kingdom_cards() -> CardList {
+    card_vec![
+        Bank,
+        Bishop,
+        City,
+        Expand,
+        Forge,
+        Goons,
+        KingsCourt,
+        Loan,
+        Mint,
+        Monument,
+        Mountebank,
+        Quarry,
+        Rabble,
+        RoyalSeal,
+        Trader,
+        Vault,
+        Venture,
+        Watchtower,
+        WorkersVillage
+    ]
+}
+
+/// Reveals cards from the top of the player's deck until they reveal a
+/// treasure, which is returned and stays set aside. The other revealed cards
+/// are discarded
+fn reveal_until_treasure(game: &mut Game, player_index: usize) -> Result<Option<Box<dyn Card>>> {
+    let mut others = CardList::new();
+    let treasure = loop {
+        let card = match game.reveal(player_index, 1) {
+            Ok(mut cards) => cards.remove(0),
+            Err(Error::EmptyDeck) => break None,
+            Err(e) => return Err(e),
+        };
+
+        if card.is_treasure() {
+            break Some(card);
+        }
+        others.push(card);
+    };
+
+    move_set_aside(game, player_index, others, Zone::Discard)?;
+    Ok(treasure)
+}
+
+/// Asks the player for any number of cards from their hand, carrying on
+/// with `then`, where the choice is read with [`chosen_any_from_hand`]
+fn choose_any_from_hand(game: &mut Game, player_index: usize, message: &str, then: Then) {
+    let count = ChoiceCountOptions::Any;
+    game.ask(Ask::hand(player_index, count, message), then);
+}
+
+/// The different cards the player chose from their hand. Cards that aren't
+/// there or repeats are an invalid answer, as for [`chosen_from_hand`]
+fn chosen_any_from_hand(game: &Game, player_index: usize, reply: &Reply) -> Result<Vec<usize>> {
+    let hand_size = game.player(player_index)?.hand.len();
+    chosen_up_to_from_hand(game, player_index, hand_size, reply)
+}
+
+// Bank
+// Worth 1 coin per Treasure you have in play, counting this
+declare_card!(
+    Bank,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Bank)"
+);
+#[typetag::serde]
+impl Card for Bank {
+    name!("Bank");
+    card_cost!(7);
+    types!(vec![Treasure]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        let player = game.player_mut(player_index)?;
+        let treasures = player.in_play.iter().filter(|c| c.is_treasure()).count();
+        player.add_treasure_coins(treasures);
+
+        Ok(())
+    }
+}
+
+// Bishop
+// +1 coin, +1 VP token, trash a card from your hand. + VP tokens equal to
+// half its cost, rounded down. Each other player may trash a card from their
+// hand
+declare_card!(
+    Bishop,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Bishop)"
+);
+impl Bishop {
+    const TRASH: usize = 0;
+    const OTHER_TRASH: usize = 1;
+}
+
+#[typetag::serde]
+impl Card for Bishop {
+    name!("Bishop");
+    card_cost!(4);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        let player = game.player_mut(player_index)?;
+        player.add_coins(1);
+        player.tokens.victory_points += 1;
+
+        choose_exact_from_hand(
+            game,
+            player_index,
+            1,
+            "Choose a card to trash",
+            Then::card(self, player_index, Self::TRASH),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        match resume.step {
+            Self::TRASH => {
+                if let Some(&index) =
+                    chosen_from_hand(game, player_index, 1, &resume.reply)?.first()
+                {
+                    let tokens = game
+                        .effective_cost(&*game.player(player_index)?.hand[index], player_index)
+                        .coins
+                        / 2;
+                    game.trash_from_hand(player_index, vec![index])?;
+                    game.player_mut(player_index)?.tokens.victory_points += tokens;
+                }
+
+                let player_count = game.player_count();
+                for i in 1..player_count {
+                    let other = (player_index + i) % player_count;
+                    game.ask(
+                        Ask::hand(
+                            other,
+                            ChoiceCountOptions::UpTo { max: 1 },
+                            "You may trash a card",
+                        ),
+                        Then::card(self, other, Self::OTHER_TRASH),
+                    );
+                }
+
+                Ok(())
+            }
+            Self::OTHER_TRASH => {
+                let indexes = chosen_up_to_from_hand(game, player_index, 1, &resume.reply)?;
+                game.trash_from_hand(player_index, indexes)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+// City
+// +1 card, +2 actions. If there are one or more empty supply piles, +1 card.
+// If there are two or more, +1 coin and +1 buy
+declare_card!(
+    City,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/City)"
+);
+#[typetag::serde]
+impl Card for City {
+    name!("City");
+    card_cost!(5);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        let empty_piles = game.supply.empty_piles();
+        let cards = if empty_piles >= 1 { 2 } else { 1 };
+        game.draw_cards(player_index, cards)?;
+
+        let player = game.player_mut(player_index)?;
+        player.add_actions(2);
+        if empty_piles >= 2 {
+            player.add_coins(1);
+            player.add_buys(1);
+        }
+
+        Ok(())
+    }
+}
+
+// Expand
+// Trash a card from your hand. Gain a card costing up to 3 coins more than it
+declare_card!(
+    Expand,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Expand)"
+);
+impl Expand {
+    const TRASH: usize = 0;
+    const GAIN: usize = 1;
+}
+
+#[typetag::serde]
+impl Card for Expand {
+    name!("Expand");
+    card_cost!(7);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        choose_exact_from_hand(
+            game,
+            player_index,
+            1,
+            "Choose a card to trash",
+            Then::card(self, player_index, Self::TRASH),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        match resume.step {
+            Self::TRASH => {
+                let Some(&index) = chosen_from_hand(game, player_index, 1, &resume.reply)?.first()
+                else {
+                    return Ok(());
+                };
+
+                let trashed = game.player(player_index)?.hand[index].clone();
+                game.trash_from_hand(player_index, vec![index])?;
+
+                let then = Then::card(self, player_index, Self::GAIN);
+                choose_gain(game, player_index, then.with_cards(vec![trashed]));
+                Ok(())
+            }
+            Self::GAIN => {
+                let Some(trashed) = resume.cards.first() else {
+                    return Ok(());
+                };
+                let max_cost = game.effective_cost(&**trashed, player_index) + Cost::new(3);
+                if let Some(card) = chosen_gain(game, &resume.reply, |c| {
+                    game.effective_cost(c, player_index) <= max_cost
+                })? {
+                    game.gain(player_index, card)?;
+                }
+
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+// Forge
+// Trash any number of cards from your hand. Gain a card with cost exactly
+// equal to the total cost of the trashed cards
+declare_card!(
+    Forge,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Forge)"
+);
+impl Forge {
+    const TRASH: usize = 0;
+    const GAIN: usize = 1;
+}
+
+#[typetag::serde]
+impl Card for Forge {
+    name!("Forge");
+    card_cost!(7);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        choose_any_from_hand(
+            game,
+            player_index,
+            "Choose cards to trash",
+            Then::card(self, player_index, Self::TRASH),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        match resume.step {
+            Self::TRASH => {
+                let indexes = chosen_any_from_hand(game, player_index, &resume.reply)?;
+
+                let hand = &game.player(player_index)?.hand;
+                let trashed: CardList = indexes.iter().map(|&i| hand[i].clone()).collect();
+                game.trash_from_hand(player_index, indexes)?;
+
+                let then = Then::card(self, player_index, Self::GAIN);
+                choose_gain(game, player_index, then.with_cards(trashed));
+                Ok(())
+            }
+            Self::GAIN => {
+                let total: usize = resume
+                    .cards
+                    .iter()
+                    .map(|card| game.effective_cost(&**card, player_index).coins)
+                    .sum();
+                if let Some(card) = chosen_gain(game, &resume.reply, |c| {
+                    game.effective_cost(c, player_index) == Cost::new(total)
+                })? {
+                    game.gain(player_index, card)?;
+                }
+
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+// Goons
+// +1 buy, +2 coins, each other player discards down to 3 cards in hand.
+// While this is in play, when you buy a card, +1 VP token
+declare_card!(
+    Goons,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Goons)"
+);
+impl Goons {
+    const DISCARD: usize = 0;
+}
+
+#[typetag::serde]
+impl Card for Goons {
+    name!("Goons");
+    card_cost!(6);
+    types!(vec![Action, Attack]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        let player = game.player_mut(player_index)?;
+        player.add_buys(1);
+        player.add_coins(2);
+        player.state.vp_per_buy += 1;
+
+        Ok(())
+    }
+
+    fn attack_target(&self) -> Option<AttackTarget> {
+        Some(EveryoneElse)
+    }
+
+    fn attack_effects(&self, game: &mut Game, player_index: usize) -> Result {
+        let hand_size = game.player(player_index)?.hand.len();
+        if hand_size <= 3 {
+            return Ok(());
+        }
+
+        choose_exact_from_hand(
+            game,
+            player_index,
+            hand_size - 3,
+            "Choose cards to discard down to 3",
+            Then::card(self, player_index, Self::DISCARD),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::DISCARD {
+            return Ok(());
+        }
+
+        let count = game.player(player_index)?.hand.len().saturating_sub(3);
+        let indexes = chosen_from_hand(game, player_index, count, &resume.reply)?;
+        game.discard_from_hand(player_index, indexes)
+    }
+}
+
+// King's Court
+// You may play an Action card from your hand three times
+declare_card!(
+    KingsCourt,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/King%27s_Court)"
+);
+impl KingsCourt {
+    const PLAY: usize = 0;
+}
+
+#[typetag::serde]
+impl Card for KingsCourt {
+    name!("King's Court");
+    card_cost!(7);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        play_action_times(
+            game,
+            player_index,
+            3,
+            Then::card(self, player_index, Self::PLAY),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::PLAY {
+            return Ok(());
+        }
+
+        play_chosen_action(game, player_index, 3, &resume.reply)
+    }
+}
+
+// Loan
+// Worth 1 coin. Reveal cards from your deck until you reveal a Treasure.
+// Discard it or trash it. Discard the other cards
+declare_card!(
+    Loan,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Loan)"
+);
+impl Loan {
+    const TRASH: usize = 0;
+}
+
+#[typetag::serde]
+impl Card for Loan {
+    name!("Loan");
+    card_cost!(3);
+    types!(vec![Treasure]);
+    treasure_value!(1);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        let Some(card) = reveal_until_treasure(game, player_index)? else {
+            return Ok(());
+        };
+
+        game.ask(
+            Ask::yes_or_no(player_index, &format!("Trash {card}?")),
+            Then::card(self, player_index, Self::TRASH).with_cards(vec![card]),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::TRASH {
+            return Ok(());
+        }
+
+        let to = if resume.reply.yes() {
+            Zone::Trash
+        } else {
+            Zone::Discard
+        };
+
+        move_set_aside(game, player_index, resume.cards, to)
+    }
+}
+
+// Mint
+// You may reveal a Treasure from your hand to gain a copy of it. When you buy
+// this, trash all Treasures you have in play
+declare_card!(
+    Mint,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Mint)"
+);
+impl Mint {
+    const REVEAL: usize = 0;
+}
+
+#[typetag::serde]
+impl Card for Mint {
+    name!("Mint");
+    card_cost!(5);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        game.ask(
+            Ask::hand(
+                player_index,
+                ChoiceCountOptions::UpTo { max: 1 },
+                "You may reveal a Treasure to gain a copy of it",
+            ),
+            Then::card(self, player_index, Self::REVEAL),
+        );
+        Ok(())
+    }
+
+    fn effects_on_buy(&self, game: &mut Game, player_index: usize) -> Result {
+        // Durations from earlier turns are left alone
+        let treasures = game
+            .player(player_index)?
+            .in_play
+            .iter()
+            .positions(|c| c.is_treasure() && !c.is_duration())
+            .collect();
+
+        game.move_card(
+            player_index,
+            Zone::InPlay,
+            Zone::Trash,
+            Selector::Indexes(treasures),
+        )?;
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::REVEAL {
+            return Ok(());
+        }
+
+        // Revealing nothing does nothing, but a card that isn't a Treasure
+        // is an invalid answer
+        let chosen = chosen_up_to_from_hand(game, player_index, 1, &resume.reply)?;
+        let Some(&index) = chosen.first() else {
+            return Ok(());
+        };
+        let card = game.player(player_index)?.hand[index].clone();
+        if !card.is_treasure() {
+            return Err(Error::InvalidAnswer);
+        }
+
+        game.reveal_cards(player_index, vec![card.clone()])?;
+
+        // Treasures without a supply pile have no copy to gain
+        if game.supply.get(card.name()).is_none() {
+            return Ok(());
+        }
+        gain_if_available(game, player_index, card, Zone::Discard)
+    }
+}
+
+// Monument
+// +2 coins, +1 VP token
+declare_card!(
+    Monument,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Monument)"
+);
+#[typetag::serde]
+impl Card for Monument {
+    name!("Monument");
+    card_cost!(4);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        let player = game.player_mut(player_index)?;
+        player.add_coins(2);
+        player.tokens.victory_points += 1;
+
+        Ok(())
+    }
+}
+
+// Mountebank
+// +2 coins, each other player may discard a Curse. If they don't, they gain
+// a Curse and a Copper
+declare_card!(
+    Mountebank,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Mountebank)"
+);
+impl Mountebank {
+    const DISCARD: usize = 0;
+
+    fn curse(game: &Game, player_index: usize) -> Result<Option<usize>> {
+        Ok(game
+            .player(player_index)?
+            .hand
+            .iter()
+            .position(|c| c.is_curse()))
+    }
+
+    fn gain_curse_and_copper(game: &mut Game, player_index: usize) -> Result {
+        gain_if_available(game, player_index, Box::new(BasicCurse), Zone::Discard)?;
+        gain_if_available(game, player_index, Box::new(Copper), Zone::Discard)
+    }
+}
+
+#[typetag::serde]
+impl Card for Mountebank {
+    name!("Mountebank");
+    card_cost!(5);
+    types!(vec![Action, Attack]);
+    basic_on_play_effects!(cards = 0, actions = 0, buys = 0, coins = 2);
+
+    fn attack_target(&self) -> Option<AttackTarget> {
+        Some(EveryoneElse)
+    }
+
+    fn attack_effects(&self, game: &mut Game, player_index: usize) -> Result {
+        if Self::curse(game, player_index)?.is_none() {
+            return Self::gain_curse_and_copper(game, player_index);
+        }
+
+        game.ask(
+            Ask::yes_or_no(player_index, "Discard a Curse?"),
+            Then::card(self, player_index, Self::DISCARD),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::DISCARD {
+            return Ok(());
+        }
+
+        match Self::curse(game, player_index)? {
+            Some(index) if resume.reply.yes() => game.discard_from_hand(player_index, vec![index]),
+            _ => Self::gain_curse_and_copper(game, player_index),
+        }
+    }
+}
+
+// Quarry
+// Worth 1 coin. While this is in play, Action cards cost 2 coins less
+declare_card!(
+    Quarry,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Quarry)"
+);
+#[typetag::serde]
+impl Card for Quarry {
+    name!("Quarry");
+    card_cost!(4);
+    types!(vec![Treasure]);
+    treasure_value!(1);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        // Treasures stay in play until clean-up, so the reduction can last
+        // for the rest of the turn
+        game.add_modifier(Modifier::new(
+            player_index,
+            ModifierKind::CostReduction {
+                coins: 2,
+                card_type: Some(Action),
+            },
+            Lifetime::ThisTurn,
+        ));
+
+        Ok(())
+    }
+}
+
+// Rabble
+// +3 cards, each other player reveals the top 3 cards of their deck,
+// discards the Actions and Treasures, and puts the rest back in any order
+// they choose
+declare_card!(
+    Rabble,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Rabble)"
+);
+impl Rabble {
+    const ORDER: usize = 0;
+}
+
+#[typetag::serde]
+impl Card for Rabble {
+    name!("Rabble");
+    card_cost!(5);
+    types!(vec![Action, Attack]);
+    basic_on_play_effects!(cards = 3, actions = 0, buys = 0, coins = 0);
+
+    fn attack_target(&self) -> Option<AttackTarget> {
+        Some(EveryoneElse)
+    }
+
+    fn attack_effects(&self, game: &mut Game, player_index: usize) -> Result {
+        let cards = match game.reveal(player_index, 3) {
+            Ok(cards) => cards,
+            Err(Error::EmptyDeck) => return Ok(()),
+            Err(e) => return Err(e),
+        };
+
+        let (discarded, rest): (CardList, CardList) = cards
+            .into_iter()
+            .partition(|c| c.is_action() || c.is_treasure());
+        move_set_aside(game, player_index, discarded, Zone::Discard)?;
+
+        let then = Then::card(self, player_index, Self::ORDER);
+        put_back_in_order(game, player_index, rest, then)
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::ORDER {
+            return Ok(());
+        }
+
+        put_back_chosen(game, player_index, resume)
+    }
+}
+
+// Royal Seal
+// Worth 2 coins. While this is in play, when you gain a card, you may put
+// that card onto your deck
+declare_card!(
+    RoyalSeal,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Royal_Seal)"
+);
+impl RoyalSeal {
+    const TOPDECK: usize = 0;
+}
+
+#[typetag::serde]
+impl Card for RoyalSeal {
+    name!("Royal Seal");
+    card_cost!(5);
+    types!(vec![Treasure]);
+    treasure_value!(2);
+
+    fn react_to_gain(&self, game: &mut Game, player_index: usize, zone: Zone) -> Result {
+        let Some(gain) = game.gaining() else {
+            return Ok(());
+        };
+        if zone != Zone::InPlay || matches!(gain.to, Zone::Deck | Zone::Trash) {
+            return Ok(());
+        }
+
+        let prompt = format!("Put the {} onto your deck?", gain.card);
+        game.ask(
+            Ask::yes_or_no(player_index, &prompt),
+            Then::card(self, player_index, Self::TOPDECK),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, _: usize, resume: Resume) -> Result {
+        if resume.step != Self::TOPDECK || !resume.reply.yes() {
+            return Ok(());
+        }
+
+        if let Some(gain) = game.gaining_mut() {
+            gain.to = Zone::Deck;
+        }
+
+        Ok(())
+    }
+}
+
+// Trader
+// Trash a card from your hand. Gain a Silver per coin it costs. When you
+// would gain a card, you may reveal this from your hand, to gain a Silver
+// instead
+declare_card!(
+    Trader,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Trader)"
+);
+impl Trader {
+    const TRASH: usize = 0;
+    const REVEAL: usize = 1;
+}
+
+#[typetag::serde]
+impl Card for Trader {
+    name!("Trader");
+    card_cost!(4);
+    types!(vec![Action, Reaction]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        choose_exact_from_hand(
+            game,
+            player_index,
+            1,
+            "Choose a card to trash",
+            Then::card(self, player_index, Self::TRASH),
+        );
+        Ok(())
+    }
+
+    fn react_to_gain(&self, game: &mut Game, player_index: usize, zone: Zone) -> Result {
+        let Some(gain) = game.gaining() else {
+            return Ok(());
+        };
+        let silvers = game
+            .supply
+            .get(Silver.name())
+            .map_or(0, |entry| entry.count);
+        if zone != Zone::Hand || gain.card.name() == Silver.name() || silvers == 0 {
+            return Ok(());
+        }
+
+        let prompt = format!(
+            "Reveal Trader to gain a Silver instead of the {}?",
+            gain.card
+        );
+        game.ask(
+            Ask::yes_or_no(player_index, &prompt),
+            Then::card(self, player_index, Self::REVEAL),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        match resume.step {
+            Self::TRASH => {
+                let Some(&index) = chosen_from_hand(game, player_index, 1, &resume.reply)?.first()
+                else {
+                    return Ok(());
+                };
+
+                let coins = game
+                    .effective_cost(&*game.player(player_index)?.hand[index], player_index)
+                    .coins;
+                game.trash_from_hand(player_index, vec![index])?;
+                for _ in 0..coins {
+                    match game.gain(player_index, Box::new(Silver)) {
+                        Err(Error::EmptyPile { .. }) => break,
+                        result => result?,
+                    }
+                }
+
+                Ok(())
+            }
+            Self::REVEAL if resume.reply.yes() => {
+                game.reveal_cards(player_index, vec![Box::new(Trader)])?;
+                if let Some(gain) = game.gaining_mut() {
+                    gain.card = Box::new(Silver);
+                    gain.from = Zone::Supply;
+                }
+
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+// Vault
+// +2 cards, discard any number of cards for +1 coin each. Each other player
+// may discard 2 cards, to draw a card
+declare_card!(
+    Vault,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Vault)"
+);
+impl Vault {
+    const DISCARD: usize = 0;
+    const OFFER: usize = 1;
+    const OTHER_DISCARD: usize = 2;
+}
+
+#[typetag::serde]
+impl Card for Vault {
+    name!("Vault");
+    card_cost!(5);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        game.draw_cards(player_index, 2)?;
+
+        choose_any_from_hand(
+            game,
+            player_index,
+            "Discard any number of cards for +1 coin each",
+            Then::card(self, player_index, Self::DISCARD),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        match resume.step {
+            Self::DISCARD => {
+                let indexes = chosen_any_from_hand(game, player_index, &resume.reply)?;
+                game.player_mut(player_index)?.add_coins(indexes.len());
+                game.discard_from_hand(player_index, indexes)?;
+
+                let player_count = game.player_count();
+                for i in 1..player_count {
+                    let other = (player_index + i) % player_count;
+                    if game.player(other)?.hand.len() < 2 {
+                        continue;
+                    }
+
+                    game.ask(
+                        Ask::yes_or_no(other, "Discard 2 cards to draw a card?"),
+                        Then::card(self, other, Self::OFFER),
+                    );
+                }
+
+                Ok(())
+            }
+            Self::OFFER if resume.reply.yes() => {
+                choose_exact_from_hand(
+                    game,
+                    player_index,
+                    2,
+                    "Choose cards to discard",
+                    Then::card(self, player_index, Self::OTHER_DISCARD),
+                );
+                Ok(())
+            }
+            Self::OTHER_DISCARD => {
+                let indexes = chosen_from_hand(game, player_index, 2, &resume.reply)?;
+                game.discard_from_hand(player_index, indexes)?;
+                game.draw_cards(player_index, 1)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+// Venture
+// Worth 1 coin. Reveal cards from your deck until you reveal a Treasure.
+// Discard the other cards and play that Treasure
+declare_card!(
+    Venture,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Venture)"
+);
+#[typetag::serde]
+impl Card for Venture {
+    name!("Venture");
+    card_cost!(5);
+    types!(vec![Treasure]);
+    treasure_value!(1);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        let Some(card) = reveal_until_treasure(game, player_index)? else {
+            return Ok(());
+        };
+
+        // Played from hand so it goes through the usual treasure rules
+        move_set_aside(game, player_index, vec![card], Zone::Hand)?;
+        let index = game.player(player_index)?.hand.len() - 1;
+        game.play_treasure_from_hand(player_index, index)
+    }
+}
+
+// Watchtower
+// Draw until you have 6 cards in hand. When you gain a card, you may reveal
+// this from your hand, to either trash that card or put it onto your deck
+declare_card!(
+    Watchtower,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Watchtower)"
+);
+impl Watchtower {
+    const REVEAL: usize = 0;
+    const CHOOSE: usize = 1;
+}
+
+#[typetag::serde]
+impl Card for Watchtower {
+    name!("Watchtower");
+    card_cost!(3);
+    types!(vec![Action, Reaction]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        let hand_size = game.player(player_index)?.hand.len();
+        game.draw_cards(player_index, 6usize.saturating_sub(hand_size))
+    }
+
+    fn react_to_gain(&self, game: &mut Game, player_index: usize, zone: Zone) -> Result {
+        let Some(gain) = game.gaining() else {
+            return Ok(());
+        };
+        if zone != Zone::Hand || gain.to == Zone::Trash {
+            return Ok(());
+        }
+
+        let prompt = format!("Reveal Watchtower for the {}?", gain.card);
+        game.ask(
+            Ask::yes_or_no(player_index, &prompt),
+            Then::card(self, player_index, Self::REVEAL),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        match resume.step {
+            Self::REVEAL if resume.reply.yes() => {
+                game.reveal_cards(player_index, vec![Box::new(Watchtower)])?;
+                game.ask(
+                    Ask::options(
+                        player_index,
+                        ChoiceCountOptions::Exact { count: 1 },
+                        &["Trash it", "Put it onto your deck"],
+                        "Choose one",
+                    ),
+                    Then::card(self, player_index, Self::CHOOSE),
+                );
+                Ok(())
+            }
+            Self::CHOOSE => {
+                if let Some(gain) = game.gaining_mut() {
+                    gain.to = match resume.reply.indexes() {
+                        [0] => Zone::Trash,
+                        [1] => Zone::Deck,
+                        _ => return Err(Error::InvalidAnswer),
+                    };
+                }
+
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+basic_action!(
+    WorkersVillage,
+    "Worker's Village",
+    cost = 4,
+    cards = 1,
+    actions = 2,
+    buys = 1,
+    coins = 0,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Worker%27s_Village)"
+);
@@ -0,0 +1,911 @@
+//! Cards from the Seaside expansion (2nd edition)
+//!
+//! Blockade, Corsair, Monkey, Pirate and Sailor are not included, since they
+//! react to other players' gains and plays, which the engine has no hooks for
+//! yet. Treasury uses its 1st edition wording, checking for Victory cards
+//! bought this turn at clean-up rather than at the end of the buy phase.
+
+#![allow(clippy::wildcard_imports)]
+
+use super::base::*;
+use super::dominion::{
+    choose_exact_from_hand, chosen_from_hand, gain_if_available, move_from_play, move_set_aside,
+    put_back_chosen, put_back_in_order, trash_from_play,
+};
+use super::prelude::*;
+
+/// The kingdom cards from Seaside that are implemented so far
+pub fn kingdom_cards() -> CardList {
+    card_vec![
+        Astrolabe,
+        Bazaar,
+        Caravan,
+        Cutpurse,
+        FishingVillage,
+        Haven,
+        Island,
+        Lighthouse,
+        Lookout,
+        MerchantShip,
+        NativeVillage,
+        Outpost,
+        Salvager,
+        SeaChart,
+        SeaWitch,
+        Smugglers,
+        Tactician,
+        TidePools,
+        TreasureMap,
+        Treasury,
+        Warehouse,
+        Wharf
+    ]
+}
+
+/// Lets a Duration card that turned out to have nothing to do on the next
+/// turn be discarded at cleanup as usual, instead of staying in play
+fn not_kept_in_play(game: &mut Game, player_index: usize, card: &dyn Card) -> Result {
+    let durations = &mut game.player_mut(player_index)?.state.durations_played;
+    let index = durations.iter().rposition(|c| match card.id() {
+        Some(id) => c.id() == Some(id),
+        None => c.name() == card.name(),
+    });
+    if let Some(index) = index {
+        durations.remove(index);
+    }
+    Ok(())
+}
+
+/// Asks the player for exactly one of the cards, carrying on with `then`,
+/// where the choice is read with [`chosen_one`]
+fn choose_one_from_selection(
+    game: &mut Game,
+    player_index: usize,
+    cards: &CardList,
+    message: &str,
+    then: Then,
+) {
+    let count = ChoiceCountOptions::Exact { count: 1 };
+    game.ask(Ask::selection(player_index, count, cards, message), then);
+}
+
+/// The card the player chose out of `count`. Any other answer is an invalid
+/// answer, as for [`chosen_from_hand`]
+fn chosen_one(reply: &Reply, count: usize) -> Result<usize> {
+    match reply.indexes() {
+        &[index] if index < count => Ok(index),
+        _ => Err(Error::InvalidAnswer),
+    }
+}
+
+/// Asks the player for exactly `count` cards from their hand to discard,
+/// carrying on with `then`, whose step should finish with [`discard_chosen`]
+fn discard_exact_from_hand(game: &mut Game, player_index: usize, count: usize, then: Then) {
+    choose_exact_from_hand(game, player_index, count, "Choose cards to discard", then);
+}
+
+/// Discards the `count` cards the player chose for
+/// [`discard_exact_from_hand`]
+fn discard_chosen(game: &mut Game, player_index: usize, count: usize, reply: &Reply) -> Result {
+    let indexes = chosen_from_hand(game, player_index, count, reply)?;
+    game.discard_from_hand(player_index, indexes)
+}
+
+// Astrolabe
+// Now and at the start of your next turn: +1 coin, +1 buy
+declare_card!(
+    Astrolabe,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Astrolabe)"
+);
+#[typetag::serde]
+impl Card for Astrolabe {
+    name!("Astrolabe");
+    card_cost!(3);
+    types!(vec![Treasure, Duration]);
+    treasure_value!(1);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        game.player_mut(player_index)?.add_buys(1);
+        Ok(())
+    }
+
+    fn effects_at_start_of_turn(&self, game: &mut Game, player_index: usize) -> Result {
+        let player = game.player_mut(player_index)?;
+        player.add_coins(1);
+        player.add_buys(1);
+
+        Ok(())
+    }
+}
+
+basic_action!(
+    Bazaar,
+    "Bazaar",
+    cost = 5,
+    cards = 1,
+    actions = 2,
+    buys = 0,
+    coins = 1,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Bazaar)"
+);
+
+// Caravan
+// +1 card, +1 action, at the start of your next turn +1 card
+declare_card!(
+    Caravan,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Caravan)"
+);
+#[typetag::serde]
+impl Card for Caravan {
+    name!("Caravan");
+    card_cost!(4);
+    types!(vec![Action, Duration]);
+    basic_on_play_effects!(cards = 1, actions = 1, buys = 0, coins = 0);
+
+    fn effects_at_start_of_turn(&self, game: &mut Game, player_index: usize) -> Result {
+        game.draw_cards(player_index, 1)
+    }
+}
+
+// Cutpurse
+// +2 coins, each other player discards a Copper, or reveals a hand without
+// any Coppers
+declare_card!(
+    Cutpurse,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Cutpurse)"
+);
+#[typetag::serde]
+impl Card for Cutpurse {
+    name!("Cutpurse");
+    card_cost!(4);
+    types!(vec![Action, Attack]);
+    basic_on_play_effects!(cards = 0, actions = 0, buys = 0, coins = 2);
+
+    fn attack_target(&self) -> Option<AttackTarget> {
+        Some(EveryoneElse)
+    }
+
+    fn attack_effects(&self, game: &mut Game, player_index: usize) -> Result {
+        let player = game.player(player_index)?;
+        if let Some(index) = player.hand.iter().position(|c| c.name() == Copper.name()) {
+            return game.discard_from_hand(player_index, vec![index]);
+        }
+
+        let cards = player.hand.iter().cloned().collect();
+        game.reveal_cards(player_index, cards)?;
+
+        Ok(())
+    }
+}
+
+// Fishing Village
+// +2 actions, +1 coin, at the start of your next turn +1 action and +1 coin
+declare_card!(
+    FishingVillage,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Fishing_Village)"
+);
+#[typetag::serde]
+impl Card for FishingVillage {
+    name!("Fishing Village");
+    card_cost!(3);
+    types!(vec![Action, Duration]);
+    basic_on_play_effects!(cards = 0, actions = 2, buys = 0, coins = 1);
+
+    fn effects_at_start_of_turn(&self, game: &mut Game, player_index: usize) -> Result {
+        let player = game.player_mut(player_index)?;
+        player.add_actions(1);
+        player.add_coins(1);
+
+        Ok(())
+    }
+}
+
+// Haven
+// +1 card, +1 action, set aside a card from your hand face down. At the
+// start of your next turn, put it into your hand
+declare_card!(
+    Haven,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Haven)"
+);
+impl Haven {
+    const SET_ASIDE: usize = 0;
+}
+
+#[typetag::serde]
+impl Card for Haven {
+    name!("Haven");
+    card_cost!(2);
+    types!(vec![Action, Duration]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        game.draw_cards(player_index, 1)?;
+        game.player_mut(player_index)?.add_actions(1);
+
+        choose_exact_from_hand(
+            game,
+            player_index,
+            1,
+            "Choose a card to set aside",
+            Then::card(self, player_index, Self::SET_ASIDE),
+        );
+        Ok(())
+    }
+
+    fn effects_at_start_of_turn(&self, game: &mut Game, player_index: usize) -> Result {
+        if !game.player(player_index)?.mat(Mat::Haven).is_empty() {
+            game.move_card(
+                player_index,
+                Mat::Haven.into(),
+                Zone::Hand,
+                Selector::Index(0),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::SET_ASIDE {
+            return Ok(());
+        }
+
+        if let Some(&index) = chosen_from_hand(game, player_index, 1, &resume.reply)?.first() {
+            game.move_card(
+                player_index,
+                Zone::Hand,
+                Mat::Haven.into(),
+                Selector::Index(index),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+// Island
+// Put this and a card from your hand onto your Island mat. Worth 2 VP
+declare_card!(
+    Island,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Island)"
+);
+impl Island {
+    const SET_ASIDE: usize = 0;
+}
+
+#[typetag::serde]
+impl Card for Island {
+    name!("Island");
+    card_cost!(4);
+    types!(vec![Action, Victory]);
+    victory_points!(2);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        choose_exact_from_hand(
+            game,
+            player_index,
+            1,
+            "Choose a card to put on your Island mat",
+            Then::card(self, player_index, Self::SET_ASIDE),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::SET_ASIDE {
+            return Ok(());
+        }
+
+        let chosen = chosen_from_hand(game, player_index, 1, &resume.reply)?;
+        move_from_play(game, player_index, self, Mat::Island.into())?;
+        if let Some(&index) = chosen.first() {
+            game.move_card(
+                player_index,
+                Zone::Hand,
+                Mat::Island.into(),
+                Selector::Index(index),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+// Lighthouse
+// +1 action, now and at the start of your next turn +1 coin. Until then,
+// attacks played by other players don't affect you
+declare_card!(
+    Lighthouse,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Lighthouse)"
+);
+#[typetag::serde]
+impl Card for Lighthouse {
+    name!("Lighthouse");
+    card_cost!(2);
+    types!(vec![Action, Duration]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        // Cleared when the player's next turn starts
+        let player = game.player_mut(player_index)?;
+        player.add_actions(1);
+        player.add_coins(1);
+        player.state.immune_to_attacks = true;
+
+        Ok(())
+    }
+
+    fn effects_at_start_of_turn(&self, game: &mut Game, player_index: usize) -> Result {
+        game.player_mut(player_index)?.add_coins(1);
+        Ok(())
+    }
+}
+
+// Lookout
+// +1 action, look at the top 3 cards of your deck. Trash one of them,
+// discard one of them, and put the other back
+declare_card!(
+    Lookout,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Lookout)"
+);
+impl Lookout {
+    const TRASH: usize = 0;
+    const DISCARD: usize = 1;
+    const ORDER: usize = 2;
+}
+
+#[typetag::serde]
+impl Card for Lookout {
+    name!("Lookout");
+    card_cost!(3);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        game.player_mut(player_index)?.add_actions(1);
+
+        let cards = game.take_from_deck(player_index, 3)?;
+        if cards.is_empty() {
+            return Ok(());
+        }
+
+        choose_one_from_selection(
+            game,
+            player_index,
+            &cards,
+            "Choose a card to trash",
+            Then::card(self, player_index, Self::TRASH).with_cards(cards.clone()),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        match resume.step {
+            Self::TRASH => {
+                let mut cards = resume.cards;
+                let card = cards.remove(chosen_one(&resume.reply, cards.len())?);
+                move_set_aside(game, player_index, vec![card], Zone::Trash)?;
+
+                if cards.is_empty() {
+                    return Ok(());
+                }
+
+                choose_one_from_selection(
+                    game,
+                    player_index,
+                    &cards,
+                    "Choose a card to discard",
+                    Then::card(self, player_index, Self::DISCARD).with_cards(cards.clone()),
+                );
+                Ok(())
+            }
+            Self::DISCARD => {
+                let mut cards = resume.cards;
+                let card = cards.remove(chosen_one(&resume.reply, cards.len())?);
+                move_set_aside(game, player_index, vec![card], Zone::Discard)?;
+
+                let then = Then::card(self, player_index, Self::ORDER);
+                put_back_in_order(game, player_index, cards, then)
+            }
+            Self::ORDER => put_back_chosen(game, player_index, resume),
+            _ => Ok(()),
+        }
+    }
+}
+
+// Merchant Ship
+// Now and at the start of your next turn: +2 coins
+declare_card!(
+    MerchantShip,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Merchant_Ship)"
+);
+#[typetag::serde]
+impl Card for MerchantShip {
+    name!("Merchant Ship");
+    card_cost!(5);
+    types!(vec![Action, Duration]);
+    basic_on_play_effects!(cards = 0, actions = 0, buys = 0, coins = 2);
+
+    fn effects_at_start_of_turn(&self, game: &mut Game, player_index: usize) -> Result {
+        game.player_mut(player_index)?.add_coins(2);
+        Ok(())
+    }
+}
+
+// Native Village
+// +2 actions, choose one: put the top card of your deck face down on your
+// Native Village mat, or put all the cards from your mat into your hand
+declare_card!(
+    NativeVillage,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Native_Village)"
+);
+impl NativeVillage {
+    const CHOOSE: usize = 0;
+}
+
+#[typetag::serde]
+impl Card for NativeVillage {
+    name!("Native Village");
+    card_cost!(2);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        game.player_mut(player_index)?.add_actions(2);
+
+        let options = [
+            "Put the top card of your deck on your mat",
+            "Put the cards from your mat into your hand",
+        ];
+        game.ask(
+            Ask::options(
+                player_index,
+                ChoiceCountOptions::Exact { count: 1 },
+                &options,
+                "Choose one",
+            ),
+            Then::card(self, player_index, Self::CHOOSE),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::CHOOSE {
+            return Ok(());
+        }
+
+        let (from, to, selector) = match chosen_one(&resume.reply, 2)? {
+            0 => (Zone::Deck, Mat::NativeVillage.into(), Selector::Top(1)),
+            _ => (Mat::NativeVillage.into(), Zone::Hand, Selector::All),
+        };
+        game.move_card(player_index, from, to, selector)?;
+
+        Ok(())
+    }
+}
+
+// Outpost
+// If this is the first time you played an Outpost this turn, and the
+// previous turn wasn't yours, then take an extra turn after this one, and
+// you only draw 3 cards for your next hand
+declare_card!(
+    Outpost,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Outpost)"
+);
+#[typetag::serde]
+impl Card for Outpost {
+    name!("Outpost");
+    card_cost!(5);
+    types!(vec![Action, Duration]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        // The first Outpost played this turn has already made the next hand
+        // smaller
+        let played_before = game
+            .modifiers_for(player_index)
+            .any(|modifier| matches!(modifier.kind, ModifierKind::HandSize(_)));
+        let after_own_turn = game
+            .current_extra_turn(player_index)
+            .is_some_and(|turn| turn.controller.is_none());
+        if played_before || after_own_turn {
+            return not_kept_in_play(game, player_index, self);
+        }
+
+        game.add_modifier(Modifier::new(
+            player_index,
+            ModifierKind::HandSize(3),
+            Lifetime::ThisTurn,
+        ));
+        game.take_extra_turn(ExtraTurn::new(player_index));
+
+        Ok(())
+    }
+}
+
+// Salvager
+// +1 buy, trash a card from your hand. + coins equal to its cost
+declare_card!(
+    Salvager,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Salvager)"
+);
+impl Salvager {
+    const TRASH: usize = 0;
+}
+
+#[typetag::serde]
+impl Card for Salvager {
+    name!("Salvager");
+    card_cost!(4);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        game.player_mut(player_index)?.add_buys(1);
+
+        choose_exact_from_hand(
+            game,
+            player_index,
+            1,
+            "Choose a card to trash",
+            Then::card(self, player_index, Self::TRASH),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::TRASH {
+            return Ok(());
+        }
+
+        let Some(&index) = chosen_from_hand(game, player_index, 1, &resume.reply)?.first() else {
+            return Ok(());
+        };
+
+        let coins = game
+            .effective_cost(&*game.player(player_index)?.hand[index], player_index)
+            .coins;
+        game.trash_from_hand(player_index, vec![index])?;
+        game.player_mut(player_index)?.add_coins(coins);
+
+        Ok(())
+    }
+}
+
+// Sea Chart
+// +1 card, +1 action, reveal the top card of your deck. If you have a copy of
+// it in play, put it into your hand
+declare_card!(
+    SeaChart,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Sea_Chart)"
+);
+#[typetag::serde]
+impl Card for SeaChart {
+    name!("Sea Chart");
+    card_cost!(3);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        game.draw_cards(player_index, 1)?;
+        game.player_mut(player_index)?.add_actions(1);
+
+        let card = match game.reveal(player_index, 1) {
+            Ok(mut cards) => cards.remove(0),
+            Err(Error::EmptyDeck) => return Ok(()),
+            Err(e) => return Err(e),
+        };
+
+        let player = game.player(player_index)?;
+        let to = if player
+            .in_play
+            .iter()
+            .chain(player.durations.iter())
+            .any(|c| c.name() == card.name())
+        {
+            Zone::Hand
+        } else {
+            Zone::Deck
+        };
+
+        move_set_aside(game, player_index, vec![card], to)
+    }
+}
+
+// Sea Witch
+// +2 cards, each other player gains a Curse. At the start of your next turn,
+// +2 cards then discard 2 cards
+declare_card!(
+    SeaWitch,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Sea_Witch)"
+);
+impl SeaWitch {
+    const DISCARD: usize = 0;
+}
+
+#[typetag::serde]
+impl Card for SeaWitch {
+    name!("Sea Witch");
+    card_cost!(5);
+    types!(vec![Action, Attack, Duration]);
+    basic_on_play_effects!(cards = 2, actions = 0, buys = 0, coins = 0);
+
+    fn attack_target(&self) -> Option<AttackTarget> {
+        Some(EveryoneElse)
+    }
+
+    fn attack_effects(&self, game: &mut Game, player_index: usize) -> Result {
+        gain_if_available(game, player_index, Box::new(BasicCurse), Zone::Discard)
+    }
+
+    fn effects_at_start_of_turn(&self, game: &mut Game, player_index: usize) -> Result {
+        game.draw_cards(player_index, 2)?;
+        let then = Then::card(self, player_index, Self::DISCARD);
+        discard_exact_from_hand(game, player_index, 2, then);
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::DISCARD {
+            return Ok(());
+        }
+
+        discard_chosen(game, player_index, 2, &resume.reply)
+    }
+}
+
+// Smugglers
+// Gain a copy of a card costing up to 6 coins that the player to your right
+// gained on their last turn
+declare_card!(
+    Smugglers,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Smugglers)"
+);
+impl Smugglers {
+    const GAIN: usize = 0;
+}
+
+#[typetag::serde]
+impl Card for Smugglers {
+    name!("Smugglers");
+    card_cost!(3);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        let player_count = game.player_count();
+        let right = (player_index + player_count - 1) % player_count;
+
+        let choices: CardList = game
+            .player(right)?
+            .state
+            .gained
+            .iter()
+            .filter(|card| {
+                game.effective_cost(&***card, player_index) <= Cost::new(6)
+                    && game
+                        .supply
+                        .get(card.name())
+                        .is_some_and(|entry| entry.count > 0)
+            })
+            .cloned()
+            .collect();
+
+        if choices.is_empty() {
+            return Ok(());
+        }
+
+        choose_one_from_selection(
+            game,
+            player_index,
+            &choices,
+            "Choose a card to gain",
+            Then::card(self, player_index, Self::GAIN).with_cards(choices.clone()),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::GAIN {
+            return Ok(());
+        }
+
+        let mut choices = resume.cards;
+        let index = chosen_one(&resume.reply, choices.len())?;
+        game.gain(player_index, choices.remove(index))
+    }
+}
+
+// Tactician
+// If you have at least one card in hand, discard your hand, and at the start
+// of your next turn +5 cards, +1 action and +1 buy
+declare_card!(
+    Tactician,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Tactician)"
+);
+#[typetag::serde]
+impl Card for Tactician {
+    name!("Tactician");
+    card_cost!(5);
+    types!(vec![Action, Duration]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        // Without anything to discard, it is discarded at cleanup as usual
+        if game.player(player_index)?.hand.is_empty() {
+            return not_kept_in_play(game, player_index, self);
+        }
+
+        game.move_card(player_index, Zone::Hand, Zone::Discard, Selector::All)?;
+        Ok(())
+    }
+
+    fn effects_at_start_of_turn(&self, game: &mut Game, player_index: usize) -> Result {
+        game.draw_cards(player_index, 5)?;
+
+        let player = game.player_mut(player_index)?;
+        player.add_actions(1);
+        player.add_buys(1);
+
+        Ok(())
+    }
+}
+
+// Tide Pools
+// +3 cards, +1 action, at the start of your next turn discard 2 cards
+declare_card!(
+    TidePools,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Tide_Pools)"
+);
+impl TidePools {
+    const DISCARD: usize = 0;
+}
+
+#[typetag::serde]
+impl Card for TidePools {
+    name!("Tide Pools");
+    card_cost!(4);
+    types!(vec![Action, Duration]);
+    basic_on_play_effects!(cards = 3, actions = 1, buys = 0, coins = 0);
+
+    fn effects_at_start_of_turn(&self, game: &mut Game, player_index: usize) -> Result {
+        let then = Then::card(self, player_index, Self::DISCARD);
+        discard_exact_from_hand(game, player_index, 2, then);
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::DISCARD {
+            return Ok(());
+        }
+
+        discard_chosen(game, player_index, 2, &resume.reply)
+    }
+}
+
+// Treasure Map
+// Trash this and a Treasure Map from your hand. If you trashed two Treasure
+// Maps, gain 4 Golds onto your deck
+declare_card!(
+    TreasureMap,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Treasure_Map)"
+);
+#[typetag::serde]
+impl Card for TreasureMap {
+    name!("Treasure Map");
+    card_cost!(4);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        let trashed_self = trash_from_play(game, player_index, self)?;
+
+        let other = game
+            .player(player_index)?
+            .hand
+            .iter()
+            .position(|c| c.name() == self.name());
+        let Some(index) = other else {
+            return Ok(());
+        };
+        game.trash_from_hand(player_index, vec![index])?;
+
+        if trashed_self {
+            for _ in 0..4 {
+                gain_if_available(game, player_index, Box::new(Gold), Zone::Deck)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Treasury
+// +1 card, +1 action, +$1, when you discard this from play, if you didn't buy
+// a Victory card this turn, you may put this onto your deck
+declare_card!(
+    Treasury,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Treasury)"
+);
+impl Treasury {
+    const TOPDECK: usize = 0;
+}
+
+#[typetag::serde]
+impl Card for Treasury {
+    name!("Treasury");
+    card_cost!(5);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        game.draw_cards(player_index, 1)?;
+        let player = game.player_mut(player_index)?;
+        player.add_actions(1);
+        player.add_coins(1);
+
+        Ok(())
+    }
+
+    fn effects_at_cleanup(&self, game: &mut Game, player_index: usize) -> Result {
+        let player = game.player(player_index)?;
+        if player.state.bought.iter().any(|card| card.is_victory()) {
+            return Ok(());
+        }
+
+        game.ask(
+            Ask::yes_or_no(player_index, "Put Treasury onto your deck?"),
+            Then::card(self, player_index, Self::TOPDECK),
+        );
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step == Self::TOPDECK && resume.reply.yes() {
+            move_from_play(game, player_index, self, Zone::Deck)?;
+        }
+
+        Ok(())
+    }
+}
+
+// Warehouse
+// +3 cards, +1 action, discard 3 cards
+declare_card!(
+    Warehouse,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Warehouse)"
+);
+impl Warehouse {
+    const DISCARD: usize = 0;
+}
+
+#[typetag::serde]
+impl Card for Warehouse {
+    name!("Warehouse");
+    card_cost!(3);
+    types!(vec![Action]);
+
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        game.draw_cards(player_index, 3)?;
+        game.player_mut(player_index)?.add_actions(1);
+
+        let then = Then::card(self, player_index, Self::DISCARD);
+        discard_exact_from_hand(game, player_index, 3, then);
+        Ok(())
+    }
+
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        if resume.step != Self::DISCARD {
+            return Ok(());
+        }
+
+        discard_chosen(game, player_index, 3, &resume.reply)
+    }
+}
+
+// Wharf
+// Now and at the start of your next turn: +2 cards, +1 buy
+declare_card!(
+    Wharf,
+    "[Wiki link](http://wiki.dominionstrategy.com/index.php/Wharf)"
+);
+#[typetag::serde]
+impl Card for Wharf {
+    name!("Wharf");
+    card_cost!(5);
+    types!(vec![Action, Duration]);
+    basic_on_play_effects!(cards = 2, actions = 0, buys = 1, coins = 0);
+
+    fn effects_at_start_of_turn(&self, game: &mut Game, player_index: usize) -> Result {
+        self.effects_on_play(game, player_index)
+    }
+}
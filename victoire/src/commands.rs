@@ -0,0 +1,161 @@
+//! Command-style API for clients that act on behalf of the player whose turn
+//! it is, rather than answering prompts
+//!
+//! Each [`Command`] is checked against the current state of the game before
+//! anything is changed, so invalid commands return an [`Error`] and leave the
+//! game as it was. Errors from card effects once a command is under way, e.g.
+//! from a client's answers, are returned as they happen, and whatever the
+//! effects did up to that point stays done.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    callbacks::Callbacks,
+    error::{Error, Result},
+    events::GameEvent,
+    types::{Card, CardType, Game, Phase, TokenKind},
+};
+
+/// Something a player can do on their turn, applied with [`Game::apply`]
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Command {
+    /// Play the action card at the given index in the player's hand
+    PlayAction { hand_index: usize },
+    /// Play the treasure card at the given index in the player's hand
+    PlayTreasure { hand_index: usize },
+    /// Play every treasure card in the player's hand
+    PlayAllTreasures,
+    /// Spend some of the player's Villagers or Coffers
+    SpendTokens { kind: TokenKind, count: usize },
+    /// Buy a card from the supply
+    Buy { card: Box<dyn Card> },
+    /// Move on from the action phase to the buy phase, or from the buy phase
+    /// to the end of the turn
+    EndPhase,
+}
+
+impl Game {
+    /// Applies a command for the given player
+    ///
+    /// If it is the player's turn but their turn hasn't started yet, it is
+    /// started first, once the command has been checked. Ending the buy phase
+    /// cleans up and passes the turn to the next player. `callbacks` are only
+    /// used for input needed by card effects.
+    pub fn apply(
+        &mut self,
+        player_index: usize,
+        command: Command,
+        callbacks: &dyn Callbacks,
+    ) -> Result {
+        self.check_idle()?;
+
+        if self.player_count() < 2 {
+            return Err(Error::NotEnoughPlayers);
+        }
+
+        if self.is_game_over() {
+            return Err(Error::GameOver);
+        }
+
+        if player_index != self.current_turn {
+            return Err(Error::OutOfTurn);
+        }
+
+        let starting = self.players[player_index].phase == Phase::OutOfTurn;
+        self.check_command(player_index, starting, &command)?;
+
+        if starting {
+            self.started = true;
+            self.start_turn(player_index, callbacks)?;
+        }
+
+        match command {
+            Command::PlayAction { hand_index } => {
+                self.play_action_from_hand(player_index, hand_index, callbacks)
+            }
+            Command::PlayTreasure { hand_index } => {
+                self.play_treasure(player_index, hand_index, callbacks)
+            }
+            Command::PlayAllTreasures => self.play_all_treasures(player_index, callbacks),
+            Command::SpendTokens { kind, count } => self.spend_tokens(player_index, kind, count),
+            Command::Buy { card } => self.buy_card(player_index, card, callbacks),
+            Command::EndPhase => match self.players[player_index].phase {
+                Phase::ActionPhase => {
+                    self.set_phase(player_index, Phase::BuyPhase)?;
+
+                    let resources = &mut self.players[player_index].resources;
+                    resources.coins_remaining = resources.coins + resources.temp_coins;
+                    Ok(())
+                }
+                _ => {
+                    self.end_turn(player_index, callbacks)?;
+                    self.next_turn();
+
+                    if self.is_game_over() {
+                        self.emit(GameEvent::GameEnded {
+                            result: self.result(),
+                        });
+                    }
+                    Ok(())
+                }
+            },
+        }
+    }
+
+    /// Checks a command without changing anything. If the player's turn is
+    /// `starting`, it is checked as if they were at the start of their
+    /// action phase
+    fn check_command(&self, player_index: usize, starting: bool, command: &Command) -> Result {
+        let player = &self.players[player_index];
+        let phase = if starting {
+            Phase::ActionPhase
+        } else {
+            player.phase
+        };
+
+        match command {
+            Command::PlayAction { hand_index } => {
+                expect_phase(phase, Phase::ActionPhase)?;
+                // A turn that is starting gets its first action
+                if !starting && player.resources.actions == 0 {
+                    return Err(Error::NoActionsLeft);
+                }
+                // Starting a turn can only add cards to the hand, e.g. from
+                // Durations, so the card stays where it is
+                self.expect_hand_card(player_index, *hand_index, CardType::Action)
+            }
+            Command::PlayTreasure { hand_index } => {
+                expect_phase(phase, Phase::BuyPhase)?;
+                self.expect_hand_card(player_index, *hand_index, CardType::Treasure)
+            }
+            Command::PlayAllTreasures => expect_phase(phase, Phase::BuyPhase),
+            Command::SpendTokens { kind, count } => {
+                self.check_spend_tokens(player_index, phase, *kind, *count)
+            }
+            Command::Buy { card } => self.check_buy(player_index, phase, &**card),
+            Command::EndPhase => match phase {
+                Phase::ActionPhase | Phase::BuyPhase => Ok(()),
+                _ => Err(Error::WrongPhase),
+            },
+        }
+    }
+
+    fn expect_hand_card(&self, player_index: usize, index: usize, expected: CardType) -> Result {
+        match self.players[player_index].hand.get(index) {
+            None => Err(Error::InvalidHandIndex { index }),
+            Some(card) if !card.types().contains(&expected) => {
+                Err(Error::CardTypeMisMatch { expected })
+            }
+            Some(_) => Ok(()),
+        }
+    }
+}
+
+fn expect_phase(phase: Phase, expected: Phase) -> Result {
+    if phase == expected {
+        Ok(())
+    } else {
+        Err(Error::WrongPhase)
+    }
+}
@@ -0,0 +1,1086 @@
+//! The engine's pending work, and the pull-based alternative to [`Callbacks`]
+//!
+//! Everything that happens in a game is broken down into tasks, which wait on
+//! a stack kept in the [`Game`] itself. Card effects never wait for a player:
+//! when they need a choice made, they [ask](Game::ask) for it and name the
+//! step of their effects that [carries on](Card::resume) with the answer.
+//! Since the stack is part of the game, a game can be saved in the middle of
+//! a turn and picked up again later.
+//!
+//! Clients can answer each [`Decision`] as it comes up by calling
+//! [`Game::step`] and [`Game::respond`], or have the engine ask [`Callbacks`]
+//! for them with [`Game::resolve`] and the procedures built on it, such as
+//! [`Game::turn`].
+//!
+//! The `_async` methods on [`Game`], such as [`Game::turn_async`], await
+//! [`AsyncCallbacks`] for each decision. They work on a copy of the game,
+//! which replaces it once the whole procedure has finished, so dropping one
+//! of their futures part way through leaves the game as it was.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    callbacks::{
+        AsyncCallbacks, Callbacks, ChoiceCountOptions, TreasureChoice, MAX_INVALID_ANSWERS,
+    },
+    error::{Error, Result},
+    events::GameEvent,
+    types::{Card, CardList, Gain, Game, GameResult, Phase, Supply, TokenKind, Zone},
+};
+
+/// A choice a player has to make before the game can continue
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Decision {
+    /// Identifies this decision when [responding](Game::respond) to it
+    pub id: u64,
+    /// The player who has to decide
+    pub player_index: usize,
+    /// The player the decision is made for, if another player is deciding
+    /// for them, e.g. during a turn controlled with Possession. Cards to
+    /// choose from are this player's
+    pub on_behalf_of: Option<usize>,
+    pub kind: DecisionKind,
+    pub prompt: String,
+    /// The cards or players to choose from. Empty for yes/no questions
+    pub options: Vec<DecisionOption>,
+    /// The fewest options that can be chosen
+    pub min: usize,
+    /// The most options that can be chosen
+    pub max: usize,
+}
+
+/// What sort of choice a [`Decision`] is
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DecisionKind {
+    /// Choose a card from the supply, or nothing
+    Supply,
+    /// Choose cards from the player's hand
+    Hand,
+    /// Choose cards from the player's discard pile
+    Discard,
+    /// Choose cards from the trash
+    Trash,
+    /// Choose from a list of cards that are not in any of the above
+    Selection,
+    /// Choose from a list of options, e.g. for "choose one" effects
+    Options,
+    /// Answer a yes/no question
+    YesOrNo,
+    /// Choose one or more players
+    Players,
+    /// Choose a treasure from the player's hand to play, choose nothing to
+    /// stop playing treasures, or [play them all](Answer::PlayAllTreasures)
+    Treasure,
+    /// Choose how many tokens of the given kind to spend. The index of the
+    /// chosen option is the number of tokens
+    Tokens { kind: TokenKind },
+}
+
+/// Something that can be chosen in a [`Decision`]
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum DecisionOption {
+    Card(Box<dyn Card>),
+    Player(usize),
+    Text(String),
+}
+
+/// A player's answer to a [`Decision`]
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Answer {
+    /// The indices of the chosen [options](Decision::options)
+    Choose(Vec<usize>),
+    /// The answer to a yes/no question
+    YesOrNo(bool),
+    /// Play every treasure left in hand, for [`DecisionKind::Treasure`]
+    PlayAllTreasures,
+}
+
+/// The outcome of a call to [`Game::step`]
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Step {
+    /// A player needs to make a decision before the game can continue
+    Decision(Decision),
+    /// A player finished their turn
+    TurnEnded { player_index: usize },
+    /// The game is over
+    GameOver(GameResult),
+}
+
+impl Decision {
+    /// Checks that `answer` is a valid response to this decision
+    pub fn validate(&self, answer: &Answer) -> Result {
+        let valid = match (self.kind, answer) {
+            (DecisionKind::YesOrNo, Answer::YesOrNo(_))
+            | (DecisionKind::Treasure, Answer::PlayAllTreasures) => true,
+            (DecisionKind::YesOrNo, _) | (_, Answer::YesOrNo(_) | Answer::PlayAllTreasures) => {
+                false
+            }
+            (_, Answer::Choose(indices)) => {
+                let mut sorted = indices.clone();
+                sorted.sort_unstable();
+                sorted.dedup();
+
+                sorted.len() == indices.len()
+                    && (self.min..=self.max).contains(&indices.len())
+                    && indices.iter().all(|&i| i < self.options.len())
+            }
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(Error::InvalidAnswer)
+        }
+    }
+
+    /// How many options can be chosen, in the form used by [`Callbacks`]
+    pub fn count(&self) -> ChoiceCountOptions {
+        if self.min == self.max {
+            ChoiceCountOptions::Exact { count: self.max }
+        } else {
+            ChoiceCountOptions::UpTo { max: self.max }
+        }
+    }
+
+    /// Turns a [validated](Decision::validate) answer into the reply the
+    /// engine carries on with
+    fn reply(&self, answer: Answer) -> Reply {
+        match (self.kind, answer) {
+            (_, Answer::YesOrNo(yes)) => Reply::YesOrNo(yes),
+            (_, Answer::PlayAllTreasures) => Reply::Treasure(TreasureChoice::PlayAll),
+            (DecisionKind::Supply, Answer::Choose(indices)) => {
+                let card = indices.first().and_then(|&i| match &self.options[i] {
+                    DecisionOption::Card(card) => Some(card.clone()),
+                    _ => None,
+                });
+                Reply::Card(card)
+            }
+            (DecisionKind::Treasure, Answer::Choose(indices)) => {
+                Reply::Treasure(match indices.first() {
+                    Some(&hand_index) => TreasureChoice::Play { hand_index },
+                    None => TreasureChoice::Stop,
+                })
+            }
+            (DecisionKind::Tokens { .. }, Answer::Choose(indices)) => {
+                Reply::Tokens(indices.first().copied().unwrap_or(0))
+            }
+            (_, Answer::Choose(indices)) => Reply::Chosen(indices),
+        }
+    }
+}
+
+/// A choice a player has to make, as [asked](Game::ask) for by card effects.
+/// It is presented as a [`Decision`] once everything scheduled before it has
+/// happened
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Ask {
+    /// The player the choice is for. If someone else controls their turn,
+    /// e.g. with Possession, that player decides for them
+    pub player_index: usize,
+    pub kind: DecisionKind,
+    pub prompt: String,
+    pub count: ChoiceCountOptions,
+    /// The choices for [`DecisionKind::Selection`], [`DecisionKind::Options`]
+    /// and [`DecisionKind::Tokens`]. Everything else is chosen from the state
+    /// of the game when the decision is presented
+    pub options: Vec<DecisionOption>,
+}
+
+impl Ask {
+    fn new(
+        player_index: usize,
+        kind: DecisionKind,
+        count: ChoiceCountOptions,
+        prompt: &str,
+    ) -> Ask {
+        Ask {
+            player_index,
+            kind,
+            prompt: prompt.to_owned(),
+            count,
+            options: Vec::new(),
+        }
+    }
+
+    /// Choose a card from the supply, or nothing
+    pub fn supply(player_index: usize, prompt: &str) -> Ask {
+        let count = ChoiceCountOptions::UpTo { max: 1 };
+        Ask::new(player_index, DecisionKind::Supply, count, prompt)
+    }
+
+    /// Choose cards from the player's hand
+    pub fn hand(player_index: usize, count: ChoiceCountOptions, prompt: &str) -> Ask {
+        Ask::new(player_index, DecisionKind::Hand, count, prompt)
+    }
+
+    /// Choose cards from the player's discard pile
+    pub fn discard(player_index: usize, count: ChoiceCountOptions, prompt: &str) -> Ask {
+        Ask::new(player_index, DecisionKind::Discard, count, prompt)
+    }
+
+    /// Choose cards from the trash
+    pub fn trash(player_index: usize, count: ChoiceCountOptions, prompt: &str) -> Ask {
+        Ask::new(player_index, DecisionKind::Trash, count, prompt)
+    }
+
+    /// Choose from the given cards
+    pub fn selection(
+        player_index: usize,
+        count: ChoiceCountOptions,
+        cards: &[Box<dyn Card>],
+        prompt: &str,
+    ) -> Ask {
+        Ask {
+            options: cards.iter().cloned().map(DecisionOption::Card).collect(),
+            ..Ask::new(player_index, DecisionKind::Selection, count, prompt)
+        }
+    }
+
+    /// Answer a yes/no question
+    pub fn yes_or_no(player_index: usize, prompt: &str) -> Ask {
+        let count = ChoiceCountOptions::Exact { count: 0 };
+        Ask::new(player_index, DecisionKind::YesOrNo, count, prompt)
+    }
+
+    /// Choose one or more players
+    pub fn players(player_index: usize, count: ChoiceCountOptions, prompt: &str) -> Ask {
+        Ask::new(player_index, DecisionKind::Players, count, prompt)
+    }
+
+    /// Choose from a list of options
+    pub fn options(
+        player_index: usize,
+        count: ChoiceCountOptions,
+        options: &[&str],
+        prompt: &str,
+    ) -> Ask {
+        Ask {
+            options: text_options(options.iter().copied()),
+            ..Ask::new(player_index, DecisionKind::Options, count, prompt)
+        }
+    }
+
+    /// Choose a treasure to play in the buy phase
+    pub(crate) fn treasure(player_index: usize) -> Ask {
+        let count = ChoiceCountOptions::UpTo { max: 1 };
+        let prompt = "Choose a treasure to play";
+        Ask::new(player_index, DecisionKind::Treasure, count, prompt)
+    }
+
+    /// Choose how many of the `available` tokens to spend
+    pub(crate) fn tokens(player_index: usize, kind: TokenKind, available: usize) -> Ask {
+        let count = ChoiceCountOptions::Exact { count: 1 };
+        let prompt = format!("Choose how many {kind:?} to spend");
+        let counts = (0..=available)
+            .map(|count| count.to_string())
+            .collect::<Vec<_>>();
+        Ask {
+            options: text_options(counts.iter().map(String::as_str)),
+            ..Ask::new(player_index, DecisionKind::Tokens { kind }, count, &prompt)
+        }
+    }
+
+    /// The cards to choose from in a selection
+    fn cards(&self) -> CardList {
+        self.options
+            .iter()
+            .filter_map(|option| match option {
+                DecisionOption::Card(card) => Some(card.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The texts to choose from in a list of options
+    fn texts(&self) -> Vec<&str> {
+        self.options
+            .iter()
+            .filter_map(|option| match option {
+                DecisionOption::Text(text) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+fn text_options<'a>(texts: impl IntoIterator<Item = &'a str>) -> Vec<DecisionOption> {
+    texts
+        .into_iter()
+        .map(|text| DecisionOption::Text(text.to_owned()))
+        .collect()
+}
+
+/// The answer to an [`Ask`], given to the step of a card's effects that
+/// carries on from it
+///
+/// Answers given through [`Callbacks`] are passed on as they are, so card
+/// effects should check them. Answers given through [`Game::respond`] have
+/// already been [validated](Decision::validate).
+#[non_exhaustive]
+#[derive(Clone, Debug, Default)]
+pub enum Reply {
+    /// Nothing was asked
+    #[default]
+    Nothing,
+    /// The indices of the chosen cards, players or options
+    Chosen(Vec<usize>),
+    /// The answer to a yes/no question
+    YesOrNo(bool),
+    /// The card chosen from the supply, if any
+    Card(Option<Box<dyn Card>>),
+    /// What to do next while playing treasures
+    Treasure(TreasureChoice),
+    /// How many tokens to spend
+    Tokens(usize),
+}
+
+impl Reply {
+    /// The indices chosen, if any
+    pub fn indexes(&self) -> &[usize] {
+        match self {
+            Reply::Chosen(indices) => indices,
+            _ => &[],
+        }
+    }
+
+    /// The first index chosen, if any
+    pub fn index(&self) -> Option<usize> {
+        self.indexes().first().copied()
+    }
+
+    /// Whether the answer to a yes/no question was yes
+    pub fn yes(&self) -> bool {
+        matches!(self, Reply::YesOrNo(true))
+    }
+
+    /// The card chosen from the supply, if any
+    pub fn card(&self) -> Option<Box<dyn Card>> {
+        match self {
+            Reply::Card(card) => card.clone(),
+            _ => None,
+        }
+    }
+}
+
+/// Where a card's effects carry on from, as given to [`Card::resume`]
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub struct Resume {
+    /// The step the card [asked](Game::ask) to carry on with
+    pub step: usize,
+    /// Any cards passed along [with](Then::with_cards) the step
+    pub cards: CardList,
+    /// The answer to the decision, if the step was waiting on one
+    pub reply: Reply,
+}
+
+/// What happens once an [`Ask`] has been answered, or once everything
+/// scheduled before it [has happened](Game::then)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Then(pub(crate) Task);
+
+impl Then {
+    /// Carries on with the given step of a card's effects, which is passed
+    /// to the card's [`Card::resume`]
+    pub fn card(card: &(dyn Card + 'static), player_index: usize, step: usize) -> Then {
+        Then(Task::Card {
+            card: dyn_clone::clone_box(card),
+            player_index,
+            step,
+            cards: CardList::new(),
+        })
+    }
+
+    /// Passes cards along to the step, e.g. cards that were revealed
+    pub fn with_cards(self, cards: CardList) -> Then {
+        match self.0 {
+            Task::Card {
+                card,
+                player_index,
+                step,
+                ..
+            } => Then(Task::Card {
+                card,
+                player_index,
+                step,
+                cards,
+            }),
+            task => Then(task),
+        }
+    }
+}
+
+/// Which of a card's effects to run
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum Hook {
+    Play,
+    Gain,
+    Buy,
+    StartOfTurn,
+    Trash,
+    Discard,
+    Reveal,
+    Cleanup,
+    Attack,
+    Reaction,
+}
+
+/// Something the engine has yet to do
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) enum Task {
+    /// Presents a decision, then carries on with the answer
+    Ask {
+        ask: Ask,
+        then: Box<Task>,
+    },
+    /// A step of a card's effects
+    Card {
+        card: Box<dyn Card>,
+        player_index: usize,
+        step: usize,
+        cards: CardList,
+    },
+    /// One of a card's effects
+    Hook {
+        hook: Hook,
+        card: Box<dyn Card>,
+        player_index: usize,
+    },
+    /// Gains a card, once the player's cards have reacted to it
+    Gain {
+        player_index: usize,
+        gain: Gain,
+    },
+    /// Lets a card react to the gain in progress
+    ReactToGain {
+        card: Box<dyn Card>,
+        player_index: usize,
+        zone: Zone,
+    },
+    /// Moves the gain in progress to where it is going
+    LandGain {
+        player_index: usize,
+    },
+    /// Plays an action card that is already in play
+    Play {
+        player_index: usize,
+        card: Box<dyn Card>,
+    },
+    /// Makes the attack of a card that has just been played
+    Attack {
+        player_index: usize,
+        card: Box<dyn Card>,
+    },
+    /// Attacks the chosen player
+    ChooseTarget {
+        player_index: usize,
+        card: Box<dyn Card>,
+    },
+    /// Resolves an attack on the targets that weren't unaffected by it
+    AttackTargets {
+        card: Box<dyn Card>,
+        targets: Vec<usize>,
+    },
+    /// Ends the attack being resolved
+    EndAttack,
+    /// Reveals the chosen reactions
+    Reactions {
+        player_index: usize,
+        reactions: CardList,
+    },
+    /// Reveals a reaction and runs its effects
+    React {
+        player_index: usize,
+        card: Box<dyn Card>,
+    },
+    Turn {
+        player_index: usize,
+    },
+    StartTurn {
+        player_index: usize,
+    },
+    /// Resolves the Duration cards from the player's last turn, one at a time
+    Durations {
+        player_index: usize,
+    },
+    SetPhase {
+        player_index: usize,
+        phase: Phase,
+    },
+    ActionPhase {
+        player_index: usize,
+    },
+    ChooseAction {
+        player_index: usize,
+    },
+    PlayChosenAction {
+        player_index: usize,
+    },
+    SpendTokens {
+        player_index: usize,
+        kind: TokenKind,
+    },
+    BuyPhase {
+        player_index: usize,
+    },
+    PlayTreasures {
+        player_index: usize,
+    },
+    PlayChosenTreasure {
+        player_index: usize,
+    },
+    StartBuying {
+        player_index: usize,
+    },
+    PayDebt {
+        player_index: usize,
+    },
+    ChooseBuy {
+        player_index: usize,
+    },
+    BuyChosen {
+        player_index: usize,
+    },
+    EndTurn {
+        player_index: usize,
+    },
+    Cleanup {
+        player_index: usize,
+    },
+    FinishCleanup {
+        player_index: usize,
+    },
+    LeaveTurn {
+        player_index: usize,
+    },
+}
+
+/// Everything the engine has yet to do, kept in the [`Game`] so that it can
+/// carry on later, e.g. once a decision has been answered
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Pending {
+    /// Tasks waiting to run. The last one runs next
+    tasks: Vec<Task>,
+    /// Tasks scheduled by the running task, which run in order once it
+    /// finishes, before anything that was already waiting
+    staged: Vec<Task>,
+    /// The decision waiting for an answer, if any
+    decision: Option<Decision>,
+    /// The gains in progress, the innermost last
+    gaining: Vec<Gain>,
+}
+
+impl Pending {
+    pub(crate) fn schedule(&mut self, task: Task) {
+        self.staged.push(task);
+    }
+
+    /// Whether the engine isn't in the middle of anything. Tasks scheduled
+    /// outside of it, e.g. by moving cards directly, run the next time it is
+    /// resolved
+    pub(crate) fn is_idle(&self) -> bool {
+        self.tasks.is_empty() && self.decision.is_none()
+    }
+
+    pub(crate) fn gaining(&self) -> Option<&Gain> {
+        self.gaining.last()
+    }
+
+    pub(crate) fn gaining_mut(&mut self) -> Option<&mut Gain> {
+        self.gaining.last_mut()
+    }
+
+    pub(crate) fn start_gain(&mut self, gain: Gain) {
+        self.gaining.push(gain);
+    }
+
+    pub(crate) fn finish_gain(&mut self) -> Option<Gain> {
+        self.gaining.pop()
+    }
+
+    fn flush(&mut self) {
+        self.tasks.extend(self.staged.drain(..).rev());
+    }
+
+    /// Drops everything, after an error has left it impossible to carry on
+    fn clear(&mut self) {
+        *self = Pending::default();
+    }
+}
+
+impl Game {
+    /// Asks a player to make a decision. Once it has been answered, the game
+    /// carries on with `then`
+    pub fn ask(&mut self, ask: Ask, then: Then) {
+        self.pending.schedule(Task::Ask {
+            ask,
+            then: Box::new(then.0),
+        });
+    }
+
+    /// Carries on with `then` once everything scheduled before it has
+    /// happened
+    pub fn then(&mut self, then: Then) {
+        self.pending.schedule(then.0);
+    }
+
+    /// Makes progress in the game until a player needs to make a decision or
+    /// the current turn ends
+    ///
+    /// Returns the same pending decision until it has been answered with
+    /// [`Game::respond`].
+    pub fn step(&mut self) -> Result<Step> {
+        if self.player_count() < 2 {
+            return Err(Error::NotEnoughPlayers);
+        }
+
+        let player_index = self.current_turn;
+        if self.pending.is_idle() {
+            if self.is_game_over() {
+                return Ok(Step::GameOver(self.result()));
+            }
+
+            self.started = true;
+            self.pending.schedule(Task::Turn { player_index });
+        }
+
+        if let Some(decision) = self.advance()? {
+            return Ok(Step::Decision(decision));
+        }
+
+        self.next_turn();
+
+        if self.is_game_over() {
+            let result = self.result();
+            self.emit(GameEvent::GameEnded {
+                result: result.clone(),
+            });
+            return Ok(Step::GameOver(result));
+        }
+
+        Ok(Step::TurnEnded { player_index })
+    }
+
+    /// Answers the pending decision with the given id
+    pub fn respond(&mut self, decision_id: u64, answer: Answer) -> Result {
+        let decision = self.pending_decision().ok_or(Error::NoPendingDecision)?;
+
+        if decision.id != decision_id {
+            return Err(Error::UnknownDecision { id: decision_id });
+        }
+
+        decision.validate(&answer)?;
+        let reply = decision.reply(answer);
+        self.answer(reply)
+    }
+
+    /// The decision waiting to be answered, if any
+    pub fn pending_decision(&self) -> Option<&Decision> {
+        self.pending.decision.as_ref()
+    }
+
+    /// Runs everything the engine has yet to do, asking `callbacks` for any
+    /// decisions along the way
+    pub fn resolve(&mut self, callbacks: &dyn Callbacks) -> Result {
+        let mut invalid_answers = 0;
+        while let Some(decision) = self.advance()? {
+            let reply = self.callback_reply(callbacks, &decision);
+            let answered = self.answer(reply);
+            if let Err(e) = count_invalid_answers(answered, &mut invalid_answers) {
+                self.pending.clear();
+                return Err(e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Schedules a task and runs it to completion with `callbacks`. Fails
+    /// with [`Error::DecisionPending`] if the engine is in the middle of
+    /// something else
+    pub(crate) fn run(&mut self, task: Task, callbacks: &dyn Callbacks) -> Result {
+        self.check_idle()?;
+        self.pending.schedule(task);
+        self.resolve(callbacks)
+    }
+
+    pub(crate) fn check_idle(&self) -> Result {
+        if self.pending.is_idle() {
+            Ok(())
+        } else {
+            Err(Error::DecisionPending)
+        }
+    }
+
+    /// Runs tasks until one needs a decision, which is presented and
+    /// returned, or until there is nothing left to do
+    fn advance(&mut self) -> Result<Option<Decision>> {
+        loop {
+            self.pending.flush();
+            if let Some(decision) = &self.pending.decision {
+                return Ok(Some(decision.clone()));
+            }
+
+            match self.pending.tasks.pop() {
+                None => return Ok(None),
+                Some(Task::Ask { ask, then }) => {
+                    let decision = self.present(&ask);
+                    self.pending.tasks.push(Task::Ask { ask, then });
+                    self.pending.decision = Some(decision);
+                }
+                Some(task) => {
+                    if let Err(e) = self.run_task(task, Reply::Nothing) {
+                        self.pending.clear();
+                        return Err(e);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Carries on from the presented decision with the given reply. If the
+    /// reply turns out to be invalid, nothing changes and the decision stays
+    /// presented
+    fn answer(&mut self, reply: Reply) -> Result {
+        let Some(Task::Ask { ask, then }) = self.pending.tasks.pop() else {
+            return Err(Error::NoPendingDecision);
+        };
+
+        match self.run_task((*then).clone(), reply) {
+            Ok(()) => {
+                self.pending.decision = None;
+                self.decision_count += 1;
+                Ok(())
+            }
+            Err(Error::InvalidAnswer) => {
+                self.pending.staged.clear();
+                self.pending.tasks.push(Task::Ask { ask, then });
+                Err(Error::InvalidAnswer)
+            }
+            Err(e) => {
+                self.pending.clear();
+                Err(e)
+            }
+        }
+    }
+
+    /// Turns an ask into the decision presented to players, using the state
+    /// of the game at this point
+    fn present(&self, ask: &Ask) -> Decision {
+        let player_index = ask.player_index;
+        let options = match ask.kind {
+            DecisionKind::Hand | DecisionKind::Treasure => {
+                card_options(&self.players[player_index].hand)
+            }
+            DecisionKind::Discard => card_options(&self.players[player_index].discard),
+            DecisionKind::Trash => card_options(&self.trash),
+            DecisionKind::Supply => card_options(&supply_options(&self.supply)),
+            // Player options are listed in player order, so an option's
+            // index is also the player's index
+            DecisionKind::Players => (0..self.player_count())
+                .map(DecisionOption::Player)
+                .collect(),
+            _ => ask.options.clone(),
+        };
+
+        let available = options.len();
+        let (min, max) = match ask.count {
+            ChoiceCountOptions::Exact { count } => (count.min(available), count.min(available)),
+            ChoiceCountOptions::UpTo { max } => (0, max.min(available)),
+            ChoiceCountOptions::Any => (0, available),
+        };
+
+        let controller = self.controller(player_index);
+        Decision {
+            id: self.decision_count,
+            player_index: controller.unwrap_or(player_index),
+            on_behalf_of: controller.map(|_| player_index),
+            kind: ask.kind,
+            prompt: ask.prompt.clone(),
+            options,
+            min,
+            max,
+        }
+    }
+
+    /// Asks `callbacks` for the answer to the presented decision
+    fn callback_reply(&self, callbacks: &dyn Callbacks, decision: &Decision) -> Reply {
+        let Some(Task::Ask { ask, .. }) = self.pending.tasks.last() else {
+            return Reply::Nothing;
+        };
+
+        let player_index = decision.player_index;
+        // Exact counts are capped by what there is to choose from
+        let count = &match ask.count {
+            ChoiceCountOptions::Exact { .. } => decision.count(),
+            count => count,
+        };
+        let prompt = ask.prompt.as_str();
+        match ask.kind {
+            DecisionKind::Supply => {
+                Reply::Card(callbacks.choose_card_from_supply(player_index, &self.supply))
+            }
+            DecisionKind::Hand => {
+                Reply::Chosen(callbacks.choose_cards_from_hand(player_index, count, prompt))
+            }
+            DecisionKind::Discard => {
+                Reply::Chosen(callbacks.choose_cards_from_discard(player_index, count, prompt))
+            }
+            DecisionKind::Trash => {
+                Reply::Chosen(callbacks.choose_cards_from_trash(player_index, count, prompt))
+            }
+            DecisionKind::Selection => Reply::Chosen(callbacks.choose_cards_from_selection(
+                player_index,
+                count,
+                &ask.cards(),
+                prompt,
+            )),
+            DecisionKind::Options => {
+                Reply::Chosen(callbacks.choose_options(player_index, count, &ask.texts(), prompt))
+            }
+            DecisionKind::YesOrNo => Reply::YesOrNo(callbacks.yes_or_no(player_index, prompt)),
+            DecisionKind::Players => {
+                Reply::Chosen(callbacks.choose_players(player_index, count, prompt))
+            }
+            DecisionKind::Treasure => Reply::Treasure(callbacks.choose_treasure(player_index)),
+            DecisionKind::Tokens { kind } => {
+                let available = ask.options.len().saturating_sub(1);
+                Reply::Tokens(callbacks.choose_tokens_to_spend(player_index, kind, available))
+            }
+        }
+    }
+
+    /// Take a turn, asking for input through `callbacks`
+    pub async fn turn_async(
+        &mut self,
+        player_index: usize,
+        callbacks: &dyn AsyncCallbacks,
+    ) -> Result {
+        self.run_async(Task::Turn { player_index }, callbacks).await
+    }
+
+    /// Action phase, asking for input through `callbacks`
+    pub async fn action_phase_async(
+        &mut self,
+        player_index: usize,
+        callbacks: &dyn AsyncCallbacks,
+    ) -> Result {
+        self.run_async(Task::ActionPhase { player_index }, callbacks)
+            .await
+    }
+
+    /// Buy phase, asking for input through `callbacks`
+    pub async fn buy_phase_async(
+        &mut self,
+        player_index: usize,
+        callbacks: &dyn AsyncCallbacks,
+    ) -> Result {
+        self.run_async(Task::BuyPhase { player_index }, callbacks)
+            .await
+    }
+
+    /// Play turns until the game is over, asking for input through
+    /// `callbacks`
+    ///
+    /// Progress is kept in the game as with [`Game::step`], so if the future
+    /// is dropped the game can be picked up again from the turn in progress.
+    pub async fn play_to_completion_async(
+        &mut self,
+        callbacks: &dyn AsyncCallbacks,
+    ) -> Result<GameResult> {
+        let mut invalid_answers = 0;
+        loop {
+            match self.step()? {
+                Step::Decision(decision) => {
+                    // Invalid answers leave the decision pending, so it is
+                    // asked again
+                    let answered = match ask(callbacks, &decision, self).await {
+                        Some(answer) => self.respond(decision.id, answer),
+                        None => Err(Error::InvalidAnswer),
+                    };
+                    count_invalid_answers(answered, &mut invalid_answers)?;
+                }
+                Step::TurnEnded { .. } => {}
+                Step::GameOver(result) => return Ok(result),
+            }
+        }
+    }
+
+    /// Runs a task to completion on a copy of the game, awaiting `callbacks`
+    /// for each decision, and replaces the game with the copy once it is done
+    async fn run_async(&mut self, task: Task, callbacks: &dyn AsyncCallbacks) -> Result {
+        self.check_idle()?;
+
+        let mut game = self.clone();
+        game.pending.schedule(task);
+
+        let mut invalid_answers = 0;
+        while let Some(decision) = game.advance()? {
+            // Invalid answers leave the decision pending, so it is asked again
+            let answered = match ask(callbacks, &decision, &game).await {
+                Some(answer) => game.respond(decision.id, answer),
+                None => Err(Error::InvalidAnswer),
+            };
+            count_invalid_answers(answered, &mut invalid_answers)?;
+        }
+
+        *self = game;
+        Ok(())
+    }
+}
+
+/// Keeps count of invalid answers given in a row, failing with
+/// [`Error::TooManyInvalidAnswers`] once there have been too many
+fn count_invalid_answers(answered: Result, invalid_answers: &mut usize) -> Result {
+    match answered {
+        Ok(()) => *invalid_answers = 0,
+        Err(Error::InvalidAnswer) => {
+            *invalid_answers += 1;
+            if *invalid_answers >= MAX_INVALID_ANSWERS {
+                return Err(Error::TooManyInvalidAnswers);
+            }
+        }
+        Err(e) => return Err(e),
+    }
+
+    Ok(())
+}
+
+fn card_options<'a>(cards: impl IntoIterator<Item = &'a Box<dyn Card>>) -> Vec<DecisionOption> {
+    cards
+        .into_iter()
+        .cloned()
+        .map(DecisionOption::Card)
+        .collect()
+}
+
+fn supply_options(supply: &Supply) -> CardList {
+    let mut cards: CardList = supply
+        .as_ref()
+        .values()
+        .map(|entry| entry.card.clone())
+        .collect();
+    cards.sort_unstable();
+    cards
+}
+
+/// Asks `callbacks` for the answer to a decision. Returns `None` if the
+/// answer doesn't match any of the decision's options
+async fn ask(callbacks: &dyn AsyncCallbacks, decision: &Decision, game: &Game) -> Option<Answer> {
+    let player_index = decision.player_index;
+    let count = decision.count();
+    let prompt = decision.prompt.as_str();
+
+    let indices = match decision.kind {
+        DecisionKind::Supply => {
+            let card = callbacks
+                .choose_card_from_supply(player_index, &game.supply)
+                .await;
+            match card {
+                Some(card) => vec![decision.options.iter().position(
+                    |option| matches!(option, DecisionOption::Card(c) if c.name() == card.name()),
+                )?],
+                None => Vec::new(),
+            }
+        }
+        DecisionKind::Hand => {
+            callbacks
+                .choose_cards_from_hand(player_index, &count, prompt)
+                .await
+        }
+        DecisionKind::Discard => {
+            callbacks
+                .choose_cards_from_discard(player_index, &count, prompt)
+                .await
+        }
+        DecisionKind::Trash => {
+            callbacks
+                .choose_cards_from_trash(player_index, &count, prompt)
+                .await
+        }
+        DecisionKind::Selection => {
+            let cards: CardList = decision
+                .options
+                .iter()
+                .filter_map(|option| match option {
+                    DecisionOption::Card(card) => Some(card.clone()),
+                    _ => None,
+                })
+                .collect();
+            callbacks
+                .choose_cards_from_selection(player_index, &count, &cards, prompt)
+                .await
+        }
+        DecisionKind::Options => {
+            let options: Vec<&str> = decision
+                .options
+                .iter()
+                .filter_map(|option| match option {
+                    DecisionOption::Text(text) => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect();
+            callbacks
+                .choose_options(player_index, &count, &options, prompt)
+                .await
+        }
+        DecisionKind::YesOrNo => {
+            return Some(Answer::YesOrNo(
+                callbacks.yes_or_no(player_index, prompt).await,
+            ));
+        }
+        DecisionKind::Players => callbacks.choose_players(player_index, &count, prompt).await,
+        DecisionKind::Treasure => match callbacks.choose_treasure(player_index).await {
+            TreasureChoice::Play { hand_index } => vec![hand_index],
+            TreasureChoice::PlayAll => return Some(Answer::PlayAllTreasures),
+            TreasureChoice::Stop => Vec::new(),
+        },
+        DecisionKind::Tokens { kind } => {
+            let available = decision.options.len().saturating_sub(1);
+            vec![
+                callbacks
+                    .choose_tokens_to_spend(player_index, kind, available)
+                    .await,
+            ]
+        }
+    };
+
+    Some(Answer::Choose(indices))
+}
+
+/// Async version of [`Card::effects_on_play`]
+#[async_trait]
+pub trait AsyncCard {
+    /// Apply the card's on-play effects, asking for input through
+    /// `callbacks`
+    async fn effects_on_play_async(
+        &self,
+        game: &mut Game,
+        player_index: usize,
+        callbacks: &dyn AsyncCallbacks,
+    ) -> Result;
+}
+
+#[async_trait]
+impl AsyncCard for dyn Card {
+    async fn effects_on_play_async(
+        &self,
+        game: &mut Game,
+        player_index: usize,
+        callbacks: &dyn AsyncCallbacks,
+    ) -> Result {
+        let task = Task::Hook {
+            hook: Hook::Play,
+            card: dyn_clone::clone_box(self),
+            player_index,
+        };
+        game.run_async(task, callbacks).await
+    }
+}
@@ -1,6 +1,6 @@
 //! Dominion error types
 
-use crate::types::{Card, CardType};
+use crate::types::{Card, CardType, TokenKind, Zone};
 
 use serde::{Deserialize, Serialize};
 use thiserror::Error as ThisError;
@@ -10,16 +10,54 @@ pub type Result<T = ()> = std::result::Result<T, Error>;
 #[non_exhaustive]
 #[derive(Clone, Debug, ThisError, Serialize, Deserialize)]
 pub enum Error {
+    #[error("There is no {name} in {zone:?}!")]
+    CardNotFound { name: String, zone: Zone },
     #[error("Card doesn't have expected type! Expected: {expected:?}")]
     CardTypeMisMatch { expected: CardType },
+    #[error("A decision is pending, answer it before doing anything else!")]
+    DecisionPending,
+    #[error("There are no cards left to reveal!")]
+    EmptyDeck,
     #[error("Pile is empty: {card:?}")]
     EmptyPile { card: Box<dyn Card> },
+    #[error("The game is already over!")]
+    GameOver,
     #[error("Not enough resources to buy that card!")]
     InsufficientFunds,
+    #[error("That answer isn't valid for the pending decision!")]
+    InvalidAnswer,
+    #[error("There is no card at index {index} in that discard pile!")]
+    InvalidDiscardIndex { index: usize },
+    #[error("There is no card at index {index} in that hand!")]
+    InvalidHandIndex { index: usize },
+    #[error("There is no player {player_index}!")]
+    InvalidPlayer { player_index: usize },
+    #[error("There is no card at index {index} in the trash!")]
+    InvalidTrashIndex { index: usize },
+    #[error("There is no card at index {index} in {zone:?}!")]
+    InvalidZoneIndex { zone: Zone, index: usize },
+    #[error("No actions left to play that card!")]
+    NoActionsLeft,
+    #[error("No buys left!")]
+    NoBuysLeft,
+    #[error("There is no decision waiting for an answer!")]
+    NoPendingDecision,
     #[error("Not enough players to start!")]
     NotEnoughPlayers,
+    #[error("Not enough {kind:?} tokens!")]
+    NotEnoughTokens { kind: TokenKind },
     #[error("Not that player's turn!")]
     OutOfTurn,
+    #[error("Debt has to be paid off before buying cards!")]
+    OutstandingDebt,
+    #[error("Too many invalid answers in a row!")]
+    TooManyInvalidAnswers,
+    #[error("{name} is not in the supply!")]
+    UnknownCard { name: String },
+    #[error("Decision {id} is not the pending decision!")]
+    UnknownDecision { id: u64 },
+    #[error("{kind:?} tokens can't be spent!")]
+    UnspendableTokens { kind: TokenKind },
     #[error("Can't perform that action during this phase!")]
     WrongPhase,
 }
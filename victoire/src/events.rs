@@ -0,0 +1,123 @@
+//! Events reported by the engine as a game progresses
+
+use std::{
+    fmt::{self, Debug, Formatter},
+    sync::Arc,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::{Card, CardList, GameResult, Phase, TokenKind, Zone};
+
+/// Something that happened during a game
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum GameEvent {
+    /// A player started their turn. `turn` counts the player's regular
+    /// turns, so it isn't increased by [extra turns](crate::types::ExtraTurn).
+    /// `controller` is the player making the decisions if it isn't the
+    /// player themselves, e.g. for Possession
+    TurnStarted {
+        player_index: usize,
+        turn: usize,
+        extra: bool,
+        controller: Option<usize>,
+    },
+    /// A player moved on to a new phase of their turn
+    PhaseChanged { player_index: usize, phase: Phase },
+    /// A card was played, either directly or through another card's effects
+    CardPlayed {
+        player_index: usize,
+        card: Box<dyn Card>,
+    },
+    /// A card moved from one zone to another. `card` is only given if
+    /// either zone is public, so e.g. the cards a player draws aren't
+    /// revealed. `to_player` owns the zone it moved to, which is
+    /// `player_index` unless it was passed to someone else, e.g. by
+    /// Masquerade
+    CardMoved {
+        player_index: usize,
+        from: Zone,
+        to: Zone,
+        to_player: usize,
+        card: Option<Box<dyn Card>>,
+    },
+    /// A card was gained
+    CardGained {
+        player_index: usize,
+        card: Box<dyn Card>,
+    },
+    /// A card was bought. Followed by a [`GameEvent::CardGained`] for the
+    /// same card
+    CardBought {
+        player_index: usize,
+        card: Box<dyn Card>,
+    },
+    /// A card was moved to the trash
+    CardTrashed {
+        player_index: usize,
+        card: Box<dyn Card>,
+    },
+    /// A player drew cards. Only the number of cards is reported, since the
+    /// cards themselves are hidden from other players
+    CardsDrawn { player_index: usize, count: usize },
+    /// A player revealed cards from their deck
+    CardsRevealed {
+        player_index: usize,
+        cards: CardList,
+    },
+    /// A player spent some of their tokens, or paid off some of their debt
+    TokensSpent {
+        player_index: usize,
+        kind: TokenKind,
+        count: usize,
+    },
+    /// A player shuffled their discard pile to form a new deck
+    Shuffled { player_index: usize },
+    /// The game ended
+    GameEnded { result: GameResult },
+}
+
+/// Trait for anything that wants to be told about [events](GameEvent) as
+/// they happen, e.g. UIs, loggers or statistics collectors
+pub trait Observer: Send + Sync {
+    /// Called once for every event, in the order the events occur
+    fn notify(&self, event: &GameEvent);
+}
+
+/// The observers registered with a game
+///
+/// Observers belong to whoever is running the game rather than to the game
+/// itself, so they are not serialized.
+#[derive(Clone, Default)]
+pub struct Observers(Vec<Arc<dyn Observer>>);
+
+impl Observers {
+    /// Register a new observer
+    pub fn add(&mut self, observer: Arc<dyn Observer>) {
+        self.0.push(observer);
+    }
+
+    /// Send an event to every registered observer
+    pub fn notify(&self, event: &GameEvent) {
+        for observer in &self.0 {
+            observer.notify(event);
+        }
+    }
+
+    /// Returns the number of registered observers
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if no observers are registered
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Debug for Observers {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Observers({})", self.0.len())
+    }
+}
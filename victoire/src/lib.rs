@@ -3,9 +3,12 @@
 #[warn(missing_docs)]
 pub mod callbacks;
 pub mod cards;
+pub mod commands;
+pub mod decisions;
 pub mod error;
+pub mod events;
 pub mod prelude;
 pub mod types;
 pub mod utils;
 
-pub use callbacks::Callbacks;
+pub use callbacks::{AsyncCallbacks, Callbacks};
@@ -1,2 +1,2 @@
 pub use crate::types::{Card, CardDeck, CardList, CardType, Game, PartialGame, Player, PlayerList};
-pub use crate::Callbacks;
+pub use crate::{AsyncCallbacks, Callbacks};
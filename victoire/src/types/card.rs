@@ -2,6 +2,7 @@ use std::{
     cmp::Ordering,
     fmt::{self, Display, Formatter},
     hash::{Hash, Hasher},
+    ops::Add,
 };
 
 use dyn_clonable::clonable;
@@ -9,14 +10,15 @@ use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    callbacks::Callbacks,
-    types::{Game, Player},
+    decisions::Resume,
+    error::Result,
+    types::{Game, Player, Zone},
 };
 
 #[clonable]
 #[allow(unused_variables)]
 #[typetag::serde(tag = "card")]
-pub trait Card: Clone + Send + Sync {
+pub trait Card: Clone + Send + Sync + Identified {
     /// Name of the card
     fn name(&self) -> &str;
     /// The card's types
@@ -37,24 +39,71 @@ pub trait Card: Clone + Send + Sync {
     }
 
     /// Effects when this card is played
-    fn effects_on_play(&self, game: &mut Game, player_index: usize, callbacks: &dyn Callbacks) {}
+    fn effects_on_play(&self, game: &mut Game, player_index: usize) -> Result {
+        Ok(())
+    }
     /// Effects when this card is gained
-    fn effects_on_gain(&self, game: &mut Game, player_index: usize, callbacks: &dyn Callbacks) {}
+    fn effects_on_gain(&self, game: &mut Game, player_index: usize) -> Result {
+        Ok(())
+    }
     /// Effects when this card is bought
-    fn effects_on_buy(&self, game: &mut Game, player_index: usize, callbacks: &dyn Callbacks) {}
+    fn effects_on_buy(&self, game: &mut Game, player_index: usize) -> Result {
+        Ok(())
+    }
+    /// Effects of a Duration card at the start of the player's turn after
+    /// it was played
+    fn effects_at_start_of_turn(&self, game: &mut Game, player_index: usize) -> Result {
+        Ok(())
+    }
+    /// Effects when this card is trashed
+    fn effects_on_trash(&self, game: &mut Game, player_index: usize) -> Result {
+        Ok(())
+    }
+    /// Effects when this card is discarded, other than during clean-up
+    fn effects_on_discard(&self, game: &mut Game, player_index: usize) -> Result {
+        Ok(())
+    }
+    /// Effects when this card is revealed
+    fn effects_on_reveal(&self, game: &mut Game, player_index: usize) -> Result {
+        Ok(())
+    }
+    /// Effects when this card is in play at the start of clean-up, before it
+    /// is discarded
+    fn effects_at_cleanup(&self, game: &mut Game, player_index: usize) -> Result {
+        Ok(())
+    }
 
     fn attack_target(&self) -> Option<AttackTarget> {
         None
     }
 
-    fn attack_effects(&self, game: &mut Game, player_index: usize, callbacks: &dyn Callbacks) {}
+    fn attack_effects(&self, game: &mut Game, player_index: usize) -> Result {
+        Ok(())
+    }
 
-    fn reaction_effects(&self, game: &mut Game, player_index: usize, callbacks: &dyn Callbacks) {}
+    fn reaction_effects(&self, game: &mut Game, player_index: usize) -> Result {
+        Ok(())
+    }
 
     fn reaction_trigger(&self) -> Option<ReactionTrigger> {
         None
     }
 
+    /// Effects of this card when its owner is about to gain a card, while
+    /// this card is in `zone` (their hand or play area). They happen before
+    /// the gained card lands, so they can change where it goes or what is
+    /// gained instead through [`Game::gaining_mut`], e.g. Watchtower
+    fn react_to_gain(&self, game: &mut Game, player_index: usize, zone: Zone) -> Result {
+        Ok(())
+    }
+
+    /// Carries on with the card's effects once a decision it
+    /// [asked](Game::ask) for has been answered, or once the things it
+    /// scheduled [before](Game::then) the step have happened
+    fn resume(&self, game: &mut Game, player_index: usize, resume: Resume) -> Result {
+        Ok(())
+    }
+
     /// Print out the card's types
     fn print_types(&self) -> String {
         format!("{}", self.types().iter().format(", "))
@@ -71,6 +120,10 @@ pub trait Card: Clone + Send + Sync {
     fn is_reaction(&self) -> bool {
         self.types().contains(&CardType::Reaction)
     }
+    /// Check if this card is a Duration card
+    fn is_duration(&self) -> bool {
+        self.types().contains(&CardType::Duration)
+    }
     /// Check if this card is a Treasure
     fn is_treasure(&self) -> bool {
         self.types().contains(&CardType::Treasure)
@@ -123,6 +176,41 @@ impl PartialOrd for dyn Card {
     }
 }
 
+/// A card that is about to be gained, which
+/// [`react_to_gain`](Card::react_to_gain) effects can change
+#[non_exhaustive]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Gain {
+    /// The card being gained
+    pub card: Box<dyn Card>,
+    /// Where it is being gained from, usually the supply
+    pub from: Zone,
+    /// Where it will land
+    pub to: Zone,
+}
+
+/// Tells apart the copies of a card in a game, e.g. to know which of two
+/// Coppers moved. Every card is given one when it enters the game, from a
+/// starting deck or the supply, and keeps it wherever it goes
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct CardId(pub u64);
+
+impl Display for CardId {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+/// Access to the [`CardId`] a card carries. Every card declared with
+/// `declare_card!` gets this for free, so card effects can call
+/// `self.id()` to find the exact copy being played
+pub trait Identified {
+    /// The id of this copy of the card, if it has entered a game
+    fn id(&self) -> Option<CardId>;
+    /// Gives this copy of the card its id
+    fn set_id(&mut self, id: CardId);
+}
+
 #[non_exhaustive]
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub enum CardType {
@@ -132,6 +220,7 @@ pub enum CardType {
     Action,
     Attack,
     Reaction,
+    Duration,
 }
 
 impl Display for CardType {
@@ -140,15 +229,84 @@ impl Display for CardType {
     }
 }
 
+/// What a card costs, which can include Potions and Debt as well as coins
+///
+/// Costs are compared the way the rules compare them: one cost is more than
+/// another if none of its parts are lower and at least one is higher, so
+/// "costs up to" and "costs more than" are `<=` and `>`. Some costs can't be
+/// compared, e.g. $3 and $2 plus a Potion, in which case neither is more
+/// than the other.
 #[non_exhaustive]
-#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct Cost {
     pub coins: usize,
+    #[serde(default)]
+    pub potions: usize,
+    #[serde(default)]
+    pub debt: usize,
 }
 
 impl Cost {
     pub fn new(coins: usize) -> Cost {
-        Cost { coins }
+        Cost {
+            coins,
+            ..Cost::default()
+        }
+    }
+
+    /// The same cost with this many Potions added
+    pub fn with_potions(self, potions: usize) -> Cost {
+        Cost { potions, ..self }
+    }
+
+    /// The same cost with this much Debt added
+    pub fn with_debt(self, debt: usize) -> Cost {
+        Cost { debt, ..self }
+    }
+}
+
+impl PartialOrd for Cost {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let parts = [
+            self.coins.cmp(&other.coins),
+            self.potions.cmp(&other.potions),
+            self.debt.cmp(&other.debt),
+        ];
+
+        if parts.iter().all(|part| part.is_eq()) {
+            Some(Ordering::Equal)
+        } else if parts.iter().all(|part| part.is_le()) {
+            Some(Ordering::Less)
+        } else if parts.iter().all(|part| part.is_ge()) {
+            Some(Ordering::Greater)
+        } else {
+            None
+        }
+    }
+}
+
+impl Add for Cost {
+    type Output = Cost;
+
+    fn add(self, other: Cost) -> Cost {
+        Cost {
+            coins: self.coins + other.coins,
+            potions: self.potions + other.potions,
+            debt: self.debt + other.debt,
+        }
+    }
+}
+
+impl Display for Cost {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "${}", self.coins)?;
+        if self.potions > 0 {
+            write!(f, " {}P", self.potions)?;
+        }
+        if self.debt > 0 {
+            write!(f, " {}D", self.debt)?;
+        }
+        Ok(())
     }
 }
 
@@ -156,11 +314,21 @@ impl Cost {
 #[derive(Copy, Clone, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct Value {
     pub coins: usize,
+    #[serde(default)]
+    pub potions: usize,
 }
 
 impl Value {
     pub fn new(coins: usize) -> Value {
-        Value { coins }
+        Value {
+            coins,
+            ..Value::default()
+        }
+    }
+
+    /// The same value with this many Potions added
+    pub fn with_potions(self, potions: usize) -> Value {
+        Value { potions, ..self }
     }
 }
 
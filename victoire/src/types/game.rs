@@ -1,20 +1,191 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, VecDeque},
+    sync::Arc,
+};
+
+use itertools::Itertools;
+use rand::{RngCore, SeedableRng};
 use serde::{Deserialize, Serialize};
 
 use victoire_macros::card_vec;
 
 use crate::{
-    callbacks::{Callbacks, ChoiceCountOptions},
+    callbacks::{Callbacks, ChoiceCountOptions, TreasureChoice},
     cards::{base::*, dominion::*},
+    decisions::{Ask, Hook, Pending, Reply, Resume, Task, Then},
     error::{Error, Result},
+    events::{GameEvent, Observer, Observers},
     types::{
-        card::{AttackTarget, ReactionTrigger},
-        Card, CardDeck, CardList, CardType, Phase, Player, PlayerList, Supply,
+        card::{AttackTarget, Cost, Gain, ReactionTrigger},
+        player::take_indexes,
+        player::Resources,
+        Card, CardDeck, CardId, CardList, CardType, Destination, Lifetime, Mat, Modifier,
+        ModifierKind, Phase, Player, PlayerList, Position, Selector, Supply, TokenKind, Tokens,
+        Visibility, Zone,
     },
+    utils::GameRng,
 };
 
+/// Everything a single player is allowed to know about a game
+///
+/// Contains the player's own hand, but never the order of anyone's deck or
+/// the contents of other players' hands.
+#[non_exhaustive]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PartialGame {
+    /// The index of the player this view belongs to
+    pub player_index: usize,
+    /// The index of the player whose turn it is
+    pub current_turn: usize,
+    /// The phase of the player whose turn it is
+    pub phase: Phase,
+    /// The player's own hand
+    pub hand: CardList,
+    /// The player's own resources for this turn
+    pub resources: Resources,
+    /// The cards on all of the player's own mats, including the ones only
+    /// they can see
+    pub mats: BTreeMap<Mat, CardList>,
+    /// What everyone (including this player) can see about each player, in
+    /// player order
+    pub players: Vec<PartialPlayer>,
+    pub supply: Supply,
+    pub trash: CardList,
+    /// The modifiers in effect, which everyone knows about
+    pub modifiers: Vec<Modifier>,
+}
+
+/// The publicly visible information about a player
 #[non_exhaustive]
 #[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct PartialGame {}
+pub struct PartialPlayer {
+    pub player_index: usize,
+    pub hand_size: usize,
+    pub deck_size: usize,
+    pub discard_size: usize,
+    /// The top card of the discard pile, if there is one
+    pub discard_top: Option<Box<dyn Card>>,
+    pub in_play: CardList,
+    /// Duration cards still in play from an earlier turn
+    pub durations: CardList,
+    /// The cards on the player's [public](Visibility::Public) mats
+    pub mats: BTreeMap<Mat, CardList>,
+    /// The number of cards on each of the player's mats, including the ones
+    /// only they can see
+    pub mat_sizes: BTreeMap<Mat, usize>,
+    /// Tokens are public, so everyone can see how many each player has
+    pub tokens: Tokens,
+    pub turns_taken: usize,
+}
+
+impl PartialPlayer {
+    fn new(player_index: usize, player: &Player) -> PartialPlayer {
+        PartialPlayer {
+            player_index,
+            hand_size: player.hand.len(),
+            deck_size: player.deck.len(),
+            discard_size: player.discard.len(),
+            discard_top: player.discard.back().cloned(),
+            in_play: player.in_play.iter().cloned().collect(),
+            durations: player.durations.iter().cloned().collect(),
+            mats: player
+                .mats
+                .iter()
+                .filter(|(mat, _)| mat.visibility() == Visibility::Public)
+                .map(|(&mat, cards)| (mat, cards.iter().cloned().collect()))
+                .collect(),
+            mat_sizes: player
+                .mats
+                .iter()
+                .map(|(&mat, cards)| (mat, cards.len()))
+                .collect(),
+            tokens: player.tokens.clone(),
+            turns_taken: player.turns_taken,
+        }
+    }
+}
+
+/// Everything a player may do at the current point in the game, as returned
+/// by [`Game::legal_actions`]
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct LegalActions {
+    /// Indices of the action cards in the player's hand that can be played
+    pub actions: Vec<usize>,
+    /// Indices of the treasure cards in the player's hand that can be played
+    pub treasures: Vec<usize>,
+    /// The cards in the supply the player can buy, sorted by name
+    pub buys: CardList,
+    /// How many Villagers the player can spend
+    pub villagers: usize,
+    /// How many Coffers the player can spend
+    pub coffers: usize,
+    /// Whether the player can end their current phase
+    pub end_phase: bool,
+}
+
+impl LegalActions {
+    /// Returns true if the player can't do anything at all, e.g. because it
+    /// isn't their turn
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+            && self.treasures.is_empty()
+            && self.buys.is_empty()
+            && self.villagers == 0
+            && self.coffers == 0
+            && !self.end_phase
+    }
+}
+
+/// The final standings of a finished game
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameResult {
+    /// Each player's score, in player order
+    pub scores: Vec<PlayerScore>,
+    /// The indices of the winning players. Contains more than one index if
+    /// the game ended in a tie
+    pub winners: Vec<usize>,
+}
+
+/// A single player's score at the end of the game
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayerScore {
+    pub player_index: usize,
+    pub victory_points: isize,
+    pub turns_taken: usize,
+}
+
+/// A turn taken outside the normal turn order, e.g. from Outpost
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtraTurn {
+    /// The player whose turn it is
+    pub player_index: usize,
+    /// The player who makes the decisions during the turn and gains the
+    /// cards, if it isn't the player themselves, e.g. for Possession
+    pub controller: Option<usize>,
+}
+
+impl ExtraTurn {
+    /// An extra turn the player takes for themselves
+    pub fn new(player_index: usize) -> Self {
+        ExtraTurn {
+            player_index,
+            controller: None,
+        }
+    }
+
+    /// An extra turn for one player that another player controls
+    pub fn controlled(player_index: usize, controller: usize) -> Self {
+        ExtraTurn {
+            player_index,
+            controller: Some(controller),
+        }
+    }
+}
 
 /// The data for a game of Dominion.
 
@@ -26,19 +197,42 @@ pub struct Game {
     pub supply: Supply,
     pub trash: CardDeck,
     pub extras: Supply,
+    /// The seed the game was created with. Two games created with the same
+    /// seed play out identically given the same decisions
+    pub seed: u64,
+    pub rng: GameRng,
+    /// Players who are unaffected by the attack currently being resolved
+    pub unaffected: Vec<usize>,
+    /// Everything the engine has yet to do, e.g. the rest of a turn that is
+    /// waiting on a decision
+    #[serde(default)]
+    pub pending: Pending,
+    /// The number of decisions answered so far
+    pub decision_count: u64,
+    /// The [id](CardId) the next card to enter the game will get
+    #[serde(default)]
+    pub next_card_id: u64,
+    /// Rule changes that last for a while, e.g. from Bridge
+    #[serde(default)]
+    pub modifiers: Vec<Modifier>,
+    /// Extra turns waiting to be taken once the current turn ends, in the
+    /// order they will be taken
+    #[serde(default)]
+    pub extra_turns: VecDeque<ExtraTurn>,
+    /// The extra turn in progress, if the current turn is one
+    #[serde(default)]
+    pub extra_turn: Option<ExtraTurn>,
+    /// While extra turns are being taken, the player whose regular turn came
+    /// before them. The normal turn order carries on from this player
+    #[serde(default)]
+    pub last_regular_turn: Option<usize>,
+    #[serde(skip)]
+    pub observers: Observers,
 }
 
 impl Default for Game {
     fn default() -> Self {
-        let mut game = Game::new();
-        for i in 0..2 {
-            let player = Player::new_with_default_deck(i);
-            game.add_player(player);
-        }
-
-        game.generate_supply(Game::default_supply_list()).unwrap();
-
-        game
+        Game::default_with_seed(rand::random())
     }
 }
 
@@ -70,6 +264,11 @@ impl Game {
         supply.insert(Province, province_count);
         supply.insert(BasicCurse, curse_count);
 
+        // Potions are only needed when something costs one
+        if cards.iter().any(|card| card.cost().potions > 0) {
+            supply.insert(Potion, 16);
+        }
+
         for card in cards {
             // If card is victory card, count matches other victory cards
             // Otherwise use 10 copies
@@ -87,14 +286,35 @@ impl Game {
         Ok(())
     }
 
+    /// Generates the supply piles like [`Game::generate_supply`], adding
+    /// Platinum and Colony piles as used with Prosperity
+    pub fn generate_supply_with_colonies(&mut self, cards: CardList) -> Result {
+        self.generate_supply(cards)?;
+
+        let colony_count = self
+            .supply
+            .get(Province.name())
+            .map_or(0, |entry| entry.count);
+        self.supply.insert(Platinum, 12);
+        self.supply.insert(Colony, colony_count.min(12));
+
+        Ok(())
+    }
+
     /// Create an empty game
     pub fn new() -> Game {
+        Game::with_seed(rand::random())
+    }
+
+    /// Create an empty game whose randomness is entirely determined by `seed`
+    pub fn with_seed(seed: u64) -> Game {
         let started = false;
         let current_turn = 0;
         let players = PlayerList::new();
         let supply = Supply::default();
         let trash = CardDeck::new();
         let extras = Supply::default();
+        let rng = GameRng::seed_from_u64(seed);
         Game {
             started,
             current_turn,
@@ -102,376 +322,1947 @@ impl Game {
             supply,
             trash,
             extras,
+            seed,
+            rng,
+            unaffected: Vec::new(),
+            pending: Pending::default(),
+            decision_count: 0,
+            next_card_id: 0,
+            modifiers: Vec::new(),
+            extra_turns: VecDeque::new(),
+            extra_turn: None,
+            last_regular_turn: None,
+            observers: Observers::default(),
         }
     }
 
-    /// Generate a [`PartialGame`]
-    pub fn partial_game(&self, player_number: usize) -> PartialGame {
-        PartialGame {}
+    /// Create a two player game using the default starting decks and supply,
+    /// with all randomness determined by `seed`
+    pub fn default_with_seed(seed: u64) -> Game {
+        let mut game = Game::with_seed(seed);
+        for _ in 0..2 {
+            game.add_player_with_deck(Player::default_deck());
+        }
+
+        game.generate_supply(Game::default_supply_list()).unwrap();
+
+        game
     }
 
-    /// Returns the number of players in the game
-    pub fn player_count(&self) -> usize {
-        self.players.len()
+    /// Creates a generator for a new player, derived from the game's own
+    /// generator so that the player's shuffles are reproducible
+    pub fn player_rng(&mut self) -> GameRng {
+        GameRng::seed_from_u64(self.rng.next_u64())
     }
 
-    /// Add a player to the game
-    pub fn add_player(&mut self, player: Player) {
-        self.players.push(player);
+    /// Register an observer to be notified of every [event](GameEvent) in
+    /// this game
+    pub fn add_observer(&mut self, observer: Arc<dyn Observer>) {
+        self.observers.add(observer);
     }
 
-    /// Get reference to a player given index
-    pub fn get_player(&self, index: usize) -> Option<&Player> {
-        self.players.get(index)
+    /// Report an event to all registered observers
+    pub fn emit(&self, event: GameEvent) {
+        self.observers.notify(&event);
     }
 
-    /// Get mutable reference to a player given index
-    pub fn get_player_mut(&mut self, index: usize) -> Option<&mut Player> {
-        self.players.get_mut(index)
+    /// Generate a [`PartialGame`] containing only what the given player is
+    /// allowed to know
+    pub fn partial_game(&self, player_index: usize) -> Result<PartialGame> {
+        let player = self.player(player_index)?;
+        let phase = self
+            .players
+            .get(self.current_turn)
+            .map_or(Phase::OutOfTurn, |current| current.phase);
+
+        Ok(PartialGame {
+            player_index,
+            current_turn: self.current_turn,
+            phase,
+            hand: player.hand.iter().cloned().collect(),
+            resources: player.resources.clone(),
+            mats: player
+                .mats
+                .iter()
+                .map(|(&mat, cards)| (mat, cards.iter().cloned().collect()))
+                .collect(),
+            players: self
+                .players
+                .iter()
+                .enumerate()
+                .map(|(index, player)| PartialPlayer::new(index, player))
+                .collect(),
+            supply: self.supply.clone(),
+            trash: self.trash.iter().cloned().collect(),
+            modifiers: self.modifiers.clone(),
+        })
     }
 
-    /// Gain a copy of a card to the discard pile
-    pub fn gain(
-        &mut self,
-        player_index: usize,
-        card: Box<dyn Card>,
-        callbacks: &dyn Callbacks,
-    ) -> Result {
-        if self.supply.get(card.name()).unwrap().count == 0 {
-            return Err(Error::EmptyPile { card });
+    /// Lists everything the given player may do right now
+    ///
+    /// Players can only act on their own turn, during their action or buy
+    /// phase. If their turn hasn't started yet, what they may do is what
+    /// they could do at the start of their action phase, as for
+    /// [`Game::apply`]. Nothing is legal once the game is over.
+    pub fn legal_actions(&self, player_index: usize) -> LegalActions {
+        let mut legal = LegalActions::default();
+
+        let Some(player) = self.players.get(player_index) else {
+            return legal;
+        };
+
+        if player_index != self.current_turn || self.is_game_over() {
+            return legal;
         }
 
-        self.supply.get_mut(card.name()).unwrap().count -= 1;
-        card.effects_on_gain(self, player_index, callbacks);
+        let hand_indices = |matches: fn(&dyn Card) -> bool| {
+            player
+                .hand
+                .iter()
+                .positions(|card| matches(card.as_ref()))
+                .collect()
+        };
 
-        let player = &mut self.players[player_index];
-        player.discard.push_back(card);
-        Ok(())
-    }
+        // A turn that is starting gets its first action
+        let starting = player.phase == Phase::OutOfTurn;
+        let phase = if starting {
+            Phase::ActionPhase
+        } else {
+            player.phase
+        };
 
-    /// Gain a copy of a card to hand
-    pub fn gain_to_hand(
-        &mut self,
-        player_index: usize,
-        card: Box<dyn Card>,
-        callbacks: &dyn Callbacks,
-    ) -> Result {
-        if self.supply.get(card.name()).unwrap().count == 0 {
-            return Err(Error::EmptyPile { card });
+        match phase {
+            Phase::ActionPhase => {
+                if starting || player.resources.actions > 0 {
+                    legal.actions = hand_indices(|card| card.is_action());
+                }
+                legal.villagers = player.tokens.villagers;
+                legal.end_phase = true;
+            }
+            Phase::BuyPhase => {
+                if !player.state.has_bought {
+                    legal.treasures = hand_indices(|card| card.is_treasure());
+                }
+                legal.coffers = player.tokens.coffers;
+
+                // Any debt is paid off before buying, and nothing can be
+                // bought if it can't all be paid off
+                let resources = &player.resources;
+                if resources.buys > 0 && player.tokens.debt <= resources.coins_remaining {
+                    let coins = resources.coins_remaining - player.tokens.debt;
+                    legal.buys = self
+                        .supply
+                        .as_ref()
+                        .values()
+                        .filter(|entry| {
+                            let cost = self.effective_cost(&*entry.card, player_index);
+                            entry.count > 0
+                                && cost.coins <= coins
+                                && cost.potions <= resources.potions
+                        })
+                        .map(|entry| entry.card.clone())
+                        .sorted()
+                        .collect();
+                }
+                legal.end_phase = true;
+            }
+            _ => {}
         }
 
-        self.supply.get_mut(card.name()).unwrap().count -= 1;
-        card.effects_on_gain(self, player_index, callbacks);
+        legal
+    }
+
+    /// Checks whether the game has ended, i.e. if the Province pile is empty
+    /// or if three supply piles (four with five or more players) are empty
+    pub fn is_game_over(&self) -> bool {
+        // Colonies only end the game if they are in the supply
+        let provinces_gone = [Province.name(), Colony.name()]
+            .into_iter()
+            .any(|name| self.supply.get(name).is_some_and(|entry| entry.count == 0));
 
-        let player = &mut self.players[player_index];
-        player.hand.push_back(card);
-        Ok(())
+        let pile_limit = if self.player_count() >= 5 { 4 } else { 3 };
+
+        provinces_gone || self.supply.empty_piles() >= pile_limit
     }
 
-    /// Gain a copy of a card to the top of the deck
-    pub fn gain_to_deck_top(
-        &mut self,
-        player_index: usize,
-        card: Box<dyn Card>,
-        callbacks: &dyn Callbacks,
-    ) -> Result {
-        if self.supply.get(card.name()).unwrap().count == 0 {
-            return Err(Error::EmptyPile { card });
+    /// Computes each player's score and the winners of the game
+    ///
+    /// If players are tied on points, whoever took fewer turns wins. Players
+    /// who are still tied share the victory.
+    pub fn result(&self) -> GameResult {
+        let scores = self
+            .players
+            .iter()
+            .enumerate()
+            .map(|(player_index, player)| PlayerScore {
+                player_index,
+                victory_points: player.victory_points(),
+                turns_taken: player.turns_taken,
+            })
+            .collect_vec();
+
+        let best = scores
+            .iter()
+            .map(|score| (score.victory_points, Reverse(score.turns_taken)))
+            .max();
+
+        let winners = scores
+            .iter()
+            .filter(|score| Some((score.victory_points, Reverse(score.turns_taken))) == best)
+            .map(|score| score.player_index)
+            .collect();
+
+        GameResult { scores, winners }
+    }
+
+    /// Pass the turn to the next player, or to whoever has the next
+    /// [extra turn](ExtraTurn) waiting
+    pub fn next_turn(&mut self) {
+        if self.players.is_empty() {
+            return;
         }
 
-        self.supply.get_mut(card.name()).unwrap().count -= 1;
-        card.effects_on_gain(self, player_index, callbacks);
+        if let Some(turn) = self.extra_turns.pop_front() {
+            self.last_regular_turn.get_or_insert(self.current_turn);
+            self.current_turn = turn.player_index;
+            self.extra_turn = Some(turn);
+            return;
+        }
 
-        let player = &mut self.players[player_index];
-        player.deck.push_front(card);
-        Ok(())
+        let last = self.last_regular_turn.take().unwrap_or(self.current_turn);
+        self.extra_turn = None;
+        self.current_turn = (last + 1) % self.player_count();
+    }
+
+    /// Queues an extra turn, to be taken after the current turn and any
+    /// extra turns already waiting
+    pub fn take_extra_turn(&mut self, turn: ExtraTurn) {
+        self.extra_turns.push_back(turn);
+    }
+
+    /// The extra turn the given player is taking, if it is their turn and
+    /// it is an extra one
+    pub fn current_extra_turn(&self, player_index: usize) -> Option<ExtraTurn> {
+        self.extra_turn
+            .filter(|turn| turn.player_index == player_index)
     }
 
-    pub fn remove_from_hand(&mut self, player_index: usize, card_index: usize) -> Box<dyn Card> {
-        let player = self.get_player_mut(player_index).unwrap();
-        player.hand.remove(card_index).unwrap()
+    /// The player controlling the given player's turn, if it is their turn
+    /// and someone else is making their decisions, e.g. with Possession
+    pub fn controller(&self, player_index: usize) -> Option<usize> {
+        self.current_extra_turn(player_index)?.controller
     }
 
-    pub fn reveal(&mut self, player_index: usize, count: usize) -> CardList {
-        let mut cards = CardList::new();
-        for _ in 0..count {
-            cards.push(self.players[player_index].deck.pop_front().unwrap());
+    /// Play turns in order until the game ends, then return the result
+    pub fn play_to_completion(&mut self, callbacks: &dyn Callbacks) -> Result<GameResult> {
+        if self.player_count() < 2 {
+            return Err(Error::NotEnoughPlayers);
+        }
+
+        self.started = true;
+
+        while !self.is_game_over() {
+            self.turn(self.current_turn, callbacks)?;
+            self.next_turn();
         }
 
-        cards
+        let result = self.result();
+        self.emit(GameEvent::GameEnded {
+            result: result.clone(),
+        });
+
+        Ok(result)
     }
 
-    /// Plays an action [card](Card) from the hand of the player corresponding
-    /// to the given index
+    /// Returns the number of players in the game
+    pub fn player_count(&self) -> usize {
+        self.players.len()
+    }
+
+    /// Add a player to the game
     ///
-    /// This is the function to call when a player plays a card directly
-    pub fn play_action_from_hand(
-        &mut self,
-        player_index: usize,
-        card_index: usize,
-        callbacks: &dyn Callbacks,
-    ) -> Result {
-        // Remove card from hand
-        let player = &mut self.players[player_index];
-        let card = player.hand.get(card_index).unwrap();
-        if card.is_action() {
-            let card = player.hand.remove(card_index).unwrap();
-            player.in_play.push_back(card.clone());
+    /// Every card the player starts with is given an [id](CardId).
+    pub fn add_player(&mut self, mut player: Player) {
+        let zones = [
+            &mut player.hand,
+            &mut player.deck,
+            &mut player.discard,
+            &mut player.in_play,
+            &mut player.durations,
+        ];
+        for cards in zones.into_iter().chain(player.mats.values_mut()) {
+            for card in cards.iter_mut() {
+                *card = self.identify(card.clone());
+            }
+        }
 
-            player.resources.actions -= 1;
-            self.action_effects(player_index, &*card, callbacks);
+        self.players.push(player);
+    }
 
-            Ok(())
-        } else {
-            Err(Error::CardTypeMisMatch {
-                expected: CardType::Action,
-            })
+    /// Gives a card entering the game its [id](CardId), unless it already
+    /// has one
+    fn identify(&mut self, mut card: Box<dyn Card>) -> Box<dyn Card> {
+        if card.id().is_none() {
+            card.set_id(CardId(self.next_card_id));
+            self.next_card_id += 1;
         }
+
+        card
     }
 
-    /// Gives the player the effects of an action card as if they had played it
-    ///
-    /// Does not subtract actions from the player's total. Should only be called
-    /// in the effects() function of other cards (e.g. Throne Room)
-    pub fn action_effects(
-        &mut self,
-        player_index: usize,
-        card: &dyn Card,
-        callbacks: &dyn Callbacks,
-    ) {
-        // Effects on the player who played the card
-        card.effects_on_play(self, player_index, callbacks);
+    /// Add a new player with the given starting deck, shuffled using the
+    /// game's generator
+    pub fn add_player_with_deck(&mut self, cards: CardList) {
+        let rng = self.player_rng();
+        let player = Player::new_with_rng(self.player_count(), cards, rng);
+        self.add_player(player);
+    }
 
-        // Attack effects, if any
-        if card.is_attack() {
-            let targets = self.get_targets(
-                player_index,
-                card.attack_target()
-                    .expect("Card has Attack type but does not define targets!"),
-                callbacks,
-            );
+    /// Get reference to a player given index
+    pub fn get_player(&self, index: usize) -> Option<&Player> {
+        self.players.get(index)
+    }
 
-            self.check_reactions(
-                player_index,
-                ReactionTrigger::OtherPlayerPlaysAttack,
-                callbacks,
-            );
+    /// Get mutable reference to a player given index
+    pub fn get_player_mut(&mut self, index: usize) -> Option<&mut Player> {
+        self.players.get_mut(index)
+    }
 
-            for index in targets {
-                let player = &self.players[player_index];
+    /// Get reference to a player given index, or
+    /// `Err(InvalidPlayer)` if there is no such player
+    pub fn player(&self, player_index: usize) -> Result<&Player> {
+        self.players
+            .get(player_index)
+            .ok_or(Error::InvalidPlayer { player_index })
+    }
 
-                if !(player.state.immune) {
-                    card.attack_effects(self, index, callbacks);
+    /// Get mutable reference to a player given index, or
+    /// `Err(InvalidPlayer)` if there is no such player
+    pub fn player_mut(&mut self, player_index: usize) -> Result<&mut Player> {
+        self.players
+            .get_mut(player_index)
+            .ok_or(Error::InvalidPlayer { player_index })
+    }
+
+    /// What a card costs the given player right now, after any
+    /// [modifiers](Modifier) that make cards cheaper (e.g. Bridge). Only the
+    /// coins part of a cost is ever reduced
+    pub fn effective_cost(&self, card: &dyn Card, player_index: usize) -> Cost {
+        let reduction: usize = self
+            .modifiers
+            .iter()
+            .filter(|modifier| self.is_active(modifier))
+            .filter_map(|modifier| match modifier.kind {
+                ModifierKind::CostReduction { coins, card_type } => card_type
+                    .is_none_or(|card_type| card.types().contains(&card_type))
+                    .then_some(coins),
+                ModifierKind::OwnCostReduction(coins) => {
+                    (modifier.player_index == player_index).then_some(coins)
                 }
+                _ => None,
+            })
+            .sum();
 
-                let mut player = &mut self.players[player_index];
-                player.state.immune = false;
-            }
+        let cost = card.cost();
+        Cost {
+            coins: cost.coins.saturating_sub(reduction),
+            ..cost
         }
     }
 
-    /// Convert the attack target type into a vec of player indices
-    pub fn get_targets(
-        &mut self,
-        player_index: usize,
-        target_type: AttackTarget,
-        callbacks: &dyn Callbacks,
-    ) -> Vec<usize> {
-        match target_type {
-            AttackTarget::EveryoneElse => {
-                let mut indices = vec![];
-                for i in 0..self.player_count() {
-                    indices.push(i);
-                }
-                indices.remove(player_index);
-
-                indices
-            }
+    /// Adds a modifier, which lasts until its [`Lifetime`] is up
+    pub fn add_modifier(&mut self, modifier: Modifier) {
+        self.modifiers.push(modifier);
+    }
 
-            AttackTarget::PlayerToLeft => {
-                vec![player_index + 1]
-            }
+    /// The modifiers in effect for the given player
+    pub fn modifiers_for(&self, player_index: usize) -> impl Iterator<Item = &Modifier> {
+        self.modifiers.iter().filter(move |modifier| {
+            modifier.player_index == player_index && self.is_active(modifier)
+        })
+    }
 
-            AttackTarget::PlayerOfChoice => callbacks.choose_players(
-                player_index,
-                &ChoiceCountOptions::Exact { count: 1 },
-                "Choose a player to target",
-            ),
+    /// Whether a modifier is still in effect. Only modifiers that last while
+    /// a card is in play can run out between the times they are cleared
+    fn is_active(&self, modifier: &Modifier) -> bool {
+        match modifier.lifetime {
+            Lifetime::WhileInPlay(id) => self
+                .players
+                .iter()
+                .flat_map(|player| player.in_play.iter().chain(player.durations.iter()))
+                .any(|card| card.id() == Some(id)),
+            _ => true,
         }
     }
 
-    pub fn check_reactions(
+    /// Moves cards for the given player from one zone to another, and
+    /// returns the cards that were moved
+    ///
+    /// This is how cards get from one place to another, so each moved card
+    /// is reported to observers with a [`GameEvent::CardMoved`], and the
+    /// effects of the move happen here: moving a card from the supply or the
+    /// trash into one of the player's zones gains it. Cards are put on top
+    /// of the zone they are moved to, so several cards moved onto the deck
+    /// at once end up in reverse order. Nothing is moved if the selector
+    /// doesn't match the cards that are there.
+    ///
+    /// Gained cards are returned straight away, but only land once the
+    /// player's cards have [reacted](Card::react_to_gain) to them. That, and
+    /// any effects of the cards being moved, e.g. on-gain effects, happen
+    /// once the game is [resolved](Game::resolve), or straight after the card
+    /// effect that moved them.
+    pub fn move_card(
         &mut self,
         player_index: usize,
-        reaction_trigger: ReactionTrigger,
-        callbacks: &dyn Callbacks,
-    ) {
-        // TODO: prompt player and perform reaction
+        from: Zone,
+        to: Zone,
+        selector: Selector,
+    ) -> Result<CardList> {
+        let to = Destination::new(player_index, to);
+        self.move_card_to(player_index, from, to, selector)
     }
 
-    /// Plays a single treasure card from the player's hand. Returns
-    /// `Err(CardTypeMisMatch { expected: Treasure })` if the card is not a
-    /// treasure card
-    pub fn play_treasure(
+    /// Like [`Game::move_card`], but the cards can go to another player's
+    /// zone, or to the bottom of a zone instead of the top. Gained cards
+    /// always go on top
+    pub fn move_card_to(
         &mut self,
         player_index: usize,
-        card_index: usize,
-        callbacks: &dyn Callbacks,
-    ) -> Result {
-        let player = &mut self.players[player_index];
-
-        // Remove card from hand
-        let c = player.hand.get(card_index).unwrap();
-        if !c.is_treasure() {
-            return Err(Error::CardTypeMisMatch {
-                expected: CardType::Treasure,
-            });
+        from: Zone,
+        to: Destination,
+        selector: Selector,
+    ) -> Result<CardList> {
+        // Cards coming into a player's zones from outside them are gained,
+        // by whoever controls the player's turn if it isn't them
+        if !from.is_player_zone() && to.zone.is_player_zone() {
+            let player_index = self.controller(to.player_index).unwrap_or(to.player_index);
+            return self.gain_cards(player_index, from, to.zone, selector);
         }
 
-        let card = player.hand.remove(card_index).unwrap();
-        card.effects_on_play(self, player_index, callbacks);
-        let player = &mut self.players[player_index];
+        // The player's own cards trashed during a turn someone else controls
+        // are set aside for them instead, though they still count as trashed
+        let to = if from.is_player_zone()
+            && to.zone == Zone::Trash
+            && self.controller(player_index).is_some()
+        {
+            Destination::new(player_index, Mat::Possession.into())
+        } else {
+            to
+        };
+        let trashed = to.zone == Zone::Trash || to.zone == Mat::Possession.into();
 
-        player.resources.coins += card.treasure_value().coins;
+        let cards = self.transfer_to(player_index, from, to, selector)?;
 
-        player.in_play.push_back(card.clone());
+        for card in &cards {
+            if trashed {
+                self.trashed(player_index, card.clone());
+            }
+            if to.zone == Zone::Discard {
+                self.hook(Hook::Discard, card.clone(), to.player_index);
+            }
+        }
 
-        Ok(())
+        Ok(cards)
     }
 
-    /// Play all treasure cards from the players hand
-    pub fn play_all_treasures(&mut self, player_index: usize, callbacks: &dyn Callbacks) {
-        let range = self.players[player_index].hand.len();
-
-        for i in 0..range {
-            let player = &mut self.players[player_index];
-            let card = player.hand.get(i).unwrap();
-            if card.is_treasure() {
-                // We know the card is a treasure card, so unwrap
-                self.play_treasure(player_index, i, callbacks).unwrap();
-            }
-        }
+    /// Reports a card that has just been trashed and schedules its on-trash
+    /// effects
+    fn trashed(&mut self, player_index: usize, card: Box<dyn Card>) {
+        self.emit(GameEvent::CardTrashed {
+            player_index,
+            card: card.clone(),
+        });
+        self.hook(Hook::Trash, card, player_index);
     }
 
-    /// Buy a card
-    pub fn buy_card(
+    /// Schedules gains of the cards `selector` picks out of a zone, one at a
+    /// time, and returns the cards that are about to be gained
+    fn gain_cards(
         &mut self,
         player_index: usize,
-        card: Box<dyn Card>,
-        callbacks: &dyn Callbacks,
-    ) -> Result {
-        if player_index != self.current_turn {
-            return Err(Error::OutOfTurn);
-        }
-
-        let player = &mut self.players[player_index];
-
-        if player.phase != Phase::BuyPhase {
-            return Err(Error::WrongPhase);
+        from: Zone,
+        to: Zone,
+        selector: Selector,
+    ) -> Result<CardList> {
+        let cards = self.peek(player_index, from, &selector)?;
+        for card in &cards {
+            let gain = Gain {
+                card: card.clone(),
+                from,
+                to,
+            };
+            self.pending.schedule(Task::Gain { player_index, gain });
         }
 
-        if player.resources.coins_remaining < card.cost().coins {
-            return Err(Error::InsufficientFunds);
-        }
+        Ok(cards)
+    }
 
-        if self.supply.get(card.name()).unwrap().count == 0 {
-            return Err(Error::EmptyPile { card });
+    /// Starts a gain. Before the card lands, the cards in the player's hand
+    /// and play area can [react](Card::react_to_gain) to it. Each card is
+    /// only asked once, however many copies of it there are
+    fn start_gain(&mut self, player_index: usize, gain: Gain) -> Result {
+        let player = self.player(player_index)?;
+        let cards = player
+            .hand
+            .iter()
+            .map(|card| (Zone::Hand, card.clone()))
+            .chain(
+                player
+                    .in_play
+                    .iter()
+                    .map(|card| (Zone::InPlay, card.clone())),
+            )
+            .unique_by(|(zone, card)| (*zone, card.name().to_owned()))
+            .collect_vec();
+
+        self.pending.start_gain(gain);
+        for (zone, card) in cards {
+            self.pending.schedule(Task::ReactToGain {
+                card,
+                player_index,
+                zone,
+            });
         }
+        self.pending.schedule(Task::LandGain { player_index });
 
-        card.effects_on_buy(self, player_index, callbacks);
-        card.effects_on_gain(self, player_index, callbacks);
-
-        self.gain(player_index, card.clone(), callbacks)?;
+        Ok(())
+    }
 
-        let player = &mut self.players[player_index];
-        player.resources.temp_coins -= card.cost().coins;
+    /// Moves the gain in progress to where it is going, and schedules the
+    /// gained card's on-gain effects. Nothing is gained if the card has gone
+    /// in the meantime, e.g. because an earlier gain emptied its pile
+    fn land_gain(&mut self, player_index: usize) -> Result {
+        let Some(Gain { card, from, to }) = self.pending.finish_gain() else {
+            return Ok(());
+        };
 
-        player.resources.buys -= 1;
+        let cards = match self.transfer(player_index, from, to, Selector::Card(card)) {
+            Err(Error::EmptyPile { .. } | Error::CardNotFound { .. }) => return Ok(()),
+            result => result?,
+        };
 
-        // Hovel check
-        if card.is_victory() {
-            self.check_reactions(player_index, ReactionTrigger::BuyAVictoryCard, callbacks);
+        for card in cards {
+            self.record_gain(player_index, card.clone());
+            self.hook(Hook::Gain, card.clone(), player_index);
+            if to == Zone::Trash {
+                self.trashed(player_index, card);
+            }
         }
 
         Ok(())
     }
 
-    /// Take a turn
-    pub fn turn(&mut self, player_index: usize, callbacks: &dyn Callbacks) {
-        let player = &mut self.players[player_index];
+    /// The card about to be gained, while the cards in the player's hand
+    /// and play area [react](Card::react_to_gain) to it
+    pub fn gaining(&self) -> Option<&Gain> {
+        self.pending.gaining()
+    }
 
-        player.reset_state();
+    /// Like [`Game::gaining`], for reactions that change where the card goes
+    /// or what is gained instead
+    pub fn gaining_mut(&mut self) -> Option<&mut Gain> {
+        self.pending.gaining_mut()
+    }
 
-        player.phase = Phase::ActionPhase;
-        self.action_phase(player_index, callbacks);
+    /// The cards `selector` picks out of a zone, without moving them
+    fn peek(&mut self, player_index: usize, zone: Zone, selector: &Selector) -> Result<CardList> {
+        if zone == Zone::Supply {
+            let Selector::Card(card) = selector else {
+                return Ok(CardList::new());
+            };
 
-        let player = &mut self.players[player_index];
-        player.phase = Phase::BuyPhase;
-        self.buy_phase(player_index, callbacks);
+            return match self.supply.get(card.name()) {
+                None => Err(Error::UnknownCard {
+                    name: card.name().to_owned(),
+                }),
+                Some(entry) if entry.count == 0 => Err(Error::EmptyPile {
+                    card: entry.card.clone(),
+                }),
+                Some(entry) => Ok(vec![entry.card.clone()]),
+            };
+        }
 
-        let player = &mut self.players[player_index];
-        player.phase = Phase::CleanupPhase;
-        player.cleanup();
+        let cards = self.cards_mut(player_index, zone)?;
+        select(cards, zone, selector)?
+            .into_iter()
+            .unique()
+            .map(|i| cards.get(i).cloned().ok_or(zone.invalid_index(i)))
+            .collect()
+    }
 
-        player.phase = Phase::OutOfTurn;
+    /// Moves cards between the player's zones and reports the moves,
+    /// without any of the other effects of [`Game::move_card`]
+    fn transfer(
+        &mut self,
+        player_index: usize,
+        from: Zone,
+        to: Zone,
+        selector: Selector,
+    ) -> Result<CardList> {
+        self.transfer_to(
+            player_index,
+            from,
+            Destination::new(player_index, to),
+            selector,
+        )
     }
 
-    /// Action phase
-    pub fn action_phase(&mut self, player_index: usize, callbacks: &dyn Callbacks) {
-        let player = &mut self.players[player_index];
+    /// Like [`Game::transfer`], but to any [destination](Destination)
+    fn transfer_to(
+        &mut self,
+        player_index: usize,
+        from: Zone,
+        destination: Destination,
+        selector: Selector,
+    ) -> Result<CardList> {
+        self.player(player_index)?;
+        self.player(destination.player_index)?;
+        let to = destination.zone;
+
+        let cards = if from == Zone::Supply {
+            let Selector::Card(card) = selector else {
+                return Ok(CardList::new());
+            };
+            self.check_destination(to, std::slice::from_ref(&card))?;
+            vec![self.take_from_supply(&*card)?]
+        } else {
+            // The deck is topped up before any cards are taken from it
+            if let (Zone::Deck, Selector::Top(count)) = (from, &selector) {
+                let player = &self.players[player_index];
+                if player.deck.len() < *count && !player.discard.is_empty() {
+                    self.players[player_index].shuffle_discard_into_deck();
+                    self.emit(GameEvent::Shuffled { player_index });
+                }
+            }
+
+            let zone = self.cards_mut(player_index, from)?;
+            let indexes = select(zone, from, &selector)?;
+            let selected = indexes
+                .iter()
+                .filter_map(|&i| zone.get(i).cloned())
+                .collect_vec();
+            self.check_destination(to, &selected)?;
+
+            let zone = self.cards_mut(player_index, from)?;
+            let cards = take_indexes(zone, indexes, |index| from.invalid_index(index));
+
+            // Mats are only kept around while they have cards on them
+            if let Zone::Mat(mat) = from {
+                let player = &mut self.players[player_index];
+                if player.mat(mat).is_empty() {
+                    player.mats.remove(&mat);
+                }
+            }
+            cards?
+        };
+
+        // The top of the deck is its front, but the top of every other zone
+        // is its back
+        let on_front = (to == Zone::Deck) == (destination.position == Position::Top);
+        for card in &cards {
+            match to {
+                Zone::Supply => {
+                    if let Some(entry) = self.supply.get_mut(card.name()) {
+                        entry.count += 1;
+                    }
+                }
+                _ if on_front => self
+                    .cards_mut(destination.player_index, to)?
+                    .push_front(card.clone()),
+                _ => self
+                    .cards_mut(destination.player_index, to)?
+                    .push_back(card.clone()),
+            }
+
+            // Cards moving between hidden zones, like when drawing, are
+            // only reported as a count
+            let card = (from.is_public() || to.is_public()).then(|| card.clone());
+            self.emit(GameEvent::CardMoved {
+                player_index,
+                from,
+                to,
+                to_player: destination.player_index,
+                card,
+            });
+        }
+
+        Ok(cards)
+    }
+
+    /// The cards in one of the given player's zones, or in the trash. Fails
+    /// for the supply, which isn't a list of cards
+    fn cards_mut(&mut self, player_index: usize, zone: Zone) -> Result<&mut CardDeck> {
+        match zone {
+            Zone::Trash => Ok(&mut self.trash),
+            _ => self
+                .player_mut(player_index)?
+                .zone_mut(zone)
+                .ok_or(zone.invalid_index(0)),
+        }
+    }
+
+    /// Checks that the given cards can be moved to a zone, i.e. that cards
+    /// returned to the supply have a pile to go back to
+    fn check_destination(&self, to: Zone, cards: &[Box<dyn Card>]) -> Result {
+        if to != Zone::Supply {
+            return Ok(());
+        }
+
+        match cards
+            .iter()
+            .find(|card| self.supply.get(card.name()).is_none())
+        {
+            Some(card) => Err(Error::UnknownCard {
+                name: card.name().to_owned(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Takes a copy of a card out of its supply pile
+    fn take_from_supply(&mut self, card: &dyn Card) -> Result<Box<dyn Card>> {
+        let Some(entry) = self.supply.get_mut(card.name()) else {
+            return Err(Error::UnknownCard {
+                name: card.name().to_owned(),
+            });
+        };
+
+        if entry.count == 0 {
+            return Err(Error::EmptyPile {
+                card: entry.card.clone(),
+            });
+        }
+
+        entry.count -= 1;
+        let card = entry.card.clone();
+        Ok(self.identify(card))
+    }
+
+    /// Remembers a card gained on the current turn by the player whose turn
+    /// it is, or by whoever controls it (e.g. for Smugglers), and reports the
+    /// gain to observers
+    fn record_gain(&mut self, player_index: usize, card: Box<dyn Card>) {
+        let turn = self.current_turn;
+        if player_index == turn || self.controller(turn) == Some(player_index) {
+            self.players[turn].state.gained.push(card.clone());
+        }
+
+        self.emit(GameEvent::CardGained { player_index, card });
+    }
+
+    /// Gain a copy of a card from the supply to one of the player's zones
+    ///
+    /// Reactions like Watchtower may send the card somewhere else, or gain
+    /// a different card instead.
+    pub fn gain_to(&mut self, player_index: usize, card: Box<dyn Card>, to: Zone) -> Result {
+        self.move_card(player_index, Zone::Supply, to, Selector::Card(card))?;
+        Ok(())
+    }
 
-        if player.resources.actions > 0 {
-            let mut indices = Vec::new();
-            while !indices.is_empty() {
-                indices = callbacks.choose_cards_from_hand(
-                    &ChoiceCountOptions::UpTo { max: 1 },
-                    "Choose an action card to play",
+    /// Gain a copy of a card to the discard pile
+    pub fn gain(&mut self, player_index: usize, card: Box<dyn Card>) -> Result {
+        self.gain_to(player_index, card, Zone::Discard)
+    }
+
+    /// Gain a copy of a card to hand
+    pub fn gain_to_hand(&mut self, player_index: usize, card: Box<dyn Card>) -> Result {
+        self.gain_to(player_index, card, Zone::Hand)
+    }
+
+    /// Gain a copy of a card to the top of the deck
+    pub fn gain_to_deck_top(&mut self, player_index: usize, card: Box<dyn Card>) -> Result {
+        self.gain_to(player_index, card, Zone::Deck)
+    }
+
+    /// Schedules one of a card's effects
+    fn hook(&mut self, hook: Hook, card: Box<dyn Card>, player_index: usize) {
+        self.pending.schedule(Task::Hook {
+            hook,
+            card,
+            player_index,
+        });
+    }
+
+    /// Removes the card at the given index from the player's hand
+    pub fn remove_from_hand(
+        &mut self,
+        player_index: usize,
+        card_index: usize,
+    ) -> Result<Box<dyn Card>> {
+        self.player_mut(player_index)?
+            .hand
+            .remove(card_index)
+            .ok_or(Error::InvalidHandIndex { index: card_index })
+    }
+
+    /// Sets aside up to `count` cards from the top of the player's deck and
+    /// reveals them, shuffling their discard pile into their deck if it runs
+    /// out. Returns `Err(EmptyDeck)` if there was nothing at all to reveal
+    ///
+    /// The revealed cards stay on the player's [set aside](Mat::SetAside) mat
+    /// until they are moved somewhere else.
+    pub fn reveal(&mut self, player_index: usize, count: usize) -> Result<CardList> {
+        let cards = self.take_from_deck(player_index, count)?;
+
+        if cards.is_empty() && count > 0 {
+            return Err(Error::EmptyDeck);
+        }
+
+        self.reveal_cards(player_index, cards.clone())?;
+        Ok(cards)
+    }
+
+    /// Reveals cards wherever they are, e.g. from the player's hand, and
+    /// schedules their on-reveal effects
+    pub fn reveal_cards(&mut self, player_index: usize, cards: CardList) -> Result {
+        self.emit(GameEvent::CardsRevealed {
+            player_index,
+            cards: cards.clone(),
+        });
+
+        for card in cards {
+            self.hook(Hook::Reveal, card, player_index);
+        }
+
+        Ok(())
+    }
+
+    /// Sets aside up to `count` cards from the top of the player's deck
+    /// without revealing them, shuffling their discard pile into their deck
+    /// if it runs out
+    ///
+    /// The cards stay on the player's [set aside](Mat::SetAside) mat until
+    /// they are moved somewhere else.
+    pub fn take_from_deck(&mut self, player_index: usize, count: usize) -> Result<CardList> {
+        self.transfer(
+            player_index,
+            Zone::Deck,
+            Mat::SetAside.into(),
+            Selector::Top(count),
+        )
+    }
+
+    /// Draws cards for the given player, shuffling their discard pile into
+    /// their deck if it runs out
+    pub fn draw_cards(&mut self, player_index: usize, count: usize) -> Result {
+        // The -1 Card token is used up by the next draw, even one of 0 cards
+        let minus_card = self.modifiers.iter().position(|modifier| {
+            modifier.player_index == player_index && modifier.kind == ModifierKind::MinusCard
+        });
+        let count = match minus_card {
+            Some(index) => {
+                self.modifiers.remove(index);
+                count.saturating_sub(1)
+            }
+            None => count,
+        };
+
+        let drawn = self
+            .transfer(player_index, Zone::Deck, Zone::Hand, Selector::Top(count))?
+            .len();
+
+        if drawn > 0 {
+            self.emit(GameEvent::CardsDrawn {
+                player_index,
+                count: drawn,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Trashes a copy of a card straight from the supply, e.g. for Lurker
+    pub fn trash_from_supply(&mut self, player_index: usize, card: Box<dyn Card>) -> Result {
+        self.move_card(
+            player_index,
+            Zone::Supply,
+            Zone::Trash,
+            Selector::Card(card),
+        )?;
+        Ok(())
+    }
+
+    /// Gains the card at the given index in the trash to the player's discard
+    /// pile
+    pub fn gain_from_trash(&mut self, player_index: usize, trash_index: usize) -> Result {
+        self.move_card(
+            player_index,
+            Zone::Trash,
+            Zone::Discard,
+            Selector::Index(trash_index),
+        )?;
+        Ok(())
+    }
+
+    /// Trashes cards from the player's hand given their indexes. Nothing is
+    /// trashed if any of the indexes are invalid
+    pub fn trash_from_hand(&mut self, player_index: usize, indexes: Vec<usize>) -> Result {
+        self.move_card(
+            player_index,
+            Zone::Hand,
+            Zone::Trash,
+            Selector::Indexes(indexes),
+        )?;
+        Ok(())
+    }
+
+    /// Discards cards from the player's hand given their indexes. Nothing is
+    /// discarded if any of the indexes are invalid
+    pub fn discard_from_hand(&mut self, player_index: usize, indexes: Vec<usize>) -> Result {
+        self.move_card(
+            player_index,
+            Zone::Hand,
+            Zone::Discard,
+            Selector::Indexes(indexes),
+        )?;
+        Ok(())
+    }
+
+    /// Discards cards from the player's hand given their indexes. Nothing is
+    /// discarded if any of the indexes are invalid
+    #[deprecated(note = "use `Game::discard_from_hand` or `Game::move_card`")]
+    pub fn discard_given_indexes(&mut self, player_index: usize, indexes: Vec<usize>) -> Result {
+        self.move_card(
+            player_index,
+            Zone::Hand,
+            Zone::Discard,
+            Selector::Indexes(indexes),
+        )?;
+        Ok(())
+    }
+
+    /// Moves cards from the player's discard pile to their hand given their
+    /// indexes, in the order the indexes are given. Nothing is moved if any
+    /// of the indexes are invalid
+    #[deprecated(note = "use `Game::move_card`")]
+    pub fn move_given_indexes_discard_to_hand(
+        &mut self,
+        player_index: usize,
+        indexes: Vec<usize>,
+    ) -> Result {
+        self.move_card(
+            player_index,
+            Zone::Discard,
+            Zone::Hand,
+            Selector::Indexes(indexes),
+        )?;
+        Ok(())
+    }
+
+    /// Trashes cards from the player's hand given their indexes. Nothing is
+    /// trashed if any of the indexes are invalid
+    #[deprecated(note = "use `Game::trash_from_hand` or `Game::move_card`")]
+    pub fn trash_given_indexes(&mut self, player_index: usize, indexes: Vec<usize>) -> Result {
+        self.move_card(
+            player_index,
+            Zone::Hand,
+            Zone::Trash,
+            Selector::Indexes(indexes),
+        )?;
+        Ok(())
+    }
+
+    /// Move to a new phase of the player's turn
+    pub fn set_phase(&mut self, player_index: usize, phase: Phase) -> Result {
+        self.player_mut(player_index)?.phase = phase;
+        self.emit(GameEvent::PhaseChanged {
+            player_index,
+            phase,
+        });
+        Ok(())
+    }
+
+    /// Plays an action [card](Card) from the hand of the player corresponding
+    /// to the given index, asking `callbacks` for any decisions its effects
+    /// need
+    ///
+    /// This is the function to call when a player plays a card directly
+    pub fn play_action_from_hand(
+        &mut self,
+        player_index: usize,
+        card_index: usize,
+        callbacks: &dyn Callbacks,
+    ) -> Result {
+        self.check_idle()?;
+        self.play_action(player_index, card_index)?;
+        self.resolve(callbacks)
+    }
+
+    /// Moves an action card from the player's hand into play and schedules
+    /// its effects
+    pub(crate) fn play_action(&mut self, player_index: usize, card_index: usize) -> Result {
+        // Remove card from hand
+        let player = self.player_mut(player_index)?;
+        let card = player
+            .hand
+            .get(card_index)
+            .ok_or(Error::InvalidHandIndex { index: card_index })?;
+        if card.is_action() {
+            if player.resources.actions == 0 {
+                return Err(Error::NoActionsLeft);
+            }
+
+            player.resources.actions -= 1;
+            let card = self
+                .move_card(
+                    player_index,
+                    Zone::Hand,
+                    Zone::InPlay,
+                    Selector::Index(card_index),
+                )?
+                .remove(0);
+
+            self.action_effects(player_index, &*card)
+        } else {
+            Err(Error::CardTypeMisMatch {
+                expected: CardType::Action,
+            })
+        }
+    }
+
+    /// Gives the player the effects of an action card as if they had played
+    /// it, once everything scheduled before it has happened
+    ///
+    /// Does not subtract actions from the player's total. Should only be called
+    /// in the effects() function of other cards (e.g. Throne Room)
+    pub fn action_effects(&mut self, player_index: usize, card: &(dyn Card + 'static)) -> Result {
+        self.player(player_index)?;
+        self.pending.schedule(Task::Play {
+            player_index,
+            card: dyn_clone::clone_box(card),
+        });
+        Ok(())
+    }
+
+    /// Plays an action card, followed by its attack if it has one
+    fn play(&mut self, player_index: usize, card: Box<dyn Card>) -> Result {
+        self.player(player_index)?;
+        self.emit(GameEvent::CardPlayed {
+            player_index,
+            card: card.clone(),
+        });
+
+        let player = self.player_mut(player_index)?;
+        player.state.actions_played += 1;
+        player.state.skip_attack = false;
+        if card.is_duration() {
+            player.state.durations_played.push(card.clone());
+        }
+
+        // Effects on the player who played the card
+        card.effects_on_play(self, player_index)?;
+
+        self.pending.schedule(Task::Attack { player_index, card });
+        Ok(())
+    }
+
+    /// Makes the attack of a card that has just been played, once its own
+    /// effects are done. Cards with the Attack type but no targets don't
+    /// attack anyone, and some cards can call off their attack while being
+    /// played
+    fn attack(&mut self, player_index: usize, card: Box<dyn Card>) -> Result {
+        let skip_attack = std::mem::take(&mut self.player_mut(player_index)?.state.skip_attack);
+        let Some(target_type) = card
+            .attack_target()
+            .filter(|_| card.is_attack() && !skip_attack)
+        else {
+            return Ok(());
+        };
+
+        match target_type {
+            AttackTarget::PlayerOfChoice => {
+                let ask = Ask::players(
+                    player_index,
+                    ChoiceCountOptions::Exact { count: 1 },
+                    "Choose a player to target",
                 );
+                self.ask(ask, Then(Task::ChooseTarget { player_index, card }));
+                Ok(())
+            }
+            target_type => {
+                let targets = self.get_targets(player_index, target_type)?;
+                self.strike(card, targets)
+            }
+        }
+    }
+
+    /// Convert the attack target type into a vec of player indices. A player
+    /// of choice is chosen when the attack happens, so there are no targets
+    /// until then
+    pub fn get_targets(
+        &self,
+        player_index: usize,
+        target_type: AttackTarget,
+    ) -> Result<Vec<usize>> {
+        self.player(player_index)?;
+        let player_count = self.player_count();
 
-                let card_index = indices[0];
+        let targets = match target_type {
+            // Other players in turn order, starting from the player's left
+            AttackTarget::EveryoneElse => (1..player_count)
+                .map(|i| (player_index + i) % player_count)
+                .collect(),
 
-                if self
-                    .play_action_from_hand(player_index, card_index, callbacks)
-                    .is_err()
-                {
-                    continue;
-                }
+            AttackTarget::PlayerToLeft => {
+                vec![(player_index + 1) % player_count]
+            }
+
+            AttackTarget::PlayerOfChoice => Vec::new(),
+        };
+
+        Ok(targets)
+    }
+
+    /// Attacks the player chosen by the attacker
+    fn choose_target(&mut self, player_index: usize, card: Box<dyn Card>, reply: &Reply) -> Result {
+        let player_count = self.player_count();
+        let targets = reply
+            .indexes()
+            .iter()
+            .copied()
+            .filter(|&i| i != player_index && i < player_count)
+            .take(1)
+            .collect();
+
+        self.strike(card, targets)
+    }
+
+    /// Every target gets a chance to react before the attack resolves.
+    /// Reactions only last for this attack
+    fn strike(&mut self, card: Box<dyn Card>, targets: Vec<usize>) -> Result {
+        self.unaffected.clear();
+        for &index in &targets {
+            self.check_reactions(index, ReactionTrigger::OtherPlayerPlaysAttack)?;
+        }
+
+        self.pending.schedule(Task::AttackTargets { card, targets });
+        Ok(())
+    }
 
-                if self.players[player_index].resources.actions == 0 {
-                    break;
+    /// Schedules the attack's effects on each target it still affects
+    fn attack_targets(&mut self, card: Box<dyn Card>, targets: Vec<usize>) {
+        for index in targets {
+            if !self.unaffected.contains(&index) && !self.players[index].state.immune_to_attacks {
+                self.hook(Hook::Attack, card.clone(), index);
+            }
+        }
+
+        self.pending.schedule(Task::EndAttack);
+    }
+
+    /// Gives the player the chance to reveal any number of cards from their
+    /// hand that react to `reaction_trigger`, then performs the effects of
+    /// each revealed reaction in the order they were chosen
+    pub fn check_reactions(
+        &mut self,
+        player_index: usize,
+        reaction_trigger: ReactionTrigger,
+    ) -> Result {
+        let reactions: CardList = self
+            .player(player_index)?
+            .hand
+            .iter()
+            .filter(|card| card.reaction_trigger() == Some(reaction_trigger))
+            .cloned()
+            .collect();
+
+        if reactions.is_empty() {
+            return Ok(());
+        }
+
+        let ask = Ask::selection(
+            player_index,
+            ChoiceCountOptions::UpTo {
+                max: reactions.len(),
+            },
+            &reactions,
+            "Choose reactions to reveal",
+        );
+        self.ask(
+            ask,
+            Then(Task::Reactions {
+                player_index,
+                reactions,
+            }),
+        );
+
+        Ok(())
+    }
+
+    /// Reveals the chosen reactions one at a time, each followed by its
+    /// effects. Reactions that weren't offered, or repeats, are an invalid
+    /// answer
+    fn reactions(
+        &mut self,
+        player_index: usize,
+        reactions: &[Box<dyn Card>],
+        reply: &Reply,
+    ) -> Result {
+        let chosen = reply.indexes();
+        if !chosen.iter().all_unique() || chosen.iter().any(|&i| i >= reactions.len()) {
+            return Err(Error::InvalidAnswer);
+        }
+
+        for &i in chosen {
+            self.pending.schedule(Task::React {
+                player_index,
+                card: reactions[i].clone(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Makes the player unaffected by the attack currently being resolved,
+    /// e.g. because they revealed a Moat
+    pub fn block_attack(&mut self, player_index: usize) -> Result {
+        self.player(player_index)?;
+
+        if !self.unaffected.contains(&player_index) {
+            self.unaffected.push(player_index);
+        }
+        Ok(())
+    }
+
+    /// Plays a single treasure card from the player's hand, asking
+    /// `callbacks` for any decisions its effects need. Returns
+    /// `Err(CardTypeMisMatch { expected: Treasure })` if the card is not a
+    /// treasure card, or `Err(WrongPhase)` if the player has already bought
+    /// a card this turn
+    pub fn play_treasure(
+        &mut self,
+        player_index: usize,
+        card_index: usize,
+        callbacks: &dyn Callbacks,
+    ) -> Result {
+        self.check_idle()?;
+        self.play_treasure_from_hand(player_index, card_index)?;
+        self.resolve(callbacks)
+    }
+
+    /// Plays a single treasure card from the player's hand, scheduling
+    /// anything its effects need to do later
+    pub(crate) fn play_treasure_from_hand(
+        &mut self,
+        player_index: usize,
+        card_index: usize,
+    ) -> Result {
+        let player = self.player_mut(player_index)?;
+        if player.state.has_bought {
+            return Err(Error::WrongPhase);
+        }
+
+        // Remove card from hand
+        let c = player
+            .hand
+            .get(card_index)
+            .ok_or(Error::InvalidHandIndex { index: card_index })?;
+        if !c.is_treasure() {
+            return Err(Error::CardTypeMisMatch {
+                expected: CardType::Treasure,
+            });
+        }
+
+        let card = self
+            .move_card(
+                player_index,
+                Zone::Hand,
+                Zone::InPlay,
+                Selector::Index(card_index),
+            )?
+            .remove(0);
+        self.emit(GameEvent::CardPlayed {
+            player_index,
+            card: card.clone(),
+        });
+        card.effects_on_play(self, player_index)?;
+        let player = &mut self.players[player_index];
+        if card.is_duration() {
+            player.state.durations_played.push(card.clone());
+        }
+
+        let mut value = card.treasure_value().coins;
+        player.resources.potions += card.treasure_value().potions;
+
+        // Bonuses for the first copy of a treasure played this turn, e.g.
+        // from Merchant
+        let copies = player
+            .in_play
+            .iter()
+            .filter(|c| c.name() == card.name())
+            .count();
+        if copies == 1 {
+            for modifier in self.modifiers_for(player_index) {
+                if let ModifierKind::FirstTreasureBonus { treasure, coins } = &modifier.kind {
+                    if treasure == card.name() {
+                        value += coins;
+                    }
                 }
             }
         }
 
-        // Do any end of action phase stuff here
+        self.players[player_index].add_treasure_coins(value);
+
+        Ok(())
     }
 
-    /// Buy phase
-    pub fn buy_phase(&mut self, player_index: usize, callbacks: &dyn Callbacks) {
-        // TODO: allow player to choose which treasures they play?
-        self.play_all_treasures(player_index, callbacks);
+    /// Play all treasure cards from the players hand
+    pub fn play_all_treasures(&mut self, player_index: usize, callbacks: &dyn Callbacks) -> Result {
+        self.check_idle()?;
+        self.play_all(player_index)?;
+        self.resolve(callbacks)
+    }
+
+    fn play_all(&mut self, player_index: usize) -> Result {
+        let player = self.player(player_index)?;
+        if player.state.has_bought {
+            return Err(Error::WrongPhase);
+        }
+
+        let range = player.hand.len();
+
+        // Go backwards so that playing a card doesn't shift the indices of
+        // the cards we haven't looked at yet
+        for i in (0..range).rev() {
+            let is_treasure = self.players[player_index]
+                .hand
+                .get(i)
+                .is_some_and(|card| card.is_treasure());
+            if is_treasure {
+                self.play_treasure_from_hand(player_index, i)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Buy a card, asking `callbacks` for any decisions that follow, e.g.
+    /// from its on-buy effects
+    pub fn buy_card(
+        &mut self,
+        player_index: usize,
+        card: Box<dyn Card>,
+        callbacks: &dyn Callbacks,
+    ) -> Result {
+        self.check_idle()?;
+        self.buy(player_index, card)?;
+        self.resolve(callbacks)
+    }
+
+    /// Buys a card, scheduling its on-buy effects and the gain
+    fn buy(&mut self, player_index: usize, card: Box<dyn Card>) -> Result {
+        if player_index != self.current_turn {
+            return Err(Error::OutOfTurn);
+        }
+
+        let phase = self.player(player_index)?.phase;
+        self.check_buy(player_index, phase, &*card)?;
+
+        // Debt has to be paid off before anything can be bought
+        self.pay_debt(player_index)?;
+        let cost = self.effective_cost(&*card, player_index);
+
+        self.emit(GameEvent::CardBought {
+            player_index,
+            card: card.clone(),
+        });
+
+        self.hook(Hook::Buy, card.clone(), player_index);
+
+        self.gain(player_index, card.clone())?;
 
         let player = &mut self.players[player_index];
-        let player_number = player.player_number;
+        player.resources.coins_remaining -= cost.coins;
+        player.resources.potions -= cost.potions;
 
-        player.resources.coins_remaining = player.resources.coins + player.resources.temp_coins;
+        // Debt is taken on by whoever gained the card
+        let debtor = self.controller(player_index).unwrap_or(player_index);
+        self.players[debtor].tokens.debt += cost.debt;
+
+        let player = &mut self.players[player_index];
+        player.resources.buys -= 1;
+        player.state.has_bought = true;
+        player.state.bought.push(card.clone());
+        player.tokens.victory_points += player.state.vp_per_buy;
+
+        // Hovel check
+        if card.is_victory() {
+            self.check_reactions(player_index, ReactionTrigger::BuyAVictoryCard)?;
+        }
+
+        Ok(())
+    }
+
+    /// Spends some of the player's tokens. Villagers are spent for +1 action
+    /// each during the action phase, and Coffers for +1 coin each during the
+    /// buy phase
+    pub fn spend_tokens(&mut self, player_index: usize, kind: TokenKind, count: usize) -> Result {
+        if player_index != self.current_turn {
+            return Err(Error::OutOfTurn);
+        }
+
+        let phase = self.player(player_index)?.phase;
+        self.check_spend_tokens(player_index, phase, kind, count)?;
+
+        let player = &mut self.players[player_index];
+        *player.tokens.get_mut(kind) -= count;
+        match kind {
+            TokenKind::Villagers => player.add_actions(count),
+            _ => {
+                player.resources.temp_coins += count;
+                player.resources.coins_remaining += count;
+            }
+        }
+
+        self.emit(GameEvent::TokensSpent {
+            player_index,
+            kind,
+            count,
+        });
+
+        Ok(())
+    }
+
+    /// Checks that the player can buy the card, without changing anything.
+    /// `phase` is the phase they are in, or will be in once their turn has
+    /// started
+    pub(crate) fn check_buy(&self, player_index: usize, phase: Phase, card: &dyn Card) -> Result {
+        let player = &self.players[player_index];
+
+        if phase != Phase::BuyPhase {
+            return Err(Error::WrongPhase);
+        }
+
+        if player.resources.buys == 0 {
+            return Err(Error::NoBuysLeft);
+        }
+
+        let Some(entry) = self.supply.get(card.name()) else {
+            return Err(Error::UnknownCard {
+                name: card.name().to_owned(),
+            });
+        };
+
+        if entry.count == 0 {
+            return Err(Error::EmptyPile {
+                card: entry.card.clone(),
+            });
+        }
+
+        // Debt is paid off first, out of the coins the player has left
+        let paid = player.tokens.debt.min(player.resources.coins_remaining);
+        if player.tokens.debt > paid {
+            return Err(Error::OutstandingDebt);
+        }
+
+        // Debt in the cost is taken on once the card is bought, rather than
+        // paid up front
+        let cost = self.effective_cost(card, player_index);
+        if player.resources.coins_remaining - paid < cost.coins
+            || player.resources.potions < cost.potions
+        {
+            return Err(Error::InsufficientFunds);
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the player can spend the tokens, without changing
+    /// anything. `phase` is as for [`Game::check_buy`]
+    pub(crate) fn check_spend_tokens(
+        &self,
+        player_index: usize,
+        phase: Phase,
+        kind: TokenKind,
+        count: usize,
+    ) -> Result {
+        let expected = match kind {
+            TokenKind::Villagers => Phase::ActionPhase,
+            TokenKind::Coffers => Phase::BuyPhase,
+            _ => return Err(Error::UnspendableTokens { kind }),
+        };
+
+        if phase != expected {
+            return Err(Error::WrongPhase);
+        }
+
+        if self.players[player_index].tokens.get(kind) < count {
+            return Err(Error::NotEnoughTokens { kind });
+        }
+
+        Ok(())
+    }
+
+    /// Pays off as much of the player's debt as they can with the coins they
+    /// have left
+    pub fn pay_debt(&mut self, player_index: usize) -> Result {
+        let player = self.player_mut(player_index)?;
+        let count = player.tokens.debt.min(player.resources.coins_remaining);
+        if count == 0 {
+            return Ok(());
+        }
+
+        player.tokens.debt -= count;
+        player.resources.coins_remaining -= count;
+
+        self.emit(GameEvent::TokensSpent {
+            player_index,
+            kind: TokenKind::Debt,
+            count,
+        });
+
+        Ok(())
+    }
+
+    /// Asks the player how many of their tokens of the given kind they want
+    /// to spend, if they have any
+    fn offer_tokens(&mut self, player_index: usize, kind: TokenKind) -> Result {
+        let available = self.player(player_index)?.tokens.get(kind);
+        if available == 0 {
+            return Ok(());
+        }
+
+        let ask = Ask::tokens(player_index, kind, available);
+        self.ask(ask, Then(Task::SpendTokens { player_index, kind }));
+        Ok(())
+    }
+
+    /// Spends the number of tokens the player chose. More tokens than they
+    /// have is an invalid answer
+    fn spend_chosen_tokens(
+        &mut self,
+        player_index: usize,
+        kind: TokenKind,
+        reply: &Reply,
+    ) -> Result {
+        let count = match reply {
+            Reply::Tokens(count) => *count,
+            _ => 0,
+        };
+        if count == 0 {
+            return Ok(());
+        }
 
-        if player.resources.buys > 0 {
-            while let Some(card) = callbacks.choose_card_from_supply(player_number, &self.supply) {
-                // If player chooses a card they cannot buy, loop
-                if self
-                    .buy_card(player_index, card.clone(), callbacks)
-                    .is_err()
+        if count > self.player(player_index)?.tokens.get(kind) {
+            return Err(Error::InvalidAnswer);
+        }
+        self.spend_tokens(player_index, kind, count)
+    }
+
+    /// Take a turn
+    ///
+    /// If another player controls the turn, e.g. with Possession, the
+    /// player's decisions are asked of them instead.
+    pub fn turn(&mut self, player_index: usize, callbacks: &dyn Callbacks) -> Result {
+        self.run(Task::Turn { player_index }, callbacks)
+    }
+
+    /// Resets the player's resources for a new turn, resolves any Duration
+    /// cards from their last turn and moves them into their action phase
+    pub fn start_turn(&mut self, player_index: usize, callbacks: &dyn Callbacks) -> Result {
+        self.run(Task::StartTurn { player_index }, callbacks)
+    }
+
+    fn begin_turn(&mut self, player_index: usize) -> Result {
+        let player = self.player_mut(player_index)?;
+
+        player.reset_state();
+
+        // Extra turns don't count towards breaking ties
+        let extra_turn = self.current_extra_turn(player_index);
+        let player = &mut self.players[player_index];
+        if extra_turn.is_none() {
+            player.turns_taken += 1;
+        }
+
+        let turn = player.turns_taken;
+        self.modifiers.retain(|modifier| {
+            modifier.player_index != player_index || modifier.lifetime != Lifetime::UntilNextTurn
+        });
+        self.emit(GameEvent::TurnStarted {
+            player_index,
+            turn,
+            extra: extra_turn.is_some(),
+            controller: extra_turn.and_then(|turn| turn.controller),
+        });
+
+        self.pending.schedule(Task::Durations { player_index });
+        Ok(())
+    }
+
+    /// Resolves the next Duration card from the player's last turn, or moves
+    /// them into their action phase once there are none left. Durations are
+    /// discarded at this turn's cleanup, once their effects have happened
+    fn next_duration(&mut self, player_index: usize) -> Result {
+        if self.player(player_index)?.durations.is_empty() {
+            return self.set_phase(player_index, Phase::ActionPhase);
+        }
+
+        let card = self
+            .transfer(
+                player_index,
+                Zone::Durations,
+                Zone::InPlay,
+                Selector::Index(0),
+            )?
+            .remove(0);
+        self.hook(Hook::StartOfTurn, card, player_index);
+        self.pending.schedule(Task::Durations { player_index });
+
+        Ok(())
+    }
+
+    /// Runs the player's cleanup phase, after which they are out of turn
+    pub fn end_turn(&mut self, player_index: usize, callbacks: &dyn Callbacks) -> Result {
+        self.run(Task::EndTurn { player_index }, callbacks)
+    }
+
+    /// Cards set aside instead of being trashed go back to their owner, and
+    /// the player is out of turn
+    fn leave_turn(&mut self, player_index: usize) -> Result {
+        if self.controller(player_index).is_some() {
+            self.transfer(
+                player_index,
+                Mat::Possession.into(),
+                Zone::Discard,
+                Selector::All,
+            )?;
+        }
+
+        self.set_phase(player_index, Phase::OutOfTurn)
+    }
+
+    /// Cleanup phase at end of turn - discard hand and cards in play, then
+    /// draw five new cards. Duration cards played this turn stay in play
+    pub fn cleanup(&mut self, player_index: usize, callbacks: &dyn Callbacks) -> Result {
+        self.run(Task::Cleanup { player_index }, callbacks)
+    }
+
+    fn start_cleanup(&mut self, player_index: usize) -> Result {
+        let player = self.player_mut(player_index)?;
+        for card in std::mem::take(&mut player.state.durations_played) {
+            // Durations that have already left play, e.g. by being trashed,
+            // are skipped
+            match self.transfer(
+                player_index,
+                Zone::InPlay,
+                Zone::Durations,
+                Selector::Card(card),
+            ) {
+                Err(Error::CardNotFound { .. }) => {}
+                result => {
+                    result?;
+                }
+            }
+        }
+
+        self.transfer(player_index, Zone::Hand, Zone::Discard, Selector::All)?;
+
+        // Cards may leave play at this point instead of being discarded,
+        // e.g. Treasury
+        let in_play = self.players[player_index].in_play.clone();
+        for card in in_play {
+            self.hook(Hook::Cleanup, card, player_index);
+        }
+        self.pending.schedule(Task::FinishCleanup { player_index });
+
+        Ok(())
+    }
+
+    fn finish_cleanup(&mut self, player_index: usize) -> Result {
+        self.transfer(player_index, Zone::InPlay, Zone::Discard, Selector::All)?;
+
+        // The next hand can be smaller, e.g. after Outpost
+        let hand_size = self
+            .modifiers_for(player_index)
+            .find_map(|modifier| match modifier.kind {
+                ModifierKind::HandSize(size) => Some(size),
+                _ => None,
+            })
+            .unwrap_or(5);
+        let modifiers = std::mem::take(&mut self.modifiers);
+        self.modifiers = modifiers
+            .into_iter()
+            .filter(|modifier| modifier.lifetime != Lifetime::ThisTurn && self.is_active(modifier))
+            .collect();
+
+        self.draw_cards(player_index, hand_size)
+    }
+
+    /// Action phase
+    pub fn action_phase(&mut self, player_index: usize, callbacks: &dyn Callbacks) -> Result {
+        self.run(Task::ActionPhase { player_index }, callbacks)
+    }
+
+    /// Asks the player for an action to play, unless they have run out
+    fn choose_action(&mut self, player_index: usize) -> Result {
+        if self.player(player_index)?.resources.actions == 0 {
+            return Ok(());
+        }
+
+        let ask = Ask::hand(
+            player_index,
+            ChoiceCountOptions::UpTo { max: 1 },
+            "Choose an action card to play",
+        );
+        self.ask(ask, Then(Task::PlayChosenAction { player_index }));
+        Ok(())
+    }
+
+    /// Plays the action the player chose, then carries on with the action
+    /// phase. Choosing nothing ends it
+    fn play_chosen_action(&mut self, player_index: usize, reply: &Reply) -> Result {
+        let Some(card_index) = reply.index() else {
+            return Ok(());
+        };
+
+        // If the player chooses a card they cannot play, ask again. This is
+        // checked up front so that errors from the card's effects aren't
+        // mistaken for a bad choice
+        let player = self.player(player_index)?;
+        if !player
+            .hand
+            .get(card_index)
+            .is_some_and(|card| card.is_action())
+        {
+            return Err(Error::InvalidAnswer);
+        }
+
+        self.play_action(player_index, card_index)?;
+        self.pending.schedule(Task::ActionPhase { player_index });
+        Ok(())
+    }
+
+    /// Lets the player choose treasures to play from their hand one at a
+    /// time, until they stop, play the rest all at once or run out
+    pub fn play_treasures(&mut self, player_index: usize, callbacks: &dyn Callbacks) -> Result {
+        self.run(Task::PlayTreasures { player_index }, callbacks)
+    }
+
+    /// Asks the player for a treasure to play, if they have any
+    fn choose_treasure(&mut self, player_index: usize) -> Result {
+        let has_treasures = self
+            .player(player_index)?
+            .hand
+            .iter()
+            .any(|card| card.is_treasure());
+        if has_treasures {
+            let ask = Ask::treasure(player_index);
+            self.ask(ask, Then(Task::PlayChosenTreasure { player_index }));
+        }
+
+        Ok(())
+    }
+
+    fn play_chosen_treasure(&mut self, player_index: usize, reply: &Reply) -> Result {
+        match reply {
+            Reply::Treasure(TreasureChoice::Play { hand_index }) => {
+                // If the player chooses a card they cannot play, ask again
+                let player = self.player(player_index)?;
+                if !player
+                    .hand
+                    .get(*hand_index)
+                    .is_some_and(|card| card.is_treasure())
                 {
-                    continue;
+                    return Err(Error::InvalidAnswer);
                 }
 
-                if self.players[player_index].resources.buys == 0 {
-                    break;
+                self.play_treasure_from_hand(player_index, *hand_index)?;
+                self.pending.schedule(Task::PlayTreasures { player_index });
+                Ok(())
+            }
+            Reply::Treasure(TreasureChoice::PlayAll) => self.play_all(player_index),
+            _ => Ok(()),
+        }
+    }
+
+    /// Buy phase
+    pub fn buy_phase(&mut self, player_index: usize, callbacks: &dyn Callbacks) -> Result {
+        self.run(Task::BuyPhase { player_index }, callbacks)
+    }
+
+    /// Once the player has played their treasures, offers them their Coffers
+    /// and pays off any debt before they buy anything
+    fn start_buying(&mut self, player_index: usize) -> Result {
+        let player = self.player_mut(player_index)?;
+        player.resources.coins_remaining = player.resources.coins + player.resources.temp_coins;
+
+        self.offer_tokens(player_index, TokenKind::Coffers)?;
+        self.pending.schedule(Task::PayDebt { player_index });
+        self.pending.schedule(Task::ChooseBuy { player_index });
+        Ok(())
+    }
+
+    /// Asks the player for a card to buy, unless they have no buys left
+    fn choose_buy(&mut self, player_index: usize) -> Result {
+        if self.player(player_index)?.resources.buys == 0 {
+            return Ok(());
+        }
+
+        let ask = Ask::supply(player_index, "Choose a card to buy");
+        self.ask(ask, Then(Task::BuyChosen { player_index }));
+        Ok(())
+    }
+
+    /// Buys the card the player chose, then asks for another. Choosing
+    /// nothing ends the buy phase
+    fn buy_chosen(&mut self, player_index: usize, reply: &Reply) -> Result {
+        let Some(card) = reply.card() else {
+            return Ok(());
+        };
+
+        // If player chooses a card they cannot buy, ask again
+        match self.buy(player_index, card) {
+            Err(
+                Error::UnknownCard { .. }
+                | Error::EmptyPile { .. }
+                | Error::InsufficientFunds
+                | Error::OutstandingDebt,
+            ) => return Err(Error::InvalidAnswer),
+            result => result?,
+        }
+
+        self.pending.schedule(Task::ChooseBuy { player_index });
+        Ok(())
+    }
+
+    /// Runs a task the engine has scheduled, carrying on with `reply` if it
+    /// was waiting on a decision
+    pub(crate) fn run_task(&mut self, task: Task, reply: Reply) -> Result {
+        match task {
+            // Decisions are only presented once they come up
+            task @ Task::Ask { .. } => self.pending.schedule(task),
+            Task::Card {
+                card,
+                player_index,
+                step,
+                cards,
+            } => {
+                let resume = Resume { step, cards, reply };
+                return card.resume(self, player_index, resume);
+            }
+            Task::Hook {
+                hook,
+                card,
+                player_index,
+            } => {
+                return match hook {
+                    Hook::Play => card.effects_on_play(self, player_index),
+                    Hook::Gain => card.effects_on_gain(self, player_index),
+                    Hook::Buy => card.effects_on_buy(self, player_index),
+                    Hook::StartOfTurn => card.effects_at_start_of_turn(self, player_index),
+                    Hook::Trash => card.effects_on_trash(self, player_index),
+                    Hook::Discard => card.effects_on_discard(self, player_index),
+                    Hook::Reveal => card.effects_on_reveal(self, player_index),
+                    Hook::Cleanup => card.effects_at_cleanup(self, player_index),
+                    Hook::Attack => card.attack_effects(self, player_index),
+                    Hook::Reaction => card.reaction_effects(self, player_index),
+                };
+            }
+            Task::Gain { player_index, gain } => return self.start_gain(player_index, gain),
+            Task::ReactToGain {
+                card,
+                player_index,
+                zone,
+            } => return card.react_to_gain(self, player_index, zone),
+            Task::LandGain { player_index } => return self.land_gain(player_index),
+            Task::Play { player_index, card } => return self.play(player_index, card),
+            Task::Attack { player_index, card } => return self.attack(player_index, card),
+            Task::ChooseTarget { player_index, card } => {
+                return self.choose_target(player_index, card, &reply)
+            }
+            Task::AttackTargets { card, targets } => self.attack_targets(card, targets),
+            Task::EndAttack => self.unaffected.clear(),
+            Task::Reactions {
+                player_index,
+                reactions,
+            } => return self.reactions(player_index, &reactions, &reply),
+            Task::React { player_index, card } => {
+                self.reveal_cards(player_index, vec![card.clone()])?;
+                self.hook(Hook::Reaction, card, player_index);
+            }
+            Task::Turn { player_index } => {
+                for task in [
+                    Task::StartTurn { player_index },
+                    Task::ActionPhase { player_index },
+                    Task::SetPhase {
+                        player_index,
+                        phase: Phase::BuyPhase,
+                    },
+                    Task::BuyPhase { player_index },
+                    Task::EndTurn { player_index },
+                ] {
+                    self.pending.schedule(task);
                 }
             }
+            Task::StartTurn { player_index } => return self.begin_turn(player_index),
+            Task::Durations { player_index } => return self.next_duration(player_index),
+            Task::SetPhase {
+                player_index,
+                phase,
+            } => return self.set_phase(player_index, phase),
+            Task::ActionPhase { player_index } => {
+                // Villagers can be spent before each action, including when
+                // the player has run out of actions
+                self.offer_tokens(player_index, TokenKind::Villagers)?;
+                self.pending.schedule(Task::ChooseAction { player_index });
+            }
+            Task::ChooseAction { player_index } => return self.choose_action(player_index),
+            Task::PlayChosenAction { player_index } => {
+                return self.play_chosen_action(player_index, &reply)
+            }
+            Task::SpendTokens { player_index, kind } => {
+                return self.spend_chosen_tokens(player_index, kind, &reply)
+            }
+            Task::BuyPhase { player_index } => {
+                self.pending.schedule(Task::PlayTreasures { player_index });
+                self.pending.schedule(Task::StartBuying { player_index });
+            }
+            Task::PlayTreasures { player_index } => return self.choose_treasure(player_index),
+            Task::PlayChosenTreasure { player_index } => {
+                return self.play_chosen_treasure(player_index, &reply)
+            }
+            Task::StartBuying { player_index } => return self.start_buying(player_index),
+            Task::PayDebt { player_index } => return self.pay_debt(player_index),
+            Task::ChooseBuy { player_index } => return self.choose_buy(player_index),
+            Task::BuyChosen { player_index } => return self.buy_chosen(player_index, &reply),
+            Task::EndTurn { player_index } => {
+                self.set_phase(player_index, Phase::CleanupPhase)?;
+                self.pending.schedule(Task::Cleanup { player_index });
+                self.pending.schedule(Task::LeaveTurn { player_index });
+            }
+            Task::Cleanup { player_index } => return self.start_cleanup(player_index),
+            Task::FinishCleanup { player_index } => return self.finish_cleanup(player_index),
+            Task::LeaveTurn { player_index } => return self.leave_turn(player_index),
         }
 
-        // Do any end of buy phase stuff here
+        Ok(())
     }
 }
+
+/// The indexes of the cards a selector picks out of a zone
+fn select(cards: &CardDeck, zone: Zone, selector: &Selector) -> Result<Vec<usize>> {
+    let len = cards.len();
+    Ok(match selector {
+        Selector::Index(index) => vec![*index],
+        Selector::Indexes(indexes) => indexes.clone(),
+        // The top of the deck is its front, but the top of every other zone
+        // is its back
+        Selector::Top(count) if zone == Zone::Deck => (0..len.min(*count)).collect(),
+        Selector::Top(count) => (len.saturating_sub(*count)..len).rev().collect(),
+        Selector::All => (0..len).collect(),
+        // Cards with an id only match that exact copy
+        Selector::Card(card) => {
+            let index = cards
+                .iter()
+                .rposition(|c| match card.id() {
+                    Some(id) => c.id() == Some(id),
+                    None => c.name() == card.name(),
+                })
+                .ok_or_else(|| Error::CardNotFound {
+                    name: card.name().to_owned(),
+                    zone,
+                })?;
+            vec![index]
+        }
+    })
+}
@@ -1,11 +1,15 @@
 pub mod card;
 pub mod game;
+pub mod modifier;
 pub mod player;
+pub mod zone;
 
 pub use self::{
-    card::{Card, CardType},
-    game::{Game, PartialGame},
-    player::{Phase, Player},
+    card::{Card, CardId, CardType, Gain, Identified},
+    game::{ExtraTurn, Game, GameResult, LegalActions, PartialGame, PartialPlayer, PlayerScore},
+    modifier::{Lifetime, Modifier, ModifierKind},
+    player::{Phase, Player, TokenKind, Tokens},
+    zone::{Destination, Mat, Position, Selector, Visibility, Zone},
 };
 use std::collections::{HashMap, VecDeque};
 
@@ -43,6 +47,14 @@ impl Supply {
     pub fn get_mut(&mut self, k: &str) -> Option<&mut SupplyEntry> {
         self.entries.get_mut(k)
     }
+
+    /// Returns the number of piles with no cards left in them
+    pub fn empty_piles(&self) -> usize {
+        self.entries
+            .values()
+            .filter(|entry| entry.count == 0)
+            .count()
+    }
 }
 
 impl AsRef<HashMap<String, SupplyEntry>> for Supply {
@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::{CardId, CardType};
+
+/// A rule change that lasts for a while, e.g. cards costing less for the
+/// rest of the turn after a Bridge is played. The engine looks at the
+/// modifiers on the [`Game`](crate::types::Game) when treasures are played,
+/// costs are worked out and cards are drawn
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Modifier {
+    /// The player whose card added the modifier
+    pub player_index: usize,
+    pub kind: ModifierKind,
+    pub lifetime: Lifetime,
+}
+
+/// What a [`Modifier`] does
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ModifierKind {
+    /// Cards cost this much less for everyone, but not less than $0. Only
+    /// cards of `card_type` are cheaper if it is set, e.g. Action cards for
+    /// Quarry
+    CostReduction {
+        coins: usize,
+        card_type: Option<CardType>,
+    },
+    /// Cards cost this much less for the modifier's player only, e.g. Canal
+    OwnCostReduction(usize),
+    /// The player gets this much extra when they play the first copy of a
+    /// treasure this turn, e.g. Merchant's +$1 for the first Silver
+    FirstTreasureBonus { treasure: String, coins: usize },
+    /// The player draws this many cards for their next hand at clean-up,
+    /// instead of 5, e.g. from Outpost
+    HandSize(usize),
+    /// The player draws one card fewer the next time they draw, after which
+    /// the modifier is used up (the -1 Card token)
+    MinusCard,
+}
+
+/// How long a [`Modifier`] lasts
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Lifetime {
+    /// Until the end of the turn it was added in
+    ThisTurn,
+    /// Until the start of its player's next turn
+    UntilNextTurn,
+    /// Until the end of the game, or until it is used up
+    RestOfGame,
+    /// While the card with this id is in play, e.g. Highway's reduction
+    WhileInPlay(CardId),
+}
+
+impl Modifier {
+    pub fn new(player_index: usize, kind: ModifierKind, lifetime: Lifetime) -> Self {
+        Modifier {
+            player_index,
+            kind,
+            lifetime,
+        }
+    }
+}
@@ -1,12 +1,14 @@
-use std::collections::VecDeque;
-use std::mem;
+use std::collections::{BTreeMap, VecDeque};
 
+use itertools::Itertools;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
-use uuid::Uuid;
+use uuid::{Builder, Uuid};
 
 use crate::cards::base::{Copper, Estate};
-use crate::types::{CardDeck, CardList};
-use crate::utils;
+use crate::error::{Error, Result};
+use crate::types::{Card, CardDeck, CardList, Mat, Zone};
+use crate::utils::{self, GameRng};
 use victoire_macros::card_vec;
 
 /// Struct representing a player
@@ -19,24 +21,43 @@ pub struct Player {
     pub deck: CardDeck,
     pub discard: CardDeck,
     pub in_play: CardDeck,
+    /// Duration cards played on an earlier turn. They stay in play until
+    /// their effects happen at the start of the player's next turn
+    pub durations: CardDeck,
+    /// The cards on each of the player's mats. Mats with no cards on them
+    /// may be missing
+    pub mats: BTreeMap<Mat, CardDeck>,
+    /// Tokens the player keeps from turn to turn
+    pub tokens: Tokens,
     pub resources: Resources,
     pub state: State,
     pub phase: Phase,
+    /// The number of turns this player has taken so far
+    pub turns_taken: usize,
+    /// The generator used to shuffle this player's cards
+    pub rng: GameRng,
 }
 
 impl Player {
+    /// The starting deck every player gets: 7 coppers and 3 estates
+    pub fn default_deck() -> CardList {
+        card_vec![Copper, Copper, Copper, Copper, Copper, Copper, Copper, Estate, Estate, Estate]
+    }
+
     /// Constructs a new Player with the default deck (3 estates and 7 copper)
     pub fn new_with_default_deck(player_number: usize) -> Player {
-        let deck = card_vec![
-            Copper, Copper, Copper, Copper, Copper, Copper, Copper, Estate, Estate, Estate
-        ];
-        Player::new(player_number, deck)
+        Player::new(player_number, Player::default_deck())
     }
 
     /// Constructs a new Player with a given deck
     pub fn new(player_number: usize, cards: CardList) -> Player {
-        let uuid = Uuid::new_v4();
-        let mut hand: CardDeck = VecDeque::new();
+        Player::new_with_rng(player_number, cards, GameRng::default())
+    }
+
+    /// Constructs a new Player with a given deck, using `rng` for all of the
+    /// player's shuffles
+    pub fn new_with_rng(player_number: usize, cards: CardList, mut rng: GameRng) -> Player {
+        let uuid = Builder::from_random_bytes(rng.gen()).into_uuid();
         let mut deck: CardDeck = VecDeque::from(cards);
         let discard: CardDeck = VecDeque::new();
         let in_play: CardDeck = VecDeque::new();
@@ -44,12 +65,10 @@ impl Player {
         let state = State::default();
         let phase = Phase::OutOfTurn;
 
-        utils::shuffle(&mut deck);
+        utils::shuffle_with_rng(&mut deck, &mut rng);
 
         // Initial hand of 5 cards
-        for _ in 0..5 {
-            hand.push_back(deck.pop_front().unwrap());
-        }
+        let hand: CardDeck = deck.drain(..deck.len().min(5)).collect();
 
         Player {
             uuid,
@@ -58,9 +77,14 @@ impl Player {
             deck,
             discard,
             in_play,
+            durations: CardDeck::new(),
+            mats: BTreeMap::new(),
+            tokens: Tokens::default(),
             resources,
             state,
             phase,
+            turns_taken: 0,
+            rng,
         }
     }
 
@@ -68,7 +92,68 @@ impl Player {
         self.hand.len()
     }
 
+    /// Iterates over every card the player owns, regardless of where it is
+    pub fn all_cards(&self) -> impl Iterator<Item = &dyn Card> {
+        self.hand
+            .iter()
+            .chain(self.deck.iter())
+            .chain(self.discard.iter())
+            .chain(self.in_play.iter())
+            .chain(self.durations.iter())
+            .chain(self.mats.values().flatten())
+            .map(AsRef::as_ref)
+    }
+
+    /// The cards in one of the player's zones, or `None` for the zones
+    /// shared by every player
+    pub fn zone(&self, zone: Zone) -> Option<&CardDeck> {
+        match zone {
+            Zone::Hand => Some(&self.hand),
+            Zone::Deck => Some(&self.deck),
+            Zone::Discard => Some(&self.discard),
+            Zone::InPlay => Some(&self.in_play),
+            Zone::Durations => Some(&self.durations),
+            Zone::Mat(mat) => Some(self.mat(mat)),
+            _ => None,
+        }
+    }
+
+    /// Mutable access to the cards in one of the player's zones, or `None`
+    /// for the zones shared by every player
+    pub fn zone_mut(&mut self, zone: Zone) -> Option<&mut CardDeck> {
+        match zone {
+            Zone::Hand => Some(&mut self.hand),
+            Zone::Deck => Some(&mut self.deck),
+            Zone::Discard => Some(&mut self.discard),
+            Zone::InPlay => Some(&mut self.in_play),
+            Zone::Durations => Some(&mut self.durations),
+            Zone::Mat(mat) => Some(self.mat_mut(mat)),
+            _ => None,
+        }
+    }
+
+    /// The cards on one of the player's mats
+    pub fn mat(&self, mat: Mat) -> &CardDeck {
+        static EMPTY: CardDeck = CardDeck::new();
+        self.mats.get(&mat).unwrap_or(&EMPTY)
+    }
+
+    /// Mutable access to the cards on one of the player's mats
+    pub fn mat_mut(&mut self, mat: Mat) -> &mut CardDeck {
+        self.mats.entry(mat).or_default()
+    }
+
+    /// The total number of victory points the player has, from their cards
+    /// and their VP tokens
+    pub fn victory_points(&self) -> isize {
+        let cards: isize = self.all_cards().map(|card| card.victory_points(self)).sum();
+        cards + self.tokens.victory_points as isize
+    }
+
     /// Draws x cards for the player
+    ///
+    /// Prefer [`Game::draw_cards`](crate::types::Game::draw_cards) during a
+    /// game, which also reports the draw and any shuffles to observers
     pub fn draw_cards(&mut self, cards: usize) {
         for _ in 0..cards {
             // If deck is empty, shuffle discard and swap it with the empty deck
@@ -78,14 +163,28 @@ impl Player {
                     return;
                 }
 
-                utils::shuffle(&mut self.discard);
-                mem::swap(&mut self.deck, &mut self.discard);
+                self.shuffle_discard_into_deck();
             }
 
-            self.hand.push_back(self.deck.pop_front().unwrap());
+            if let Some(card) = self.deck.pop_front() {
+                self.hand.push_back(card);
+            }
         }
     }
 
+    /// Shuffles the discard pile and puts it under the deck
+    pub fn shuffle_discard_into_deck(&mut self) {
+        utils::shuffle_with_rng(&mut self.discard, &mut self.rng);
+        self.deck.append(&mut self.discard);
+    }
+
+    /// Adds coins from a treasure, which can be spent straight away even if
+    /// the buy phase has already started
+    pub fn add_treasure_coins(&mut self, coins: usize) {
+        self.resources.coins += coins;
+        self.resources.coins_remaining += coins;
+    }
+
     /// Gives the player extra actions for this turn
     pub fn add_actions(&mut self, actions: usize) {
         self.resources.actions += actions;
@@ -106,13 +205,20 @@ impl Player {
         // Reset resources
         self.resources.actions = 1;
         self.resources.buys = 1;
+        self.resources.coins = 0;
         self.resources.temp_coins = 0;
+        self.resources.coins_remaining = 0;
+        self.resources.potions = 0;
 
         // Reset conditions
         self.state = State::default();
     }
 
     /// Cleanup phase at end of turn - discard hand and draw five new cards
+    ///
+    /// Prefer [`Game::cleanup`](crate::types::Game::cleanup) during a game,
+    /// which also keeps durations in play, runs cleanup effects and reports
+    /// the moves to observers
     pub fn cleanup(&mut self) {
         self.discard.append(&mut self.hand);
         self.discard.append(&mut self.in_play);
@@ -120,49 +226,93 @@ impl Player {
         self.draw_cards(5);
     }
 
-    /// Discards cards from hand given an array of indexes of said cards
-    ///
-    /// Will panic if indexes are invalid
-    pub fn discard_given_indexes(&mut self, mut indexes: Vec<usize>) {
-        indexes.sort_unstable();
-        indexes.reverse();
-        for i in indexes {
-            //if hand is empty, return from function
-            if self.hand.is_empty() {
-                return;
-            }
-            self.discard.push_back(self.hand.remove(i).unwrap());
-        }
+    /// Removes the card at the given index from the player's hand
+    pub fn remove_from_hand(&mut self, index: usize) -> Result<Box<dyn Card>> {
+        self.hand
+            .remove(index)
+            .ok_or(Error::InvalidHandIndex { index })
     }
+}
 
-    /// Moves cards given indexes to hand
-    ///
-    /// Will panic if indexes are invalid
-    pub fn move_given_indexes_discard_to_hand(&mut self, indexes: Vec<usize>) {
-        for i in indexes {
-            if self.discard.is_empty() {
-                return;
-            }
-            self.hand.push_back(self.discard.remove(i).unwrap());
+/// Removes the cards at the given indexes, returning them in the order the
+/// indexes were given. Duplicate indexes are ignored. Fails without removing
+/// anything if any index is out of range
+pub(crate) fn take_indexes(
+    cards: &mut CardDeck,
+    indexes: Vec<usize>,
+    invalid: impl Fn(usize) -> Error,
+) -> Result<CardList> {
+    if let Some(&index) = indexes.iter().find(|&&i| i >= cards.len()) {
+        return Err(invalid(index));
+    }
+
+    let indexes: Vec<usize> = indexes.into_iter().unique().collect();
+
+    // Remove from the back so earlier indexes stay valid
+    let mut removed: Vec<(usize, Box<dyn Card>)> = indexes
+        .iter()
+        .copied()
+        .sorted_unstable()
+        .rev()
+        .filter_map(|i| cards.remove(i).map(|card| (i, card)))
+        .collect();
+
+    Ok(indexes
+        .into_iter()
+        .filter_map(|i| {
+            let position = removed.iter().position(|(index, _)| *index == i)?;
+            Some(removed.swap_remove(position).1)
+        })
+        .collect())
+}
+
+/// The token pools a player has. Unlike [`Resources`], these aren't reset
+/// at the start of each turn
+#[non_exhaustive]
+#[derive(Clone, Default, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tokens {
+    /// Can be spent during the buy phase for +1 coin each
+    pub coffers: usize,
+    /// Can be spent during the action phase for +1 action each
+    pub villagers: usize,
+    /// Count towards the player's score
+    pub victory_points: usize,
+    /// Has to be paid off with coins before the player can buy cards
+    pub debt: usize,
+}
+
+impl Tokens {
+    /// The number of tokens of the given kind
+    pub fn get(&self, kind: TokenKind) -> usize {
+        match kind {
+            TokenKind::Coffers => self.coffers,
+            TokenKind::Villagers => self.villagers,
+            TokenKind::VictoryPoints => self.victory_points,
+            TokenKind::Debt => self.debt,
         }
     }
 
-    /// Trashes cards from hand given an array of indexes of said cards
-    ///
-    /// Will panic if indexes are invalid
-    pub fn trash_given_indexes(&mut self, mut indexes: Vec<usize>, trash: &mut CardDeck) {
-        indexes.sort_unstable();
-        indexes.reverse();
-        for i in indexes {
-            //if hand is empty, return from function
-            if self.hand.is_empty() {
-                return;
-            }
-            trash.push_back(self.hand.remove(i).unwrap());
+    /// Mutable access to the number of tokens of the given kind
+    pub fn get_mut(&mut self, kind: TokenKind) -> &mut usize {
+        match kind {
+            TokenKind::Coffers => &mut self.coffers,
+            TokenKind::Villagers => &mut self.villagers,
+            TokenKind::VictoryPoints => &mut self.victory_points,
+            TokenKind::Debt => &mut self.debt,
         }
     }
 }
 
+/// The kinds of [`Tokens`] a player can have
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TokenKind {
+    Coffers,
+    Villagers,
+    VictoryPoints,
+    Debt,
+}
+
 #[non_exhaustive]
 #[derive(Clone, Default, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Resources {
@@ -171,6 +321,9 @@ pub struct Resources {
     pub coins: usize,
     pub temp_coins: usize,
     pub coins_remaining: usize,
+    /// Potions from treasures played this turn that haven't been spent
+    #[serde(default)]
+    pub potions: usize,
 }
 
 /// What phase are we in
@@ -197,9 +350,29 @@ impl Phase {
 
 /// Struct to keep track of certain conditions
 #[non_exhaustive]
-#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct State {
-    /// Is the player immune to attacks
-    pub immune: bool,
-    pub merchant_bonus: usize,
+    /// Whether the player has bought a card this turn, after which they
+    /// can't play any more treasures
+    pub has_bought: bool,
+    /// How many action cards the player has played this turn, including
+    /// ones played again by e.g. Throne Room
+    pub actions_played: usize,
+    /// Set by cards whose attack depends on a choice made while playing them
+    /// (e.g. Minion) to call off the attack. Cleared whenever an action is
+    /// played
+    pub skip_attack: bool,
+    /// Duration cards played this turn, which stay in play at cleanup
+    pub durations_played: CardList,
+    /// The cards gained during the player's turn, by them or by whoever
+    /// controls the turn (e.g. with Possession), which Smugglers can copy
+    pub gained: CardList,
+    /// The cards the player bought this turn
+    pub bought: CardList,
+    /// Whether attacks played by other players don't affect this player
+    /// until their next turn, e.g. from Lighthouse
+    pub immune_to_attacks: bool,
+    /// Victory point tokens the player gets whenever they buy a card this
+    /// turn, e.g. from Goons
+    pub vp_per_buy: usize,
 }
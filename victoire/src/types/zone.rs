@@ -0,0 +1,157 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{error::Error, types::Card};
+
+/// A place where cards can be. All zones except the trash and the supply
+/// belong to a player
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Zone {
+    Hand,
+    Deck,
+    Discard,
+    InPlay,
+    /// Duration cards played on an earlier turn that are still in play
+    Durations,
+    Mat(Mat),
+    Trash,
+    Supply,
+}
+
+/// The mats and set-aside areas a player can have cards on, besides their
+/// hand, deck, discard pile and play area
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Mat {
+    /// Cards set aside face down by Haven until the player's next turn
+    Haven,
+    /// Cards set aside by Island for the rest of the game
+    Island,
+    /// Cards set aside face down by Native Village
+    NativeVillage,
+    /// Reserve cards waiting to be called
+    Tavern,
+    /// Cards exiled until their owner gains another copy
+    Exile,
+    /// Cards set aside while a card's effects are resolved, e.g. the actions
+    /// skipped by Library, or the cards Sentry looks at. Cards that everyone
+    /// should see are [revealed](crate::types::Game::reveal_cards) as well
+    SetAside,
+    /// Cards trashed during a turn controlled by another player, e.g. with
+    /// Possession. They are returned to their owner's discard pile at the
+    /// end of the turn
+    Possession,
+}
+
+/// Who can look at the cards on a [`Mat`]
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Visibility {
+    /// Everyone can see the cards
+    Public,
+    /// Only the mat's owner can see the cards. Everyone else only knows how
+    /// many there are
+    Owner,
+}
+
+impl Mat {
+    /// Who can look at the cards on this mat
+    pub fn visibility(self) -> Visibility {
+        match self {
+            Mat::Haven | Mat::NativeVillage | Mat::SetAside => Visibility::Owner,
+            Mat::Island | Mat::Tavern | Mat::Exile | Mat::Possession => Visibility::Public,
+        }
+    }
+}
+
+impl From<Mat> for Zone {
+    fn from(mat: Mat) -> Self {
+        Zone::Mat(mat)
+    }
+}
+
+impl Zone {
+    /// Whether the zone belongs to a player, rather than being shared
+    pub fn is_player_zone(self) -> bool {
+        !matches!(self, Zone::Trash | Zone::Supply)
+    }
+
+    /// Whether everyone can see the cards in this zone
+    pub fn is_public(self) -> bool {
+        match self {
+            Zone::Hand | Zone::Deck => false,
+            Zone::Mat(mat) => mat.visibility() == Visibility::Public,
+            _ => true,
+        }
+    }
+
+    /// The error for a card index that is out of range in this zone
+    pub fn invalid_index(self, index: usize) -> Error {
+        match self {
+            Zone::Hand => Error::InvalidHandIndex { index },
+            Zone::Discard => Error::InvalidDiscardIndex { index },
+            Zone::Trash => Error::InvalidTrashIndex { index },
+            zone => Error::InvalidZoneIndex { zone, index },
+        }
+    }
+}
+
+/// Which cards to move with [`Game::move_card`](crate::types::Game::move_card)
+#[derive(Clone, Debug)]
+pub enum Selector {
+    /// The card at the given index. Index 0 is the top of the deck, and the
+    /// bottom of every other zone
+    Index(usize),
+    /// The cards at the given indexes, in the order given. Repeated indexes
+    /// are ignored
+    Indexes(Vec<usize>),
+    /// Up to this many cards from the top of the zone, topmost first. Taking
+    /// cards from the top of the deck shuffles the discard pile into it if it
+    /// runs out
+    Top(usize),
+    /// Every card in the zone
+    All,
+    /// This exact card if it has an [id](crate::types::CardId), otherwise
+    /// the most recent card with the same name. From the supply, a copy of
+    /// it from its pile
+    Card(Box<dyn Card>),
+}
+
+/// Whether cards moved with [`Game::move_card_to`](crate::types::Game::move_card_to)
+/// go on top of the zone they are moved to or at its bottom
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Position {
+    Top,
+    /// e.g. the bottom of the deck, for Secret Passage
+    Bottom,
+}
+
+/// Where [`Game::move_card_to`](crate::types::Game::move_card_to) puts the
+/// cards it moves
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Destination {
+    /// The player whose zone the cards go to, e.g. the player Masquerade
+    /// passes a card to
+    pub player_index: usize,
+    pub zone: Zone,
+    pub position: Position,
+}
+
+impl Destination {
+    /// The top of one of the player's zones, or of the trash or the supply
+    pub fn new(player_index: usize, zone: Zone) -> Self {
+        Destination {
+            player_index,
+            zone,
+            position: Position::Top,
+        }
+    }
+
+    /// The bottom of the zone instead of the top
+    pub fn bottom(self) -> Self {
+        Destination {
+            position: Position::Bottom,
+            ..self
+        }
+    }
+}
@@ -1,10 +1,52 @@
 use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 
 pub fn shuffle<T>(v: &mut VecDeque<T>) {
     shuffle_with_rng(v, &mut rand::thread_rng());
 }
 
-fn shuffle_with_rng<T>(v: &mut VecDeque<T>, rng: &mut impl Rng) {
+pub fn shuffle_with_rng<T>(v: &mut VecDeque<T>, rng: &mut impl Rng) {
     v.make_contiguous().shuffle(rng);
 }
+
+/// Seedable random number generator used for everything random in a game
+///
+/// The generator's state is serialized along with the rest of the game, so a
+/// saved game continues exactly as it would have without being saved.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameRng(ChaCha8Rng);
+
+impl Default for GameRng {
+    /// Creates a generator from a random seed
+    fn default() -> Self {
+        GameRng::seed_from_u64(rand::random())
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.0.try_fill_bytes(dest)
+    }
+}
+
+impl SeedableRng for GameRng {
+    type Seed = <ChaCha8Rng as SeedableRng>::Seed;
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        GameRng(ChaCha8Rng::from_seed(seed))
+    }
+}
@@ -0,0 +1,303 @@
+//! Helpers shared between the integration tests
+
+#![allow(dead_code)]
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use victoire::callbacks::{Callbacks, ChoiceCountOptions, TreasureChoice};
+use victoire::cards::base::*;
+use victoire::error::Result;
+use victoire::events::{GameEvent, Observer};
+use victoire::types::{Card, CardDeck, CardList, Game, Supply, TokenKind};
+
+pub fn cards(cards: Vec<Box<dyn Card>>) -> CardDeck {
+    cards.into_iter().collect()
+}
+
+/// A two player game where player 0 has the given hand and it is the start
+/// of their turn
+pub fn game_with_hand(hand: Vec<Box<dyn Card>>) -> Game {
+    let mut game = Game::default();
+    let player = &mut game.players[0];
+    player.reset_state();
+    player.hand = cards(hand);
+    game
+}
+
+/// Runs a card's effects for the player as if they had just played it,
+/// answering any decisions with `callbacks`
+pub fn play_effects(
+    game: &mut Game,
+    player_index: usize,
+    card: &(dyn Card + 'static),
+    callbacks: &dyn Callbacks,
+) -> Result {
+    game.action_effects(player_index, card)?;
+    game.resolve(callbacks)
+}
+
+pub fn names(cards: &CardDeck) -> Vec<&str> {
+    cards.iter().map(|card| card.name()).collect()
+}
+
+/// An observer that keeps every event it is sent
+#[derive(Default)]
+pub struct RecordingObserver {
+    pub events: Mutex<Vec<GameEvent>>,
+}
+
+impl RecordingObserver {
+    pub fn events(&self) -> Vec<GameEvent> {
+        self.events.lock().unwrap().clone()
+    }
+}
+
+impl Observer for RecordingObserver {
+    fn notify(&self, event: &GameEvent) {
+        self.events.lock().unwrap().push(event.clone());
+    }
+}
+
+/// A client that never plays actions and tries to buy the most expensive
+/// treasure or victory card it can, cycling through Province, Gold and Silver
+#[derive(Default)]
+pub struct BigMoneyClient {
+    attempts: Mutex<usize>,
+}
+
+impl Callbacks for BigMoneyClient {
+    fn choose_card_from_supply(
+        &self,
+        _player_index: usize,
+        _supply: &Supply,
+    ) -> Option<Box<dyn Card>> {
+        let mut attempts = self.attempts.lock().unwrap();
+        *attempts += 1;
+        match *attempts % 4 {
+            1 => Some(Box::new(Province)),
+            2 => Some(Box::new(Gold)),
+            3 => Some(Box::new(Silver)),
+            _ => None,
+        }
+    }
+
+    fn choose_cards_from_hand(
+        &self,
+        _player_index: usize,
+        _count: &ChoiceCountOptions,
+        _message: &str,
+    ) -> Vec<usize> {
+        vec![]
+    }
+
+    fn choose_cards_from_discard(
+        &self,
+        _player_index: usize,
+        _count: &ChoiceCountOptions,
+        _message: &str,
+    ) -> Vec<usize> {
+        vec![]
+    }
+
+    fn choose_cards_from_trash(
+        &self,
+        _player_index: usize,
+        _count: &ChoiceCountOptions,
+        _message: &str,
+    ) -> Vec<usize> {
+        vec![]
+    }
+
+    fn choose_cards_from_selection(
+        &self,
+        _player_index: usize,
+        _count: &ChoiceCountOptions,
+        _card_choices: &CardList,
+        _message: &str,
+    ) -> Vec<usize> {
+        vec![]
+    }
+
+    fn yes_or_no(&self, _player_index: usize, _prompt: &str) -> bool {
+        false
+    }
+
+    fn choose_players(
+        &self,
+        _player_index: usize,
+        _count: &ChoiceCountOptions,
+        _prompt: &str,
+    ) -> Vec<usize> {
+        vec![]
+    }
+    fn choose_options(
+        &self,
+        _player_index: usize,
+        _count: &ChoiceCountOptions,
+        _options: &[&str],
+        _prompt: &str,
+    ) -> Vec<usize> {
+        vec![]
+    }
+}
+
+/// A client that gives pre-recorded answers, in order, to each kind of
+/// prompt. Once a queue runs out it chooses nothing / answers no, except
+/// for treasures, which are then all played
+#[derive(Default)]
+pub struct ScriptedClient {
+    pub supply: Mutex<VecDeque<Option<Box<dyn Card>>>>,
+    pub hand: Mutex<VecDeque<Vec<usize>>>,
+    pub discard: Mutex<VecDeque<Vec<usize>>>,
+    pub trash: Mutex<VecDeque<Vec<usize>>>,
+    pub selection: Mutex<VecDeque<Vec<usize>>>,
+    pub yes_or_no: Mutex<VecDeque<bool>>,
+    pub players: Mutex<VecDeque<Vec<usize>>>,
+    pub options: Mutex<VecDeque<Vec<usize>>>,
+    pub treasures: Mutex<VecDeque<TreasureChoice>>,
+    pub tokens: Mutex<VecDeque<usize>>,
+}
+
+impl ScriptedClient {
+    pub fn supply(self, card: impl Card + 'static) -> Self {
+        self.supply.lock().unwrap().push_back(Some(Box::new(card)));
+        self
+    }
+
+    pub fn hand(self, indexes: Vec<usize>) -> Self {
+        self.hand.lock().unwrap().push_back(indexes);
+        self
+    }
+
+    pub fn discard(self, indexes: Vec<usize>) -> Self {
+        self.discard.lock().unwrap().push_back(indexes);
+        self
+    }
+
+    pub fn trash(self, indexes: Vec<usize>) -> Self {
+        self.trash.lock().unwrap().push_back(indexes);
+        self
+    }
+
+    pub fn selection(self, indexes: Vec<usize>) -> Self {
+        self.selection.lock().unwrap().push_back(indexes);
+        self
+    }
+
+    pub fn yes_or_no(self, answer: bool) -> Self {
+        self.yes_or_no.lock().unwrap().push_back(answer);
+        self
+    }
+
+    pub fn players(self, indexes: Vec<usize>) -> Self {
+        self.players.lock().unwrap().push_back(indexes);
+        self
+    }
+
+    pub fn options(self, indexes: Vec<usize>) -> Self {
+        self.options.lock().unwrap().push_back(indexes);
+        self
+    }
+
+    pub fn treasure(self, choice: TreasureChoice) -> Self {
+        self.treasures.lock().unwrap().push_back(choice);
+        self
+    }
+
+    pub fn tokens(self, count: usize) -> Self {
+        self.tokens.lock().unwrap().push_back(count);
+        self
+    }
+}
+
+fn next<T: Default>(queue: &Mutex<VecDeque<T>>) -> T {
+    queue.lock().unwrap().pop_front().unwrap_or_default()
+}
+
+impl Callbacks for ScriptedClient {
+    fn choose_card_from_supply(
+        &self,
+        _player_index: usize,
+        _supply: &Supply,
+    ) -> Option<Box<dyn Card>> {
+        next(&self.supply)
+    }
+
+    fn choose_cards_from_hand(
+        &self,
+        _player_index: usize,
+        _count: &ChoiceCountOptions,
+        _message: &str,
+    ) -> Vec<usize> {
+        next(&self.hand)
+    }
+
+    fn choose_cards_from_discard(
+        &self,
+        _player_index: usize,
+        _count: &ChoiceCountOptions,
+        _message: &str,
+    ) -> Vec<usize> {
+        next(&self.discard)
+    }
+
+    fn choose_cards_from_trash(
+        &self,
+        _player_index: usize,
+        _count: &ChoiceCountOptions,
+        _message: &str,
+    ) -> Vec<usize> {
+        next(&self.trash)
+    }
+
+    fn choose_cards_from_selection(
+        &self,
+        _player_index: usize,
+        _count: &ChoiceCountOptions,
+        _card_choices: &CardList,
+        _message: &str,
+    ) -> Vec<usize> {
+        next(&self.selection)
+    }
+
+    fn yes_or_no(&self, _player_index: usize, _prompt: &str) -> bool {
+        next(&self.yes_or_no)
+    }
+
+    fn choose_players(
+        &self,
+        _player_index: usize,
+        _count: &ChoiceCountOptions,
+        _prompt: &str,
+    ) -> Vec<usize> {
+        next(&self.players)
+    }
+
+    fn choose_options(
+        &self,
+        _player_index: usize,
+        _count: &ChoiceCountOptions,
+        _options: &[&str],
+        _prompt: &str,
+    ) -> Vec<usize> {
+        next(&self.options)
+    }
+
+    fn choose_treasure(&self, _player_index: usize) -> TreasureChoice {
+        self.treasures
+            .lock()
+            .unwrap()
+            .pop_front()
+            .unwrap_or(TreasureChoice::PlayAll)
+    }
+
+    fn choose_tokens_to_spend(
+        &self,
+        _player_index: usize,
+        _kind: TokenKind,
+        _available: usize,
+    ) -> usize {
+        next(&self.tokens)
+    }
+}
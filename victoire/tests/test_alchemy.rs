@@ -0,0 +1,128 @@
+//! Tests for the cards in Alchemy and turns controlled by another player
+
+mod common;
+
+use std::sync::Arc;
+
+use common::{play_effects, RecordingObserver, ScriptedClient};
+use victoire::cards::{alchemy::*, base::*, dominion::*, seaside::*};
+use victoire::events::GameEvent;
+use victoire::types::{Card, Game, Mat};
+
+#[test]
+fn test_possession() {
+    let mut game = Game::default();
+    let observer = Arc::new(RecordingObserver::default());
+    game.add_observer(observer.clone());
+    let client = ScriptedClient::default().supply(Province);
+
+    game.players[0].reset_state();
+    play_effects(&mut game, 0, &Possession, &client).unwrap();
+    game.end_turn(0, &client).unwrap();
+    game.next_turn();
+    assert_eq!(game.current_turn, 1);
+    assert_eq!(game.controller(1), Some(0));
+
+    // Whatever player 1 gains on the turn goes to player 0 instead
+    game.players[1].hand = vec![Box::new(Gold) as Box<dyn Card>; 3].into();
+    game.turn(1, &client).unwrap();
+    assert!(game.players[0]
+        .discard
+        .iter()
+        .any(|c| c.name() == "Province"));
+    assert!(game.players[1].all_cards().all(|c| c.name() != "Province"));
+    let gained: Vec<_> = game.players[1]
+        .state
+        .gained
+        .iter()
+        .map(|c| c.name())
+        .collect();
+    assert_eq!(gained, ["Province"]);
+    assert!(observer.events().contains(&GameEvent::TurnStarted {
+        player_index: 1,
+        turn: 0,
+        extra: true,
+        controller: Some(0),
+    }));
+
+    // Afterwards player 1 takes their own turn as usual
+    game.next_turn();
+    assert_eq!(game.current_turn, 1);
+    assert_eq!(game.controller(1), None);
+    assert!(game.extra_turn.is_none());
+}
+
+#[test]
+fn test_possession_smugglers() {
+    let mut game = Game::default();
+    let client = ScriptedClient::default().supply(Silver);
+
+    game.players[0].reset_state();
+    play_effects(&mut game, 0, &Possession, &client).unwrap();
+    game.end_turn(0, &client).unwrap();
+    game.next_turn();
+
+    game.players[1].hand = vec![Box::new(Copper) as Box<dyn Card>; 3].into();
+    game.turn(1, &client).unwrap();
+    assert_eq!(game.players[0].discard.back().unwrap().name(), "Silver");
+
+    // Cards gained on a possessed turn are still gained on that player's
+    // turn, so Smugglers can copy them even though the player who played
+    // Possession got them (Possession's rulings, Dominion Strategy wiki)
+    game.players[0].reset_state();
+    game.players[0].discard.clear();
+    play_effects(
+        &mut game,
+        0,
+        &Smugglers,
+        &ScriptedClient::default().selection(vec![0]),
+    )
+    .unwrap();
+    let discard: Vec<_> = game.players[0].discard.iter().map(|c| c.name()).collect();
+    assert_eq!(discard, ["Silver"]);
+}
+
+#[test]
+fn test_possession_trash() {
+    let mut game = Game::default();
+    let observer = Arc::new(RecordingObserver::default());
+    game.add_observer(observer.clone());
+    let client = ScriptedClient::default();
+
+    game.players[0].reset_state();
+    play_effects(&mut game, 0, &Possession, &client).unwrap();
+    game.end_turn(0, &client).unwrap();
+    game.next_turn();
+
+    // Cards of player 1's trashed on the turn are set aside for them, then
+    // returned to their discard pile at the end of the turn
+    game.players[1].hand = vec![
+        Box::new(Chapel) as Box<dyn Card>,
+        Box::new(Copper),
+        Box::new(Copper),
+        Box::new(Estate),
+    ]
+    .into();
+    game.players[1].discard.clear();
+    let client = ScriptedClient::default().hand(vec![0]).hand(vec![0, 1]);
+    game.turn(1, &client).unwrap();
+
+    assert!(game.trash.is_empty());
+    assert!(game.players[1].mat(Mat::Possession).is_empty());
+    let discard: Vec<_> = game.players[1].discard.iter().map(|c| c.name()).collect();
+    assert_eq!(discard, ["Estate", "Chapel", "Copper", "Copper"]);
+    let trashed = observer
+        .events()
+        .into_iter()
+        .filter(|event| {
+            matches!(
+                event,
+                GameEvent::CardTrashed {
+                    player_index: 1,
+                    ..
+                }
+            )
+        })
+        .count();
+    assert_eq!(trashed, 2);
+}
@@ -0,0 +1,272 @@
+//! Tests for driving a game through asynchronous callbacks
+
+mod common;
+
+use std::future;
+
+use async_trait::async_trait;
+use common::{BigMoneyClient, ScriptedClient};
+use futures::{executor::block_on, FutureExt};
+use victoire::callbacks::{AsyncCallbacks, ChoiceCountOptions, SyncCallbacks};
+use victoire::cards::base::*;
+use victoire::cards::dominion::*;
+use victoire::decisions::AsyncCard;
+use victoire::error::Error;
+use victoire::types::{Card, CardList, Game, Phase, Supply};
+
+/// A client that never gets round to choosing a card from the supply, like a
+/// player who has disconnected
+struct StalledClient;
+
+#[async_trait]
+impl AsyncCallbacks for StalledClient {
+    async fn choose_card_from_supply(
+        &self,
+        _player_index: usize,
+        _supply: &Supply,
+    ) -> Option<Box<dyn Card>> {
+        future::pending().await
+    }
+
+    async fn choose_cards_from_hand(
+        &self,
+        _player_index: usize,
+        _count: &ChoiceCountOptions,
+        _message: &str,
+    ) -> Vec<usize> {
+        vec![]
+    }
+
+    async fn choose_cards_from_discard(
+        &self,
+        _player_index: usize,
+        _count: &ChoiceCountOptions,
+        _message: &str,
+    ) -> Vec<usize> {
+        vec![]
+    }
+
+    async fn choose_cards_from_trash(
+        &self,
+        _player_index: usize,
+        _count: &ChoiceCountOptions,
+        _message: &str,
+    ) -> Vec<usize> {
+        vec![]
+    }
+
+    async fn choose_cards_from_selection(
+        &self,
+        _player_index: usize,
+        _count: &ChoiceCountOptions,
+        _card_choices: &CardList,
+        _message: &str,
+    ) -> Vec<usize> {
+        vec![]
+    }
+
+    async fn yes_or_no(&self, _player_index: usize, _prompt: &str) -> bool {
+        false
+    }
+
+    async fn choose_players(
+        &self,
+        _player_index: usize,
+        _count: &ChoiceCountOptions,
+        _prompt: &str,
+    ) -> Vec<usize> {
+        vec![]
+    }
+    async fn choose_options(
+        &self,
+        _player_index: usize,
+        _count: &ChoiceCountOptions,
+        _options: &[&str],
+        _prompt: &str,
+    ) -> Vec<usize> {
+        vec![]
+    }
+}
+
+/// A client whose answers never match the decision it was asked, like a
+/// buggy or malicious frontend
+struct WrongClient;
+
+#[async_trait]
+impl AsyncCallbacks for WrongClient {
+    async fn choose_card_from_supply(
+        &self,
+        _player_index: usize,
+        _supply: &Supply,
+    ) -> Option<Box<dyn Card>> {
+        Some(Box::new(Chapel))
+    }
+
+    async fn choose_cards_from_hand(
+        &self,
+        _player_index: usize,
+        _count: &ChoiceCountOptions,
+        _message: &str,
+    ) -> Vec<usize> {
+        vec![99]
+    }
+
+    async fn choose_cards_from_discard(
+        &self,
+        _player_index: usize,
+        _count: &ChoiceCountOptions,
+        _message: &str,
+    ) -> Vec<usize> {
+        vec![99]
+    }
+
+    async fn choose_cards_from_trash(
+        &self,
+        _player_index: usize,
+        _count: &ChoiceCountOptions,
+        _message: &str,
+    ) -> Vec<usize> {
+        vec![99]
+    }
+
+    async fn choose_cards_from_selection(
+        &self,
+        _player_index: usize,
+        _count: &ChoiceCountOptions,
+        _card_choices: &CardList,
+        _message: &str,
+    ) -> Vec<usize> {
+        vec![99]
+    }
+
+    async fn yes_or_no(&self, _player_index: usize, _prompt: &str) -> bool {
+        false
+    }
+
+    async fn choose_players(
+        &self,
+        _player_index: usize,
+        _count: &ChoiceCountOptions,
+        _prompt: &str,
+    ) -> Vec<usize> {
+        vec![99]
+    }
+    async fn choose_options(
+        &self,
+        _player_index: usize,
+        _count: &ChoiceCountOptions,
+        _options: &[&str],
+        _prompt: &str,
+    ) -> Vec<usize> {
+        vec![99]
+    }
+}
+
+#[test]
+fn test_turn_async_matches_sync() {
+    let mut game = Game::default_with_seed(3);
+    game.players[0].hand.push_back(Box::new(Smithy));
+    let mut async_game = game.clone();
+
+    let script = || ScriptedClient::default().hand(vec![5]).supply(Silver);
+    game.turn(0, &script()).unwrap();
+    block_on(async_game.turn_async(0, &SyncCallbacks(script()))).unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&game.players).unwrap(),
+        serde_json::to_value(&async_game.players).unwrap()
+    );
+    assert_eq!(async_game.players[0].turns_taken, 1);
+    assert!(async_game.players[0]
+        .all_cards()
+        .any(|card| card.name() == "Silver"));
+}
+
+#[test]
+fn test_phases_async() {
+    let mut game = Game::default();
+    game.players[0].reset_state();
+    game.players[0].hand.clear();
+    game.players[0].hand.push_back(Box::new(Village));
+
+    let client = SyncCallbacks(ScriptedClient::default().hand(vec![0]).supply(Copper));
+    game.set_phase(0, Phase::ActionPhase).unwrap();
+    block_on(game.action_phase_async(0, &client)).unwrap();
+    assert_eq!(game.players[0].in_play[0].name(), "Village");
+    assert_eq!(game.players[0].hand.len(), 1);
+    assert_eq!(game.players[0].resources.actions, 2);
+
+    game.set_phase(0, Phase::BuyPhase).unwrap();
+    block_on(game.buy_phase_async(0, &client)).unwrap();
+    assert_eq!(game.players[0].discard.back().unwrap().name(), "Copper");
+    assert_eq!(game.players[0].resources.buys, 0);
+}
+
+#[test]
+fn test_effects_on_play_async() {
+    let mut game = Game::default();
+    let cellar: Box<dyn Card> = Box::new(Cellar);
+
+    let client = SyncCallbacks(ScriptedClient::default().hand(vec![0, 1]));
+    block_on(cellar.effects_on_play_async(&mut game, 0, &client)).unwrap();
+
+    assert_eq!(game.players[0].hand.len(), 5);
+    assert_eq!(game.players[0].discard.len(), 2);
+}
+
+#[test]
+fn test_async_with_pending_decision() {
+    let mut game = Game::default();
+    game.step().unwrap();
+
+    let client = SyncCallbacks(BigMoneyClient::default());
+    assert!(matches!(
+        block_on(game.turn_async(0, &client)),
+        Err(Error::DecisionPending)
+    ));
+}
+
+#[test]
+fn test_dropped_turn_leaves_game_unchanged() {
+    let mut game = Game::default();
+    let before = serde_json::to_value(&game).unwrap();
+
+    assert!(game.turn_async(0, &StalledClient).now_or_never().is_none());
+
+    assert_eq!(serde_json::to_value(&game).unwrap(), before);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_concurrent_games() {
+    let tasks: Vec<_> = (0..4)
+        .map(|seed| {
+            tokio::spawn(async move {
+                let mut game = Game::default_with_seed(seed);
+                let client = SyncCallbacks(BigMoneyClient::default());
+                let result = game.play_to_completion_async(&client).await.unwrap();
+                (game, result)
+            })
+        })
+        .collect();
+
+    for task in tasks {
+        let (game, result) = task.await.unwrap();
+        assert!(game.is_game_over());
+        assert_eq!(result, game.result());
+    }
+}
+
+#[test]
+fn test_async_gives_up_on_invalid_answers() {
+    let mut game = Game::default();
+    game.players[0].hand.push_back(Box::new(Village));
+
+    assert!(matches!(
+        block_on(game.turn_async(0, &WrongClient)),
+        Err(Error::TooManyInvalidAnswers)
+    ));
+    assert!(matches!(
+        block_on(game.play_to_completion_async(&WrongClient)),
+        Err(Error::TooManyInvalidAnswers)
+    ));
+}
@@ -0,0 +1,250 @@
+//! Tests for driving a game through commands
+
+mod common;
+
+use common::BigMoneyClient;
+use victoire::cards::{base::*, dominion::*};
+use victoire::commands::Command;
+use victoire::error::Error;
+use victoire::types::{Card, Game, Phase, TokenKind};
+
+fn set_hand(game: &mut Game, player_index: usize, cards: Vec<Box<dyn Card>>) {
+    let hand = &mut game.players[player_index].hand;
+    hand.clear();
+    hand.extend(cards);
+}
+
+#[test]
+fn test_commands_turn() {
+    let mut game = Game::default();
+    let client = BigMoneyClient::default();
+    set_hand(
+        &mut game,
+        0,
+        vec![
+            Box::new(Festival),
+            Box::new(Copper),
+            Box::new(Silver),
+            Box::new(Estate),
+        ],
+    );
+
+    game.apply(0, Command::PlayAction { hand_index: 0 }, &client)
+        .unwrap();
+    assert_eq!(game.players[0].phase, Phase::ActionPhase);
+    assert_eq!(game.players[0].turns_taken, 1);
+    assert_eq!(game.players[0].resources.actions, 2);
+
+    game.apply(0, Command::EndPhase, &client).unwrap();
+    assert_eq!(game.players[0].phase, Phase::BuyPhase);
+
+    game.apply(0, Command::PlayTreasure { hand_index: 1 }, &client)
+        .unwrap();
+    game.apply(0, Command::PlayAllTreasures, &client).unwrap();
+    assert_eq!(game.players[0].resources.coins_remaining, 5);
+
+    game.apply(
+        0,
+        Command::Buy {
+            card: Box::new(Silver),
+        },
+        &client,
+    )
+    .unwrap();
+    assert_eq!(game.players[0].resources.coins_remaining, 2);
+    assert_eq!(game.players[0].resources.buys, 1);
+
+    // No more treasures can be played once a card has been bought
+    game.players[0].hand.push_back(Box::new(Copper));
+    assert!(matches!(
+        game.apply(0, Command::PlayAllTreasures, &client),
+        Err(Error::WrongPhase)
+    ));
+    assert!(game.legal_actions(0).treasures.is_empty());
+
+    game.apply(0, Command::EndPhase, &client).unwrap();
+    assert_eq!(game.players[0].phase, Phase::OutOfTurn);
+    assert_eq!(game.players[0].hand.len(), 5);
+    assert_eq!(game.current_turn, 1);
+}
+
+#[test]
+fn test_commands_spend_tokens() {
+    let mut game = Game::default();
+    let client = BigMoneyClient::default();
+    game.players[0].tokens.villagers = 1;
+    game.players[0].tokens.coffers = 2;
+
+    let spend = |kind, count| Command::SpendTokens { kind, count };
+    game.apply(0, spend(TokenKind::Villagers, 1), &client)
+        .unwrap();
+    assert_eq!(game.players[0].resources.actions, 2);
+    assert_eq!(game.legal_actions(0).villagers, 0);
+    assert!(matches!(
+        game.apply(0, spend(TokenKind::Coffers, 1), &client),
+        Err(Error::WrongPhase)
+    ));
+
+    game.apply(0, Command::EndPhase, &client).unwrap();
+    assert_eq!(game.legal_actions(0).coffers, 2);
+    let coins = game.players[0].resources.coins_remaining;
+    game.apply(0, spend(TokenKind::Coffers, 2), &client)
+        .unwrap();
+    assert_eq!(game.players[0].resources.coins_remaining, coins + 2);
+    assert_eq!(game.players[0].tokens.coffers, 0);
+}
+
+#[test]
+fn test_commands_validation() {
+    let mut game = Game::default();
+    let client = BigMoneyClient::default();
+    set_hand(&mut game, 0, vec![Box::new(Copper), Box::new(Smithy)]);
+
+    let apply = |game: &mut Game, player_index, command| {
+        let before = serde_json::to_value(&game.players).unwrap();
+        let result = game.apply(player_index, command, &client);
+        if result.is_err() {
+            assert_eq!(serde_json::to_value(&game.players).unwrap(), before);
+        }
+        result
+    };
+
+    assert!(matches!(
+        apply(&mut game, 1, Command::EndPhase),
+        Err(Error::OutOfTurn)
+    ));
+    assert!(matches!(
+        apply(&mut game, 5, Command::EndPhase),
+        Err(Error::OutOfTurn)
+    ));
+
+    // An invalid first command doesn't start the turn
+    assert!(matches!(
+        apply(&mut game, 0, Command::PlayTreasure { hand_index: 0 }),
+        Err(Error::WrongPhase)
+    ));
+    assert!(matches!(
+        apply(&mut game, 0, Command::PlayAction { hand_index: 2 }),
+        Err(Error::InvalidHandIndex { index: 2 })
+    ));
+    assert!(matches!(
+        apply(&mut game, 0, Command::PlayAction { hand_index: 0 }),
+        Err(Error::CardTypeMisMatch { .. })
+    ));
+    assert_eq!(game.players[0].phase, Phase::OutOfTurn);
+
+    game.start_turn(0, &client).unwrap();
+    game.players[0].resources.actions = 0;
+    assert!(matches!(
+        apply(&mut game, 0, Command::PlayAction { hand_index: 1 }),
+        Err(Error::NoActionsLeft)
+    ));
+    assert!(matches!(
+        apply(
+            &mut game,
+            0,
+            Command::Buy {
+                card: Box::new(Copper)
+            }
+        ),
+        Err(Error::WrongPhase)
+    ));
+
+    apply(&mut game, 0, Command::EndPhase).unwrap();
+    assert!(matches!(
+        apply(&mut game, 0, Command::PlayTreasure { hand_index: 1 }),
+        Err(Error::CardTypeMisMatch { .. })
+    ));
+    assert!(matches!(
+        apply(
+            &mut game,
+            0,
+            Command::Buy {
+                card: Box::new(Gold)
+            }
+        ),
+        Err(Error::InsufficientFunds)
+    ));
+    assert!(matches!(
+        apply(
+            &mut game,
+            0,
+            Command::Buy {
+                card: Box::new(Witch)
+            }
+        ),
+        Err(Error::UnknownCard { .. })
+    ));
+    game.supply.get_mut(BasicCurse.name()).unwrap().count = 0;
+    assert!(matches!(
+        apply(
+            &mut game,
+            0,
+            Command::Buy {
+                card: Box::new(BasicCurse)
+            }
+        ),
+        Err(Error::EmptyPile { .. })
+    ));
+
+    game.players[0].resources.buys = 0;
+    assert!(matches!(
+        apply(
+            &mut game,
+            0,
+            Command::Buy {
+                card: Box::new(Copper)
+            }
+        ),
+        Err(Error::NoBuysLeft)
+    ));
+
+    game.supply.get_mut(Province.name()).unwrap().count = 0;
+    assert!(matches!(
+        apply(&mut game, 0, Command::EndPhase),
+        Err(Error::GameOver)
+    ));
+}
+
+#[test]
+fn test_commands_serialization() {
+    let commands = vec![
+        Command::PlayAction { hand_index: 3 },
+        Command::PlayAllTreasures,
+        Command::Buy {
+            card: Box::new(Gold),
+        },
+        Command::EndPhase,
+    ];
+
+    let json = serde_json::to_string(&commands).unwrap();
+    let restored: Vec<Command> = serde_json::from_str(&json).unwrap();
+    assert_eq!(commands, restored);
+}
+
+#[test]
+fn test_commands_play_to_completion() {
+    let mut game = Game::default_with_seed(11);
+    let client = BigMoneyClient::default();
+
+    while !game.is_game_over() {
+        let player_index = game.current_turn;
+        let legal = game.legal_actions(player_index);
+
+        let wanted = ["Province", "Gold", "Silver"]
+            .into_iter()
+            .find_map(|name| legal.buys.iter().find(|card| card.name() == name));
+
+        let command = if !legal.treasures.is_empty() {
+            Command::PlayAllTreasures
+        } else if let Some(card) = wanted {
+            Command::Buy { card: card.clone() }
+        } else {
+            Command::EndPhase
+        };
+
+        game.apply(player_index, command, &client).unwrap();
+    }
+
+    assert!(game.players.iter().all(|player| player.turns_taken > 0));
+}
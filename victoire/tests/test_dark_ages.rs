@@ -0,0 +1,28 @@
+//! Tests for the cards in Dark Ages
+
+mod common;
+
+use common::ScriptedClient;
+use victoire::cards::{base::*, dark_ages::*};
+use victoire::types::{Card, Game};
+
+#[test]
+fn test_feodum() {
+    let mut game = Game::default();
+    let player = &mut game.players[0];
+    player.hand = vec![Box::new(Feodum) as Box<dyn Card>]
+        .into_iter()
+        .collect();
+    player.deck = vec![Box::new(Silver) as Box<dyn Card>; 5]
+        .into_iter()
+        .collect();
+    assert_eq!(Feodum.victory_points(&game.players[0]), 1);
+
+    // Trashing it gains two Silvers, which are counted straight away
+    game.trash_from_hand(0, vec![0]).unwrap();
+    game.resolve(&ScriptedClient::default()).unwrap();
+    let player = &game.players[0];
+    assert!(player.hand.is_empty());
+    assert_eq!(player.discard.len(), 2);
+    assert_eq!(Feodum.victory_points(player), 2);
+}
@@ -0,0 +1,245 @@
+//! Tests for driving a game through decisions rather than callbacks
+
+mod common;
+
+use std::sync::Arc;
+
+use common::RecordingObserver;
+use victoire::cards::{base::Copper, dominion::*};
+use victoire::decisions::{Answer, Decision, DecisionKind, DecisionOption, Step};
+use victoire::error::Error;
+use victoire::events::GameEvent;
+use victoire::types::{Card, ExtraTurn, Game, TokenKind};
+
+/// Buys the best treasure or Province the player can afford, and otherwise
+/// chooses as little as possible
+fn big_money(game: &Game, decision: &Decision) -> Answer {
+    match decision.kind {
+        DecisionKind::YesOrNo => Answer::YesOrNo(false),
+        DecisionKind::Treasure => Answer::PlayAllTreasures,
+        DecisionKind::Supply => {
+            let coins = game.players[decision.player_index]
+                .resources
+                .coins_remaining;
+            let wanted = match coins {
+                8.. => "Province",
+                6..=7 => "Gold",
+                3..=5 => "Silver",
+                _ => return Answer::Choose(vec![]),
+            };
+            let index = decision
+                .options
+                .iter()
+                .position(
+                    |option| matches!(option, DecisionOption::Card(card) if card.name() == wanted),
+                )
+                .unwrap();
+            Answer::Choose(vec![index])
+        }
+        _ => Answer::Choose((0..decision.min).collect()),
+    }
+}
+
+fn expect_decision(step: Step) -> Decision {
+    match step {
+        Step::Decision(decision) => decision,
+        other => panic!("Expected a decision, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_decisions_turn() {
+    let mut game = Game::default();
+
+    let decision = expect_decision(game.step().unwrap());
+    assert_eq!(decision.player_index, 0);
+    assert_eq!(decision.kind, DecisionKind::Hand);
+    assert_eq!(decision.options.len(), 5);
+    assert_eq!((decision.min, decision.max), (0, 1));
+
+    // Asking again gives the same decision
+    assert_eq!(expect_decision(game.step().unwrap()), decision);
+
+    game.respond(decision.id, Answer::Choose(vec![])).unwrap();
+    let decision = expect_decision(game.step().unwrap());
+    assert_eq!(decision.kind, DecisionKind::Treasure);
+    assert_eq!(decision.options.len(), 5);
+
+    game.respond(decision.id, Answer::PlayAllTreasures).unwrap();
+    let decision = expect_decision(game.step().unwrap());
+    assert_eq!(decision.kind, DecisionKind::Supply);
+
+    game.respond(decision.id, Answer::Choose(vec![])).unwrap();
+    assert_eq!(game.step().unwrap(), Step::TurnEnded { player_index: 0 });
+    assert_eq!(game.current_turn, 1);
+    assert_eq!(game.players[0].turns_taken, 1);
+}
+
+#[test]
+fn test_decisions_spend_tokens() {
+    let mut game = Game::default();
+    game.players[0].tokens.villagers = 2;
+
+    let decision = expect_decision(game.step().unwrap());
+    assert_eq!(
+        decision.kind,
+        DecisionKind::Tokens {
+            kind: TokenKind::Villagers
+        }
+    );
+    assert_eq!(decision.options.len(), 3);
+    assert_eq!((decision.min, decision.max), (1, 1));
+
+    game.respond(decision.id, Answer::Choose(vec![2])).unwrap();
+    let decision = expect_decision(game.step().unwrap());
+    assert_eq!(decision.kind, DecisionKind::Hand);
+    assert_eq!(game.players[0].resources.actions, 3);
+    assert_eq!(game.players[0].tokens.villagers, 0);
+}
+
+#[test]
+fn test_decisions_invalid_responses() {
+    let mut game = Game::default();
+    assert!(matches!(
+        game.respond(0, Answer::Choose(vec![])),
+        Err(Error::NoPendingDecision)
+    ));
+
+    let decision = expect_decision(game.step().unwrap());
+    assert!(matches!(
+        game.respond(decision.id + 1, Answer::Choose(vec![])),
+        Err(Error::UnknownDecision { .. })
+    ));
+    assert!(matches!(
+        game.respond(decision.id, Answer::Choose(vec![0, 1])),
+        Err(Error::InvalidAnswer)
+    ));
+    assert!(matches!(
+        game.respond(decision.id, Answer::Choose(vec![7])),
+        Err(Error::InvalidAnswer)
+    ));
+    assert!(matches!(
+        game.respond(decision.id, Answer::YesOrNo(true)),
+        Err(Error::InvalidAnswer)
+    ));
+    // The answer is well formed, but Estate isn't an action, so the decision
+    // stays pending
+    assert!(matches!(
+        game.respond(decision.id, Answer::Choose(vec![0])),
+        Err(Error::InvalidAnswer)
+    ));
+    assert_eq!(game.pending_decision().map(|d| d.id), Some(decision.id));
+    assert!(game.respond(decision.id, Answer::Choose(vec![])).is_ok());
+}
+
+#[test]
+fn test_decisions_play_cards() {
+    let mut game = Game::default();
+    let observer = Arc::new(RecordingObserver::default());
+    game.add_observer(observer.clone());
+    game.players[0].hand.push_back(Box::new(Cellar));
+
+    // Play Cellar, then discard two cards with it
+    let decision = expect_decision(game.step().unwrap());
+    game.respond(decision.id, Answer::Choose(vec![5])).unwrap();
+    let decision = expect_decision(game.step().unwrap());
+    assert_eq!(decision.kind, DecisionKind::Hand);
+    assert_eq!(decision.options.len(), 5);
+    game.respond(decision.id, Answer::Choose(vec![0, 1]))
+        .unwrap();
+
+    // Cellar gives another action, so choose not to play anything else
+    let decision = expect_decision(game.step().unwrap());
+    assert_eq!(decision.kind, DecisionKind::Hand);
+    game.respond(decision.id, Answer::Choose(vec![])).unwrap();
+
+    let decision = expect_decision(game.step().unwrap());
+    assert_eq!(decision.kind, DecisionKind::Treasure);
+    let player = &game.players[0];
+    assert_eq!(player.in_play[0].name(), "Cellar");
+    assert_eq!(player.discard.len(), 2);
+    assert_eq!(player.hand.len(), 5);
+
+    // The turn carries on from where it was, so it only starts once
+    let turn_starts = |events: &[GameEvent]| {
+        events
+            .iter()
+            .filter(|event| matches!(event, GameEvent::TurnStarted { .. }))
+            .count()
+    };
+    assert_eq!(turn_starts(&observer.events()), 1);
+
+    game.respond(decision.id, Answer::Choose(vec![])).unwrap();
+    while let Step::Decision(decision) = game.step().unwrap() {
+        let answer = big_money(&game, &decision);
+        game.respond(decision.id, answer).unwrap();
+    }
+    assert_eq!(turn_starts(&observer.events()), 1);
+    assert_eq!(game.current_turn, 1);
+}
+
+#[test]
+fn test_decisions_save_mid_turn() {
+    let mut game = Game::default_with_seed(5);
+    for _ in 0..6 {
+        let step = game.step().unwrap();
+        if let Step::Decision(decision) = step {
+            let answer = big_money(&game, &decision);
+            game.respond(decision.id, answer).unwrap();
+        }
+    }
+
+    let json = serde_json::to_string(&game).unwrap();
+    let mut restored: Game = serde_json::from_str(&json).unwrap();
+
+    for game in [&mut game, &mut restored] {
+        loop {
+            match game.step().unwrap() {
+                Step::Decision(decision) => {
+                    let answer = big_money(game, &decision);
+                    game.respond(decision.id, answer).unwrap();
+                }
+                Step::TurnEnded { .. } if game.players[0].turns_taken < 10 => {}
+                _ => break,
+            }
+        }
+    }
+
+    assert_eq!(
+        serde_json::to_value(&game).unwrap(),
+        serde_json::to_value(&restored).unwrap()
+    );
+}
+
+#[test]
+fn test_decisions_play_to_completion() {
+    let mut game = Game::default();
+    let result = loop {
+        match game.step().unwrap() {
+            Step::Decision(decision) => {
+                let answer = big_money(&game, &decision);
+                game.respond(decision.id, answer).unwrap();
+            }
+            Step::GameOver(result) => break result,
+            _ => {}
+        }
+    };
+
+    assert!(game.is_game_over());
+    assert_eq!(result, game.result());
+    assert_eq!(game.step().unwrap(), Step::GameOver(result));
+}
+
+#[test]
+fn test_decisions_controlled_turn() {
+    let mut game = Game::default();
+    game.take_extra_turn(ExtraTurn::controlled(1, 0));
+    game.next_turn();
+    game.players[1].hand = vec![Box::new(Copper) as Box<dyn Card>; 3].into();
+
+    // Player 0 decides, but chooses from player 1's cards
+    let decision = expect_decision(game.step().unwrap());
+    assert_eq!(decision.player_index, 0);
+    assert_eq!(decision.on_behalf_of, Some(1));
+    assert_eq!(decision.options.len(), 3);
+}
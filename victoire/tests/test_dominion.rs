@@ -0,0 +1,335 @@
+//! Tests for the cards in the base set
+
+mod common;
+
+use common::{cards, game_with_hand, names, play_effects, ScriptedClient};
+use victoire::callbacks::MAX_INVALID_ANSWERS;
+use victoire::cards::{base::*, dominion::*};
+use victoire::error::Error;
+use victoire::types::{Card, Game};
+
+#[test]
+fn test_artisan() {
+    let mut game = game_with_hand(vec![Box::new(Estate)]);
+
+    // Gold costs too much, so the player is asked again
+    let client = ScriptedClient::default()
+        .supply(Gold)
+        .supply(Silver)
+        .hand(vec![0]);
+    play_effects(&mut game, 0, &Artisan, &client).unwrap();
+
+    assert_eq!(names(&game.players[0].hand), ["Silver"]);
+    assert_eq!(game.players[0].deck[0].name(), "Estate");
+}
+
+#[test]
+fn test_bandit() {
+    let mut game = game_with_hand(vec![]);
+    game.players[1]
+        .deck
+        .extend(cards(vec![Box::new(Silver), Box::new(Gold)]));
+    game.players[1].deck.rotate_right(2);
+
+    let client = ScriptedClient::default().selection(vec![1]);
+    play_effects(&mut game, 0, &Bandit, &client).unwrap();
+
+    assert_eq!(game.players[0].discard[0].name(), "Gold");
+    assert_eq!(names(&game.trash), ["Gold"]);
+    assert_eq!(names(&game.players[1].discard), ["Silver"]);
+}
+
+#[test]
+fn test_bureaucrat() {
+    let mut game = game_with_hand(vec![]);
+    game.players[1].hand = cards(vec![Box::new(Copper), Box::new(Estate), Box::new(Duchy)]);
+
+    let client = ScriptedClient::default().hand(vec![2]);
+    play_effects(&mut game, 0, &Bureaucrat, &client).unwrap();
+
+    assert_eq!(game.players[0].deck[0].name(), "Silver");
+    assert_eq!(game.players[1].deck[0].name(), "Duchy");
+    assert_eq!(names(&game.players[1].hand), ["Copper", "Estate"]);
+
+    // Without any victory cards, nothing happens
+    game.players[1].hand = cards(vec![Box::new(Copper)]);
+    play_effects(&mut game, 0, &Bureaucrat, &client).unwrap();
+    assert_eq!(game.players[1].hand.len(), 1);
+}
+
+#[test]
+fn test_bureaucrat_gains() {
+    let mut game = game_with_hand(vec![]);
+
+    // An empty Silver pile just means no Silver is gained
+    game.supply.get_mut("Silver").unwrap().count = 0;
+    play_effects(&mut game, 0, &Bureaucrat, &ScriptedClient::default()).unwrap();
+    assert!(game.players[0].deck.iter().all(|c| c.name() != "Silver"));
+
+    // Other problems with the gain aren't hidden
+    game.supply.as_mut().remove("Silver");
+    assert!(matches!(
+        play_effects(&mut game, 0, &Bureaucrat, &ScriptedClient::default()),
+        Err(Error::UnknownCard { .. })
+    ));
+}
+
+#[test]
+fn test_cellar() {
+    let mut game = game_with_hand(vec![Box::new(Estate), Box::new(Estate), Box::new(Copper)]);
+
+    let client = ScriptedClient::default().hand(vec![0, 1]);
+    play_effects(&mut game, 0, &Cellar, &client).unwrap();
+
+    let player = &game.players[0];
+    assert_eq!(player.resources.actions, 2);
+    assert_eq!(player.hand.len(), 3);
+    assert_eq!(names(&player.discard), ["Estate", "Estate"]);
+}
+
+#[test]
+fn test_cellar_invalid_answers() {
+    let mut game = game_with_hand(vec![Box::new(Estate), Box::new(Copper)]);
+
+    // Repeated cards and cards that aren't there are asked again
+    let client = ScriptedClient::default()
+        .hand(vec![0, 0])
+        .hand(vec![2])
+        .hand(vec![0]);
+    play_effects(&mut game, 0, &Cellar, &client).unwrap();
+
+    let player = &game.players[0];
+    assert_eq!(player.hand.len(), 2);
+    assert_eq!(names(&player.discard), ["Estate"]);
+}
+
+#[test]
+fn test_chapel() {
+    let mut game = game_with_hand(vec![Box::new(Copper); 5]);
+
+    // Trashing more than 4 cards is asked again
+    let client = ScriptedClient::default()
+        .hand(vec![0, 1, 2, 3, 4])
+        .hand(vec![0, 1]);
+    play_effects(&mut game, 0, &Chapel, &client).unwrap();
+
+    assert_eq!(game.players[0].hand.len(), 3);
+    assert_eq!(names(&game.trash), ["Copper", "Copper"]);
+}
+
+#[test]
+fn test_gardens() {
+    let mut game = Game::default();
+    let player = &mut game.players[0];
+    assert_eq!(Gardens.victory_points(player), 1);
+
+    player.in_play.extend(cards(vec![Box::new(Copper); 9]));
+    assert_eq!(Gardens.victory_points(player), 1);
+    player.in_play.push_back(Box::new(Copper));
+    assert_eq!(Gardens.victory_points(player), 2);
+}
+
+#[test]
+fn test_harbinger() {
+    let mut game = game_with_hand(vec![]);
+    game.players[0].discard = cards(vec![Box::new(Estate), Box::new(Gold)]);
+
+    // Choosing a card that isn't there is asked again
+    let client = ScriptedClient::default().discard(vec![5]).discard(vec![1]);
+    play_effects(&mut game, 0, &Harbinger, &client).unwrap();
+
+    let player = &game.players[0];
+    assert_eq!(player.resources.actions, 2);
+    assert_eq!(player.hand.len(), 1);
+    assert_eq!(player.deck[0].name(), "Gold");
+    assert_eq!(names(&player.discard), ["Estate"]);
+}
+
+#[test]
+fn test_merchant() {
+    let mut game = game_with_hand(vec![Box::new(Silver), Box::new(Silver)]);
+    game.players[0].deck.clear();
+
+    let client = ScriptedClient::default();
+    play_effects(&mut game, 0, &Merchant, &client).unwrap();
+    game.play_all_treasures(0, &client).unwrap();
+
+    // Only the first Silver gets the bonus
+    let player = &game.players[0];
+    assert_eq!(player.resources.actions, 2);
+    assert_eq!(player.resources.coins, 5);
+}
+
+#[test]
+fn test_militia() {
+    let mut game = game_with_hand(vec![]);
+    assert_eq!(game.players[1].hand.len(), 5);
+
+    let client = ScriptedClient::default().hand(vec![0, 4]);
+    play_effects(&mut game, 0, &Militia, &client).unwrap();
+
+    assert_eq!(game.players[0].resources.temp_coins, 2);
+    assert_eq!(game.players[1].hand.len(), 3);
+    assert_eq!(game.players[1].discard.len(), 2);
+
+    // Players with 3 or fewer cards in hand aren't affected
+    play_effects(&mut game, 0, &Militia, &client).unwrap();
+    assert_eq!(game.players[1].hand.len(), 3);
+}
+
+#[test]
+fn test_mine() {
+    let mut game = game_with_hand(vec![Box::new(Estate), Box::new(Copper)]);
+
+    // Choosing a card that isn't a treasure does nothing
+    let client = ScriptedClient::default().hand(vec![0]);
+    play_effects(&mut game, 0, &Mine, &client).unwrap();
+    assert_eq!(game.players[0].hand.len(), 2);
+
+    let client = ScriptedClient::default()
+        .hand(vec![1])
+        .supply(Gold)
+        .supply(Silver);
+    play_effects(&mut game, 0, &Mine, &client).unwrap();
+    assert_eq!(names(&game.players[0].hand), ["Estate", "Silver"]);
+    assert_eq!(names(&game.trash), ["Copper"]);
+}
+
+#[test]
+fn test_moneylender() {
+    let mut game = game_with_hand(vec![Box::new(Estate), Box::new(Copper)]);
+
+    play_effects(&mut game, 0, &Moneylender, &ScriptedClient::default()).unwrap();
+    assert_eq!(game.players[0].hand.len(), 2);
+
+    let client = ScriptedClient::default().yes_or_no(true);
+    play_effects(&mut game, 0, &Moneylender, &client).unwrap();
+    assert_eq!(names(&game.players[0].hand), ["Estate"]);
+    assert_eq!(game.players[0].resources.temp_coins, 3);
+    assert_eq!(names(&game.trash), ["Copper"]);
+}
+
+#[test]
+fn test_poacher() {
+    let mut game = game_with_hand(vec![Box::new(Estate), Box::new(Estate), Box::new(Copper)]);
+    game.supply.get_mut("Village").unwrap().count = 0;
+    game.supply.get_mut("Smithy").unwrap().count = 0;
+
+    let client = ScriptedClient::default().hand(vec![0, 1]);
+    play_effects(&mut game, 0, &Poacher, &client).unwrap();
+
+    let player = &game.players[0];
+    assert_eq!(player.resources.actions, 2);
+    assert_eq!(player.resources.temp_coins, 1);
+    assert_eq!(player.hand.len(), 2);
+    assert_eq!(names(&player.discard), ["Estate", "Estate"]);
+}
+
+#[test]
+fn test_remodel() {
+    let mut game = game_with_hand(vec![Box::new(Copper), Box::new(Estate)]);
+
+    let client = ScriptedClient::default()
+        .hand(vec![1])
+        .supply(Gold)
+        .supply(Smithy);
+    play_effects(&mut game, 0, &Remodel, &client).unwrap();
+
+    assert_eq!(names(&game.players[0].hand), ["Copper"]);
+    assert_eq!(names(&game.trash), ["Estate"]);
+    assert_eq!(names(&game.players[0].discard), ["Smithy"]);
+
+    // The player is only asked so many times
+    let client = (0..MAX_INVALID_ANSWERS)
+        .fold(ScriptedClient::default().hand(vec![0]), |client, _| {
+            client.supply(Gold)
+        });
+    let result = play_effects(&mut game, 0, &Remodel, &client);
+    assert!(matches!(result, Err(Error::TooManyInvalidAnswers)));
+}
+
+#[test]
+fn test_sentry() {
+    let mut game = game_with_hand(vec![]);
+    game.players[0].deck = cards(vec![Box::new(Copper), Box::new(Silver), Box::new(Gold)]);
+
+    // Keep both, putting Gold back on top
+    let client = ScriptedClient::default()
+        .selection(vec![])
+        .selection(vec![])
+        .selection(vec![1]);
+    play_effects(&mut game, 0, &Sentry, &client).unwrap();
+
+    let player = &game.players[0];
+    assert_eq!(player.resources.actions, 2);
+    assert_eq!(names(&player.hand), ["Copper"]);
+    assert_eq!(names(&player.deck), ["Gold", "Silver"]);
+
+    // Trash one and discard the other
+    game.players[0].deck.push_front(Box::new(Copper));
+    let client = ScriptedClient::default()
+        .selection(vec![0])
+        .selection(vec![0]);
+    play_effects(&mut game, 0, &Sentry, &client).unwrap();
+
+    assert!(game.players[0].deck.is_empty());
+    assert_eq!(names(&game.trash), ["Gold"]);
+    assert_eq!(names(&game.players[0].discard), ["Silver"]);
+}
+
+#[test]
+fn test_throne_room() {
+    let mut game = game_with_hand(vec![Box::new(Copper), Box::new(Festival)]);
+
+    // Choosing a card that isn't an action is asked again
+    let client = ScriptedClient::default().hand(vec![0]).hand(vec![1]);
+    play_effects(&mut game, 0, &ThroneRoom, &client).unwrap();
+
+    let player = &game.players[0];
+    assert_eq!(player.resources.actions, 5);
+    assert_eq!(player.resources.temp_coins, 4);
+    assert_eq!(names(&player.in_play), ["Festival"]);
+
+    // The player is only asked so many times
+    let mut game = game_with_hand(vec![Box::new(Copper)]);
+    let client =
+        (0..MAX_INVALID_ANSWERS).fold(ScriptedClient::default(), |client, _| client.hand(vec![0]));
+    let result = play_effects(&mut game, 0, &ThroneRoom, &client);
+    assert!(matches!(result, Err(Error::TooManyInvalidAnswers)));
+}
+
+#[test]
+fn test_vassal() {
+    let mut game = game_with_hand(vec![]);
+    game.players[0].deck.push_front(Box::new(Smithy));
+
+    let client = ScriptedClient::default().yes_or_no(true);
+    play_effects(&mut game, 0, &Vassal, &client).unwrap();
+
+    let player = &game.players[0];
+    assert_eq!(player.resources.temp_coins, 2);
+    assert_eq!(player.resources.actions, 1);
+    assert_eq!(player.hand.len(), 3);
+    assert_eq!(names(&player.in_play), ["Smithy"]);
+
+    // Cards that aren't actions are just discarded
+    game.players[0].deck.push_front(Box::new(Gold));
+    play_effects(&mut game, 0, &Vassal, &client).unwrap();
+    assert_eq!(game.players[0].discard.back().unwrap().name(), "Gold");
+}
+
+#[test]
+fn test_workshop() {
+    let mut game = game_with_hand(vec![]);
+
+    let client = ScriptedClient::default().supply(Gold).supply(Smithy);
+    play_effects(&mut game, 0, &Workshop, &client).unwrap();
+
+    assert_eq!(names(&game.players[0].discard), ["Smithy"]);
+
+    // The player is only asked so many times
+    let client =
+        (0..MAX_INVALID_ANSWERS).fold(ScriptedClient::default(), |client, _| client.supply(Gold));
+    let result = play_effects(&mut game, 0, &Workshop, &client);
+    assert!(matches!(result, Err(Error::TooManyInvalidAnswers)));
+}
@@ -0,0 +1,145 @@
+//! Tests for the cards removed in the 2nd edition of the base set
+
+mod common;
+
+use common::{cards, game_with_hand, names, play_effects, ScriptedClient};
+use victoire::cards::{base::*, dominion::*, dominion_first_edition::*, Edition};
+use victoire::types::{CardId, Identified};
+
+#[test]
+fn test_editions() {
+    assert_eq!(Edition::First.kingdom_cards().len(), 25);
+    assert_eq!(Edition::Second.kingdom_cards().len(), 26);
+
+    assert!(Edition::First.contains(&Woodcutter));
+    assert!(!Edition::Second.contains(&Woodcutter));
+    assert!(Edition::Second.contains(&Sentry));
+    assert!(!Edition::First.contains(&Sentry));
+    assert!(Edition::First.contains(&Village) && Edition::Second.contains(&Village));
+}
+
+#[test]
+fn test_adventurer() {
+    let mut game = game_with_hand(vec![]);
+    game.players[0].deck = cards(vec![
+        Box::new(Estate),
+        Box::new(Copper),
+        Box::new(Estate),
+        Box::new(Gold),
+        Box::new(Silver),
+    ]);
+
+    play_effects(&mut game, 0, &Adventurer, &ScriptedClient::default()).unwrap();
+
+    let player = &game.players[0];
+    assert_eq!(names(&player.hand), ["Copper", "Gold"]);
+    assert_eq!(names(&player.discard), ["Estate", "Estate"]);
+    assert_eq!(names(&player.deck), ["Silver"]);
+}
+
+#[test]
+fn test_chancellor() {
+    let mut game = game_with_hand(vec![]);
+
+    let client = ScriptedClient::default().yes_or_no(true);
+    play_effects(&mut game, 0, &Chancellor, &client).unwrap();
+
+    let player = &game.players[0];
+    assert_eq!(player.resources.temp_coins, 2);
+    assert!(player.deck.is_empty());
+    assert_eq!(player.discard.len(), 5);
+}
+
+#[test]
+fn test_feast() {
+    let mut game = game_with_hand(vec![]);
+    game.players[0].hand.push_back(Box::new(Feast));
+
+    // Gold costs too much, so the player is asked again
+    let client = ScriptedClient::default().supply(Gold).supply(Market);
+    game.play_action_from_hand(0, 0, &client).unwrap();
+
+    let player = &game.players[0];
+    assert!(player.in_play.is_empty());
+    assert_eq!(names(&player.discard), ["Market"]);
+    assert_eq!(names(&game.trash), ["Feast"]);
+
+    // With Throne Room, Feast is only trashed once but gains twice
+    let mut game = game_with_hand(vec![]);
+    game.players[0].hand = cards(vec![Box::new(ThroneRoom), Box::new(Feast)]);
+    let client = ScriptedClient::default()
+        .hand(vec![0])
+        .supply(Market)
+        .supply(Smithy);
+    game.play_action_from_hand(0, 0, &client).unwrap();
+
+    let player = &game.players[0];
+    assert_eq!(names(&player.in_play), ["Throne Room"]);
+    assert_eq!(names(&player.discard), ["Market", "Smithy"]);
+    assert_eq!(names(&game.trash), ["Feast"]);
+
+    // Feast only trashes its own copy, not another Feast already in play
+    let mut game = game_with_hand(vec![]);
+    let (mut in_play, mut played) = (Feast, Feast);
+    in_play.set_id(CardId(100));
+    played.set_id(CardId(101));
+    game.players[0].in_play.push_back(Box::new(in_play));
+    game.players[0].hand = cards(vec![Box::new(ThroneRoom), Box::new(played)]);
+    let client = ScriptedClient::default()
+        .hand(vec![0])
+        .supply(Market)
+        .supply(Smithy);
+    game.play_action_from_hand(0, 0, &client).unwrap();
+
+    let player = &game.players[0];
+    assert_eq!(names(&player.in_play), ["Feast", "Throne Room"]);
+    assert_eq!(player.in_play[0].id(), Some(CardId(100)));
+    assert_eq!(game.trash.len(), 1);
+    assert_eq!(game.trash[0].id(), Some(CardId(101)));
+}
+
+#[test]
+fn test_spy() {
+    let mut game = game_with_hand(vec![]);
+    game.players[0].deck = cards(vec![Box::new(Copper), Box::new(Estate)]);
+    game.players[1].deck.push_front(Box::new(Gold));
+
+    // Discard our own Estate, put the opponent's Gold back
+    let client = ScriptedClient::default().yes_or_no(true).yes_or_no(false);
+    play_effects(&mut game, 0, &Spy, &client).unwrap();
+
+    assert_eq!(game.players[0].resources.actions, 2);
+    assert_eq!(names(&game.players[0].hand), ["Copper"]);
+    assert_eq!(names(&game.players[0].discard), ["Estate"]);
+    assert_eq!(game.players[1].deck[0].name(), "Gold");
+    assert!(game.players[1].discard.is_empty());
+}
+
+#[test]
+fn test_thief() {
+    let mut game = game_with_hand(vec![]);
+    game.players[1].deck = cards(vec![Box::new(Silver), Box::new(Gold)]);
+
+    let client = ScriptedClient::default().selection(vec![1]).yes_or_no(true);
+    play_effects(&mut game, 0, &Thief, &client).unwrap();
+
+    assert_eq!(names(&game.players[0].discard), ["Gold"]);
+    assert_eq!(names(&game.players[1].discard), ["Silver"]);
+    assert!(game.trash.is_empty());
+
+    // Treasures that aren't gained stay in the trash
+    game.players[1].deck = cards(vec![Box::new(Estate), Box::new(Copper)]);
+    play_effects(&mut game, 0, &Thief, &ScriptedClient::default()).unwrap();
+    assert_eq!(names(&game.trash), ["Copper"]);
+    assert_eq!(names(&game.players[1].discard), ["Silver", "Estate"]);
+}
+
+#[test]
+fn test_woodcutter() {
+    let mut game = game_with_hand(vec![]);
+    play_effects(&mut game, 0, &Woodcutter, &ScriptedClient::default()).unwrap();
+
+    let player = &game.players[0];
+    assert_eq!(player.resources.buys, 2);
+    assert_eq!(player.resources.temp_coins, 2);
+}
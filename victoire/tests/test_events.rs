@@ -0,0 +1,191 @@
+//! Tests for the events reported to observers
+
+mod common;
+
+use std::sync::Arc;
+
+use common::{
+    cards, game_with_hand, names, play_effects, BigMoneyClient, RecordingObserver, ScriptedClient,
+};
+use victoire::cards::{base::*, dominion::Sentry};
+use victoire::events::GameEvent;
+use victoire::types::{Game, Mat, Phase, Selector, Zone};
+
+#[test]
+fn test_events_turn() {
+    let mut game = Game::default_with_seed(7);
+    let observer = Arc::new(RecordingObserver::default());
+    game.add_observer(observer.clone());
+
+    let treasures = game.players[0]
+        .hand
+        .iter()
+        .filter(|card| card.is_treasure())
+        .count();
+
+    game.turn(0, &BigMoneyClient::default()).unwrap();
+
+    let events = observer.events();
+    assert_eq!(
+        events[0],
+        GameEvent::TurnStarted {
+            player_index: 0,
+            turn: 1,
+            extra: false,
+            controller: None,
+        }
+    );
+    assert_eq!(
+        events[1],
+        GameEvent::PhaseChanged {
+            player_index: 0,
+            phase: Phase::ActionPhase
+        }
+    );
+
+    let played = events
+        .iter()
+        .filter(|event| matches!(event, GameEvent::CardPlayed { .. }))
+        .count();
+    assert_eq!(played, treasures);
+
+    assert!(events.contains(&GameEvent::CardsDrawn {
+        player_index: 0,
+        count: 5
+    }));
+    assert_eq!(
+        events.last(),
+        Some(&GameEvent::PhaseChanged {
+            player_index: 0,
+            phase: Phase::OutOfTurn
+        })
+    );
+}
+
+#[test]
+fn test_events_buy_gain_and_trash() {
+    let mut game = Game::default();
+    let observer = Arc::new(RecordingObserver::default());
+    game.add_observer(observer.clone());
+
+    game.players[0].phase = Phase::BuyPhase;
+    game.players[0].resources.buys = 1;
+    game.players[0].resources.coins_remaining = 3;
+    let client = BigMoneyClient::default();
+    game.buy_card(0, Box::new(Silver), &client).unwrap();
+    game.trash_from_hand(0, vec![0]).unwrap();
+    game.resolve(&client).unwrap();
+
+    let events = observer.events();
+    let bought = events
+        .iter()
+        .position(
+            |event| matches!(event, GameEvent::CardBought { card, .. } if card.name() == "Silver"),
+        )
+        .unwrap();
+    let gained = events
+        .iter()
+        .position(
+            |event| matches!(event, GameEvent::CardGained { card, .. } if card.name() == "Silver"),
+        )
+        .unwrap();
+    assert!(bought < gained);
+    assert!(matches!(
+        events.last(),
+        Some(GameEvent::CardTrashed {
+            player_index: 0,
+            ..
+        })
+    ));
+    assert_eq!(game.trash.len(), 1);
+}
+
+#[test]
+fn test_events_shuffle() {
+    let mut game = Game::default();
+    let observer = Arc::new(RecordingObserver::default());
+    game.add_observer(observer.clone());
+
+    // 5 cards left in the deck, so the sixth draw needs a shuffle
+    let player = &mut game.players[0];
+    let mut hand = std::mem::take(&mut player.hand);
+    player.discard.append(&mut hand);
+    game.draw_cards(0, 6).unwrap();
+
+    let events = observer.events();
+    assert_eq!(events[0], GameEvent::Shuffled { player_index: 0 });
+    assert_eq!(game.players[0].hand.len(), 6);
+}
+
+#[test]
+fn test_events_card_moved() {
+    let mut game = Game::default();
+    let observer = Arc::new(RecordingObserver::default());
+    game.add_observer(observer.clone());
+
+    // Drawing is hidden from everyone else, but discarding isn't
+    game.draw_cards(0, 1).unwrap();
+    let moved = game
+        .move_card(0, Zone::Hand, Zone::Discard, Selector::Top(1))
+        .unwrap();
+    game.resolve(&BigMoneyClient::default()).unwrap();
+
+    let events = observer.events();
+    assert_eq!(
+        events[0],
+        GameEvent::CardMoved {
+            player_index: 0,
+            from: Zone::Deck,
+            to: Zone::Hand,
+            to_player: 0,
+            card: None
+        }
+    );
+    assert!(events.contains(&GameEvent::CardMoved {
+        player_index: 0,
+        from: Zone::Hand,
+        to: Zone::Discard,
+        to_player: 0,
+        card: Some(moved[0].clone())
+    }));
+    assert_eq!(game.players[0].hand.len(), 5);
+}
+
+#[test]
+fn test_events_set_aside_is_private() {
+    let mut game = game_with_hand(vec![]);
+    game.players[0].deck = cards(vec![Box::new(Copper), Box::new(Silver), Box::new(Gold)]);
+    let observer = Arc::new(RecordingObserver::default());
+    game.add_observer(observer.clone());
+
+    // Sentry looks at Silver and Gold and puts them back, so nobody else
+    // learns what they are
+    let client = ScriptedClient::default()
+        .selection(vec![])
+        .selection(vec![])
+        .selection(vec![]);
+    play_effects(&mut game, 0, &Sentry, &client).unwrap();
+    assert_eq!(names(&game.players[0].deck), ["Silver", "Gold"]);
+
+    let seen: Vec<_> = observer
+        .events()
+        .into_iter()
+        .flat_map(|event| match event {
+            GameEvent::CardMoved {
+                card: Some(card), ..
+            } => vec![card],
+            GameEvent::CardsRevealed { cards, .. } => cards,
+            _ => vec![],
+        })
+        .collect();
+    assert!(seen
+        .iter()
+        .all(|card| card.name() != "Silver" && card.name() != "Gold"));
+    assert!(observer.events().contains(&GameEvent::CardMoved {
+        player_index: 0,
+        from: Zone::Deck,
+        to: Mat::SetAside.into(),
+        to_player: 0,
+        card: None
+    }));
+}
@@ -0,0 +1,783 @@
+//! Tests for game-related methods and structs
+
+mod common;
+
+use std::collections::HashSet;
+
+use common::{names, play_effects, BigMoneyClient, ScriptedClient};
+use victoire::callbacks::{TreasureChoice, MAX_INVALID_ANSWERS};
+use victoire::cards::{base::*, dominion::*};
+use victoire::commands::Command;
+use victoire::error::Error;
+use victoire::types::card::Cost;
+use victoire::types::{
+    Card, Game, Lifetime, Mat, Modifier, ModifierKind, Phase, Player, Selector, TokenKind, Zone,
+};
+
+#[test]
+fn test_game_over_conditions() {
+    let mut game = Game::default();
+    assert!(!game.is_game_over());
+
+    game.supply.get_mut(Province.name()).unwrap().count = 0;
+    assert!(game.is_game_over());
+
+    let mut game = Game::default();
+    for name in ["Cellar", "Market"] {
+        game.supply.get_mut(name).unwrap().count = 0;
+    }
+    assert!(!game.is_game_over());
+
+    game.supply.get_mut("Village").unwrap().count = 0;
+    assert!(game.is_game_over());
+}
+
+#[test]
+fn test_game_result_tiebreaker() {
+    let mut game = Game::default();
+
+    // Both players start with 3 Estates
+    game.players[0].turns_taken = 10;
+    game.players[1].turns_taken = 9;
+    let result = game.result();
+    assert_eq!(result.scores[0].victory_points, 3);
+    assert_eq!(result.winners, vec![1]);
+
+    game.players[1].turns_taken = 10;
+    assert_eq!(game.result().winners, vec![0, 1]);
+
+    game.players[0].discard.push_back(Box::new(Duchy));
+    let result = game.result();
+    assert_eq!(result.scores[0].victory_points, 6);
+    assert_eq!(result.winners, vec![0]);
+}
+
+#[test]
+fn test_game_play_to_completion() {
+    let mut game = Game::default();
+    let result = game.play_to_completion(&BigMoneyClient::default()).unwrap();
+
+    assert!(game.is_game_over());
+    assert!(!result.winners.is_empty());
+    for score in &result.scores {
+        let player = &game.players[score.player_index];
+        assert_eq!(score.victory_points, player.victory_points());
+        assert_eq!(score.turns_taken, player.turns_taken);
+    }
+}
+
+#[test]
+fn test_game_seeded_games_are_identical() {
+    let mut first = Game::default_with_seed(1234);
+    let mut second = Game::default_with_seed(1234);
+
+    first
+        .play_to_completion(&BigMoneyClient::default())
+        .unwrap();
+    second
+        .play_to_completion(&BigMoneyClient::default())
+        .unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&first).unwrap(),
+        serde_json::to_value(&second).unwrap()
+    );
+}
+
+#[test]
+fn test_game_seed_survives_serialization() {
+    let mut game = Game::default_with_seed(99);
+    let json = serde_json::to_string(&game).unwrap();
+    let mut restored: Game = serde_json::from_str(&json).unwrap();
+
+    game.turn(0, &BigMoneyClient::default()).unwrap();
+    restored.turn(0, &BigMoneyClient::default()).unwrap();
+
+    let hand = |game: &Game| {
+        game.players[0]
+            .hand
+            .iter()
+            .map(|card| card.name().to_owned())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(hand(&game), hand(&restored));
+}
+
+#[test]
+fn test_game_partial_game_hides_information() {
+    let mut game = Game::default();
+    game.players[1].discard.push_back(Box::new(Gold));
+
+    let view = game.partial_game(0).unwrap();
+    assert_eq!(view.player_index, 0);
+    assert_eq!(view.hand.len(), 5);
+    assert_eq!(view.players.len(), 2);
+
+    let opponent = &view.players[1];
+    assert_eq!(opponent.hand_size, 5);
+    assert_eq!(opponent.deck_size, 5);
+    assert_eq!(opponent.discard_size, 1);
+    assert_eq!(opponent.discard_top.as_ref().unwrap().name(), "Gold");
+
+    // Nothing in the view should reveal which cards are in anyone's deck,
+    // or what's in the other player's hand
+    let json = serde_json::to_value(&view).unwrap();
+    assert!(json.get("deck").is_none());
+    assert!(json["players"][1].get("hand").is_none());
+    assert!(json["players"][1].get("deck").is_none());
+}
+
+#[test]
+fn test_game_mats() {
+    let mut game = Game::default();
+    let player = &mut game.players[0];
+    player.hand.clear();
+    player
+        .hand
+        .extend([Box::new(Gold) as Box<dyn Card>, Box::new(Duchy)]);
+    let points = player.victory_points();
+
+    let client = ScriptedClient::default();
+    let island = Zone::Mat(Mat::Island);
+    game.move_card(0, Zone::Hand, island, Selector::Index(1))
+        .unwrap();
+    game.resolve(&client).unwrap();
+    game.move_card(0, Zone::Hand, Mat::Haven.into(), Selector::Index(0))
+        .unwrap();
+    game.resolve(&client).unwrap();
+    let player = &game.players[0];
+    assert!(player.hand.is_empty());
+    assert_eq!(player.mat(Mat::Haven)[0].name(), "Gold");
+    assert!(player.mat(Mat::Tavern).is_empty());
+    assert!(matches!(
+        game.move_card(0, Mat::Tavern.into(), Zone::Hand, Selector::Index(0)),
+        Err(Error::InvalidZoneIndex { .. })
+    ));
+    assert!(!game.players[0].mats.contains_key(&Mat::Tavern));
+
+    // Cards on mats still count towards the player's score
+    assert_eq!(game.players[0].victory_points(), points);
+
+    // Haven is face down, so other players only know how many cards are on
+    // it
+    let view = &game.partial_game(1).unwrap().players[0];
+    assert_eq!(view.mats[&Mat::Island][0].name(), "Duchy");
+    assert!(!view.mats.contains_key(&Mat::Haven));
+    assert_eq!(view.mat_sizes[&Mat::Haven], 1);
+    assert_eq!(game.partial_game(0).unwrap().mats.len(), 2);
+
+    let json = serde_json::to_string(&game).unwrap();
+    let restored: Game = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.players[0].mat(Mat::Island)[0].name(), "Duchy");
+
+    let cards = game
+        .move_card(0, Mat::Haven.into(), Zone::Hand, Selector::All)
+        .unwrap();
+    game.resolve(&ScriptedClient::default()).unwrap();
+    assert_eq!(cards.len(), 1);
+    assert!(game.players[0].mat(Mat::Haven).is_empty());
+}
+
+#[test]
+fn test_game_move_card() {
+    let mut game = Game::default();
+    let client = ScriptedClient::default();
+    let player = &mut game.players[0];
+    player.deck.clear();
+    player.discard = [Box::new(Silver) as Box<dyn Card>, Box::new(Gold)]
+        .into_iter()
+        .collect();
+
+    // Taking from an empty deck shuffles the discard pile into it first
+    let cards = game
+        .move_card(0, Zone::Deck, Zone::InPlay, Selector::Top(2))
+        .unwrap();
+    game.resolve(&client).unwrap();
+    assert_eq!(cards.len(), 2);
+    assert!(game.players[0].discard.is_empty());
+
+    game.move_card(0, Zone::InPlay, Zone::Trash, Selector::Card(Box::new(Gold)))
+        .unwrap();
+    game.resolve(&client).unwrap();
+    assert_eq!(game.trash[0].name(), "Gold");
+    assert!(matches!(
+        game.move_card(0, Zone::InPlay, Zone::Trash, Selector::Card(Box::new(Gold))),
+        Err(Error::CardNotFound {
+            zone: Zone::InPlay,
+            ..
+        })
+    ));
+
+    // Moving a card out of the supply gains it, and it can be returned
+    game.move_card(0, Zone::Supply, Zone::Deck, Selector::Card(Box::new(Duchy)))
+        .unwrap();
+    game.resolve(&client).unwrap();
+    assert_eq!(game.players[0].deck[0].name(), "Duchy");
+    assert_eq!(game.players[0].state.gained.len(), 1);
+    game.move_card(0, Zone::Deck, Zone::Supply, Selector::Top(1))
+        .unwrap();
+    game.resolve(&client).unwrap();
+    assert_eq!(game.supply.get("Duchy").unwrap().count, 8);
+
+    // Cards without a supply pile can't be returned to it
+    game.players[0].hand.push_back(Box::new(Witch));
+    assert!(matches!(
+        game.move_card(0, Zone::Hand, Zone::Supply, Selector::Top(1)),
+        Err(Error::UnknownCard { .. })
+    ));
+    assert_eq!(game.players[0].hand.len(), 6);
+}
+
+#[test]
+fn test_game_modifiers() {
+    let mut game = Game::default();
+    let client = ScriptedClient::default();
+    game.add_modifier(Modifier::new(
+        0,
+        ModifierKind::CostReduction {
+            coins: 1,
+            card_type: None,
+        },
+        Lifetime::UntilNextTurn,
+    ));
+    game.add_modifier(Modifier::new(
+        1,
+        ModifierKind::CostReduction {
+            coins: 2,
+            card_type: None,
+        },
+        Lifetime::RestOfGame,
+    ));
+    assert_eq!(game.effective_cost(&Gold, 0).coins, 3);
+
+    // Cost reductions apply to everyone, but only the owner's next turn
+    // ends theirs
+    game.start_turn(1, &client).unwrap();
+    game.end_turn(1, &client).unwrap();
+    assert_eq!(game.effective_cost(&Gold, 0).coins, 3);
+    game.start_turn(0, &client).unwrap();
+    assert_eq!(game.effective_cost(&Gold, 0).coins, 4);
+    assert_eq!(game.partial_game(1).unwrap().modifiers.len(), 1);
+
+    // Some reductions only apply to the player who added them
+    game.add_modifier(Modifier::new(
+        1,
+        ModifierKind::OwnCostReduction(1),
+        Lifetime::ThisTurn,
+    ));
+    assert_eq!(game.effective_cost(&Gold, 0).coins, 4);
+    assert_eq!(game.effective_cost(&Gold, 1).coins, 3);
+
+    // The -1 Card token is used up by the next draw
+    game.add_modifier(Modifier::new(
+        0,
+        ModifierKind::MinusCard,
+        Lifetime::RestOfGame,
+    ));
+    game.draw_cards(0, 2).unwrap();
+    assert_eq!(game.players[0].hand.len(), 6);
+    game.draw_cards(0, 2).unwrap();
+    assert_eq!(game.players[0].hand.len(), 8);
+    assert_eq!(game.modifiers_for(0).count(), 0);
+}
+
+#[test]
+fn test_game_card_ids() {
+    let mut game = Game::default();
+    let client = ScriptedClient::default();
+    let ids: HashSet<_> = game
+        .players
+        .iter()
+        .flat_map(|player| player.all_cards())
+        .map(|card| card.id().unwrap())
+        .collect();
+    assert_eq!(ids.len(), 20);
+
+    game.gain(0, Box::new(Silver)).unwrap();
+    game.resolve(&client).unwrap();
+    let silver = game.players[0].discard[0].id().unwrap();
+    assert!(!ids.contains(&silver));
+
+    // Selecting a card with an id moves that exact copy
+    let card = game.players[0].hand[2].clone();
+    game.move_card(0, Zone::Hand, Zone::Trash, Selector::Card(card.clone()))
+        .unwrap();
+    game.resolve(&client).unwrap();
+    assert_eq!(game.trash[0].id(), card.id());
+    assert!(game.players[0].hand.iter().all(|c| c.id() != card.id()));
+
+    let hand = game.partial_game(0).unwrap().hand;
+    assert_eq!(hand[0].id(), game.players[0].hand[0].id());
+
+    let json = serde_json::to_string(&game).unwrap();
+    let mut restored: Game = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.trash[0].id(), card.id());
+    restored.gain(0, Box::new(Silver)).unwrap();
+    restored.resolve(&client).unwrap();
+    assert!(restored.players[0].discard[1].id().unwrap() > silver);
+}
+
+mod gain_counter {
+    use victoire::cards::prelude::*;
+
+    declare_card!(GainCounter);
+    #[typetag::serde]
+    impl Card for GainCounter {
+        name!("Gain Counter");
+        card_cost!(0);
+        types!(vec![Action]);
+
+        fn effects_on_gain(&self, game: &mut Game, player_index: usize) -> Result {
+            game.player_mut(player_index)?.tokens.victory_points += 1;
+            Ok(())
+        }
+    }
+}
+
+#[test]
+fn test_game_gain_effects_happen_once() {
+    use gain_counter::GainCounter;
+
+    let mut game = Game::default();
+    game.supply.insert(GainCounter, 10);
+    let client = ScriptedClient::default();
+
+    game.players[0].phase = Phase::BuyPhase;
+    game.players[0].resources.buys = 1;
+    game.buy_card(0, Box::new(GainCounter), &client).unwrap();
+    assert_eq!(game.players[0].tokens.victory_points, 1);
+
+    game.gain_to(0, Box::new(GainCounter), Zone::Hand).unwrap();
+    game.resolve(&client).unwrap();
+    assert_eq!(game.players[0].tokens.victory_points, 2);
+    assert_eq!(game.players[0].hand.len(), 6);
+}
+
+#[test]
+fn test_game_cost_comparisons() {
+    let potion = Cost::new(2).with_potions(1);
+    assert!(Cost::new(3) <= Cost::new(5));
+    assert!(Cost::new(5) > Cost::new(3));
+    assert!(potion > Cost::new(2));
+    assert!(potion <= Cost::new(4).with_potions(1));
+
+    // Neither is more than the other, so neither costs up to the other
+    assert_eq!(potion.partial_cmp(&Cost::new(5)), None);
+    assert!(Cost::new(0).with_debt(8) > Cost::new(0));
+    assert_eq!(potion + Cost::new(2), Cost::new(4).with_potions(1));
+    assert_eq!(potion.to_string(), "$2 1P");
+}
+
+mod costs {
+    use victoire::cards::prelude::*;
+
+    declare_card!(PotionCard);
+    #[typetag::serde]
+    impl Card for PotionCard {
+        name!("Potion Card");
+        card_cost!(2, potions = 1);
+        types!(vec![Action]);
+    }
+
+    declare_card!(DebtCard);
+    #[typetag::serde]
+    impl Card for DebtCard {
+        name!("Debt Card");
+        card_cost!(0, debt = 8);
+        types!(vec![Action]);
+    }
+}
+
+#[test]
+fn test_game_potions_and_debt() {
+    use costs::{DebtCard, PotionCard};
+
+    let mut game = Game::default();
+    game.generate_supply(vec![Box::new(PotionCard), Box::new(DebtCard)])
+        .unwrap();
+    assert_eq!(game.supply.get("Potion").unwrap().count, 16);
+    let client = ScriptedClient::default();
+
+    // A Potion is needed to buy a card that costs one
+    let player = &mut game.players[0];
+    player.phase = Phase::BuyPhase;
+    player.resources.buys = 3;
+    player.resources.coins_remaining = 2;
+    assert!(matches!(
+        game.buy_card(0, Box::new(PotionCard), &client),
+        Err(Error::InsufficientFunds)
+    ));
+    game.players[0].hand = vec![Box::new(Potion) as Box<dyn Card>].into();
+    game.play_treasure(0, 0, &client).unwrap();
+    assert!(game
+        .legal_actions(0)
+        .buys
+        .iter()
+        .any(|c| c.name() == "Potion Card"));
+    game.buy_card(0, Box::new(PotionCard), &client).unwrap();
+    assert_eq!(game.players[0].resources.potions, 0);
+
+    // Debt is taken on when the card is bought, and stops further buys
+    game.buy_card(0, Box::new(DebtCard), &client).unwrap();
+    assert_eq!(game.players[0].tokens.debt, 8);
+    assert!(matches!(
+        game.buy_card(0, Box::new(Copper), &client),
+        Err(Error::OutstandingDebt)
+    ));
+}
+
+#[test]
+fn test_game_legal_actions() {
+    let mut game = Game::default();
+    assert!(!game.legal_actions(0).is_empty());
+    assert!(game.legal_actions(1).is_empty());
+    assert!(game.legal_actions(7).is_empty());
+
+    let player = &mut game.players[0];
+    player.reset_state();
+    player.hand.clear();
+    let hand: [Box<dyn Card>; 4] = [
+        Box::new(Copper),
+        Box::new(Village),
+        Box::new(Silver),
+        Box::new(Smithy),
+    ];
+    player.hand.extend(hand);
+
+    game.set_phase(0, Phase::ActionPhase).unwrap();
+    let legal = game.legal_actions(0);
+    assert_eq!(legal.actions, vec![1, 3]);
+    assert!(legal.treasures.is_empty());
+    assert!(legal.buys.is_empty());
+    assert!(legal.end_phase);
+    assert!(game.legal_actions(1).is_empty());
+
+    game.players[0].resources.actions = 0;
+    assert!(game.legal_actions(0).actions.is_empty());
+
+    game.set_phase(0, Phase::BuyPhase).unwrap();
+    game.players[0].resources.coins_remaining = 3;
+    game.supply.get_mut(Copper.name()).unwrap().count = 0;
+    let legal = game.legal_actions(0);
+    assert_eq!(legal.treasures, vec![0, 2]);
+    assert!(legal.buys.iter().any(|card| card.name() == "Silver"));
+    assert!(legal.buys.iter().all(|card| card.cost().coins <= 3));
+    assert!(!legal.buys.iter().any(|card| card.name() == "Copper"));
+    assert!(legal
+        .buys
+        .windows(2)
+        .all(|pair| pair[0].name() <= pair[1].name()));
+
+    game.players[0].resources.buys = 0;
+    assert!(game.legal_actions(0).buys.is_empty());
+}
+
+#[test]
+fn test_game_legal_actions_before_turn_starts() {
+    let mut game = Game::default();
+    let client = BigMoneyClient::default();
+    game.next_turn();
+
+    // Before their turn starts, the player may do what they could at the
+    // start of their action phase, as apply would check it
+    let player = &mut game.players[1];
+    assert_eq!(player.phase, Phase::OutOfTurn);
+    player.resources.actions = 0;
+    player.tokens.villagers = 1;
+    player.hand.clear();
+    player
+        .hand
+        .extend([Box::new(Copper) as Box<dyn Card>, Box::new(Village)]);
+
+    let legal = game.legal_actions(1);
+    assert_eq!(legal.actions, vec![1]);
+    assert_eq!(legal.villagers, 1);
+    assert!(legal.treasures.is_empty());
+    assert!(legal.buys.is_empty());
+    assert!(legal.end_phase);
+    assert!(game.legal_actions(0).is_empty());
+
+    game.apply(1, Command::PlayAction { hand_index: 1 }, &client)
+        .unwrap();
+    assert_eq!(game.players[1].phase, Phase::ActionPhase);
+}
+
+fn curses(game: &Game, player_index: usize) -> usize {
+    game.players[player_index]
+        .discard
+        .iter()
+        .filter(|card| card.is_curse())
+        .count()
+}
+
+#[test]
+fn test_game_moat_blocks_single_attack() {
+    let mut game = Game::default();
+    game.players[1].hand.push_back(Box::new(Moat));
+
+    // Revealing Moat protects against this attack only
+    play_effects(
+        &mut game,
+        0,
+        &Witch,
+        &ScriptedClient::default().selection(vec![0]),
+    )
+    .unwrap();
+    assert_eq!(curses(&game, 1), 0);
+
+    play_effects(&mut game, 0, &Witch, &ScriptedClient::default()).unwrap();
+    assert_eq!(curses(&game, 1), 1);
+}
+
+#[test]
+fn test_game_reactions_per_target() {
+    let mut game = Game::new();
+    for _ in 0..3 {
+        game.add_player_with_deck(Player::default_deck());
+    }
+    game.generate_supply(Game::default_supply_list()).unwrap();
+    game.players[2].hand.push_back(Box::new(Moat));
+
+    // Only player 2 has a reaction to reveal, so only they are protected
+    play_effects(
+        &mut game,
+        0,
+        &Witch,
+        &ScriptedClient::default().selection(vec![0]),
+    )
+    .unwrap();
+    assert_eq!(curses(&game, 0), 0);
+    assert_eq!(curses(&game, 1), 1);
+    assert_eq!(curses(&game, 2), 0);
+}
+
+#[test]
+fn test_game_invalid_input_errors() {
+    let mut game = Game::default();
+
+    assert!(matches!(
+        game.draw_cards(5, 1),
+        Err(Error::InvalidPlayer { player_index: 5 })
+    ));
+    assert!(matches!(
+        game.partial_game(2),
+        Err(Error::InvalidPlayer { player_index: 2 })
+    ));
+    assert!(matches!(
+        game.remove_from_hand(0, 5),
+        Err(Error::InvalidHandIndex { index: 5 })
+    ));
+    assert!(matches!(
+        game.trash_from_hand(0, vec![0, 7]),
+        Err(Error::InvalidHandIndex { index: 7 })
+    ));
+    assert_eq!(game.players[0].hand.len(), 5);
+    assert!(matches!(
+        game.gain(0, Box::new(Witch)),
+        Err(Error::UnknownCard { .. })
+    ));
+
+    let player = &mut game.players[0];
+    player.deck.clear();
+    player.discard.clear();
+    assert!(matches!(game.reveal(0, 2), Err(Error::EmptyDeck)));
+}
+
+#[test]
+fn test_game_bad_choices_do_not_panic() {
+    let mut game = Game::default();
+    game.players[0].hand.push_back(Box::new(Copper));
+
+    // Indexes past the end of the hand are ignored by Throne Room
+    let client = ScriptedClient::default().hand(vec![9]);
+    play_effects(&mut game, 0, &ThroneRoom, &client).unwrap();
+    assert!(game.players[0].in_play.is_empty());
+
+    // Bandit with nothing left to reveal does nothing
+    let player = &mut game.players[1];
+    player.deck.clear();
+    player.discard.clear();
+    play_effects(&mut game, 0, &Bandit, &ScriptedClient::default()).unwrap();
+    assert!(game.trash.is_empty());
+}
+
+#[test]
+fn test_game_choose_treasures() {
+    let mut game = Game::default();
+    let player = &mut game.players[0];
+    player.reset_state();
+    player.hand.clear();
+    player.hand.extend([
+        Box::new(Estate) as Box<dyn Card>,
+        Box::new(Copper),
+        Box::new(Gold),
+    ]);
+
+    // Choosing a card that isn't a treasure is asked again
+    let client = ScriptedClient::default()
+        .treasure(TreasureChoice::Play { hand_index: 0 })
+        .treasure(TreasureChoice::Play { hand_index: 2 })
+        .treasure(TreasureChoice::Stop);
+    game.play_treasures(0, &client).unwrap();
+    assert_eq!(game.players[0].resources.coins, 3);
+    assert_eq!(game.players[0].hand.len(), 2);
+    assert_eq!(game.players[0].hand[1].name(), "Copper");
+
+    let client = ScriptedClient::default().treasure(TreasureChoice::PlayAll);
+    game.play_treasures(0, &client).unwrap();
+    assert_eq!(game.players[0].resources.coins, 4);
+    assert_eq!(game.players[0].hand.len(), 1);
+}
+
+#[test]
+fn test_game_phases_give_up_on_invalid_choices() {
+    let mut game = Game::default();
+    let player = &mut game.players[0];
+    player.reset_state();
+    player.hand.clear();
+    player
+        .hand
+        .extend([Box::new(Estate) as Box<dyn Card>, Box::new(Copper)]);
+
+    // Choosing a card that can't be played is asked again, but only so many
+    // times
+    let client =
+        (0..MAX_INVALID_ANSWERS).fold(ScriptedClient::default(), |client, _| client.hand(vec![0]));
+    game.set_phase(0, Phase::ActionPhase).unwrap();
+    assert!(matches!(
+        game.action_phase(0, &client),
+        Err(Error::TooManyInvalidAnswers)
+    ));
+
+    let client = (0..MAX_INVALID_ANSWERS).fold(ScriptedClient::default(), |client, _| {
+        client.treasure(TreasureChoice::Play { hand_index: 0 })
+    });
+    game.set_phase(0, Phase::BuyPhase).unwrap();
+    assert!(matches!(
+        game.play_treasures(0, &client),
+        Err(Error::TooManyInvalidAnswers)
+    ));
+
+    let client = (0..MAX_INVALID_ANSWERS).fold(
+        ScriptedClient::default().treasure(TreasureChoice::Stop),
+        |client, _| client.supply(Province),
+    );
+    assert!(matches!(
+        game.buy_phase(0, &client),
+        Err(Error::TooManyInvalidAnswers)
+    ));
+    assert_eq!(game.players[0].hand.len(), 2);
+}
+
+#[test]
+fn test_game_action_phase_card_invalid_answers_ask_the_card_again() {
+    let mut game = Game::default();
+    let player = &mut game.players[0];
+    player.reset_state();
+    player.hand.clear();
+    player
+        .hand
+        .extend([Box::new(Chapel) as Box<dyn Card>, Box::new(Copper)]);
+
+    // A bad choice made for Chapel asks Chapel again, not which card to play
+    let client = ScriptedClient::default()
+        .hand(vec![0])
+        .hand(vec![7])
+        .hand(vec![0]);
+    game.set_phase(0, Phase::ActionPhase).unwrap();
+    game.action_phase(0, &client).unwrap();
+
+    assert_eq!(names(&game.players[0].in_play), ["Chapel"]);
+    assert_eq!(names(&game.trash), ["Copper"]);
+    assert!(game.players[0].hand.is_empty());
+}
+
+#[test]
+fn test_game_tokens() {
+    let mut game = Game::default();
+    let tokens = &mut game.players[0].tokens;
+    tokens.villagers = 2;
+    tokens.coffers = 3;
+    tokens.victory_points = 4;
+
+    // Tokens are kept from turn to turn
+    game.players[0].reset_state();
+    assert_eq!(game.players[0].tokens.villagers, 2);
+    assert_eq!(game.players[0].victory_points(), 7);
+    assert_eq!(game.partial_game(1).unwrap().players[0].tokens.coffers, 3);
+
+    let client = ScriptedClient::default().tokens(1).tokens(2);
+    game.players[0].hand.clear();
+    game.set_phase(0, Phase::ActionPhase).unwrap();
+    game.action_phase(0, &client).unwrap();
+    assert_eq!(game.players[0].tokens.villagers, 1);
+    assert_eq!(game.players[0].resources.actions, 2);
+
+    game.set_phase(0, Phase::BuyPhase).unwrap();
+    game.buy_phase(0, &client).unwrap();
+    assert_eq!(game.players[0].tokens.coffers, 1);
+    assert_eq!(game.players[0].resources.coins_remaining, 2);
+
+    // Villagers can only be spent in the action phase
+    assert!(matches!(
+        game.spend_tokens(0, TokenKind::Villagers, 1),
+        Err(Error::WrongPhase)
+    ));
+    assert!(matches!(
+        game.spend_tokens(0, TokenKind::Coffers, 2),
+        Err(Error::NotEnoughTokens { .. })
+    ));
+    assert!(matches!(
+        game.spend_tokens(0, TokenKind::VictoryPoints, 1),
+        Err(Error::UnspendableTokens { .. })
+    ));
+}
+
+#[test]
+fn test_game_debt() {
+    let mut game = Game::default();
+    game.players[0].reset_state();
+    game.players[0].tokens.debt = 4;
+    game.set_phase(0, Phase::BuyPhase).unwrap();
+    game.players[0].resources.coins_remaining = 3;
+
+    // Debt is paid off first, so not even a Copper can be bought, and a buy
+    // that fails doesn't pay anything
+    let client = ScriptedClient::default();
+    assert!(game.legal_actions(0).buys.is_empty());
+    assert!(matches!(
+        game.buy_card(0, Box::new(Copper), &client),
+        Err(Error::OutstandingDebt)
+    ));
+    assert_eq!(game.players[0].tokens.debt, 4);
+    assert_eq!(game.players[0].resources.coins_remaining, 3);
+
+    game.pay_debt(0).unwrap();
+    assert_eq!(game.players[0].tokens.debt, 1);
+
+    game.players[0].resources.coins_remaining = 4;
+    game.buy_card(0, Box::new(Silver), &client).unwrap();
+    assert_eq!(game.players[0].tokens.debt, 0);
+    assert_eq!(game.players[0].resources.coins_remaining, 0);
+}
+
+#[test]
+fn test_game_buy_outside_buy_phase() {
+    let mut game = Game::default();
+    game.players[0].reset_state();
+    game.players[0].resources.coins_remaining = 3;
+    game.set_phase(0, Phase::ActionPhase).unwrap();
+
+    // Cards can only be bought in the buy phase
+    let client = ScriptedClient::default();
+    assert!(matches!(
+        game.buy_card(0, Box::new(Copper), &client),
+        Err(Error::WrongPhase)
+    ));
+    assert_eq!(game.players[0].resources.buys, 1);
+    assert!(game.players[0].discard.is_empty());
+
+    game.set_phase(0, Phase::BuyPhase).unwrap();
+    game.buy_card(0, Box::new(Copper), &client).unwrap();
+    assert_eq!(game.players[0].resources.buys, 0);
+}
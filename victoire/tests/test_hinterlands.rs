@@ -0,0 +1,111 @@
+//! Tests for the cards in Hinterlands
+
+mod common;
+
+use std::sync::Arc;
+
+use common::{play_effects, RecordingObserver, ScriptedClient};
+use victoire::cards::{base::*, dominion::*, hinterlands::*};
+use victoire::events::GameEvent;
+use victoire::types::{Card, CardId, Game, Identified, Selector, Zone};
+
+#[test]
+fn test_highway() {
+    let mut game = Game::default();
+    game.players[0].reset_state();
+    let client = ScriptedClient::default();
+
+    play_effects(&mut game, 0, &Highway, &client).unwrap();
+    let player = &game.players[0];
+    assert_eq!(player.hand.len(), 6);
+    assert_eq!(player.resources.actions, 2);
+    assert_eq!(game.effective_cost(&Gold, 0).coins, 5);
+
+    game.end_turn(0, &client).unwrap();
+    assert_eq!(game.effective_cost(&Gold, 0).coins, 6);
+
+    // A Highway played twice with Throne Room only makes cards $1 cheaper,
+    // and only while it is in play
+    let mut highway = Highway;
+    highway.set_id(CardId(100));
+    game.players[0].reset_state();
+    game.players[0].hand.push_front(Box::new(ThroneRoom));
+    game.players[0].hand.push_back(Box::new(highway.clone()));
+    let client = ScriptedClient::default().hand(vec![5]);
+    game.play_action_from_hand(0, 0, &client).unwrap();
+    assert_eq!(game.players[0].resources.actions, 2);
+    assert_eq!(game.effective_cost(&Gold, 0).coins, 5);
+
+    game.move_card(
+        0,
+        Zone::InPlay,
+        Zone::Trash,
+        Selector::Card(Box::new(highway)),
+    )
+    .unwrap();
+    game.resolve(&client).unwrap();
+    assert_eq!(game.effective_cost(&Gold, 0).coins, 6);
+}
+
+#[test]
+fn test_tunnel() {
+    let mut game = Game::default();
+    let mut tunnel = Tunnel;
+    tunnel.set_id(CardId(7));
+    game.players[0].hand = vec![Box::new(tunnel) as Box<dyn Card>, Box::new(Tunnel)]
+        .into_iter()
+        .collect();
+    let observer = Arc::new(RecordingObserver::default());
+    game.add_observer(observer.clone());
+    let client = ScriptedClient::default().yes_or_no(true).yes_or_no(false);
+
+    // Discarding it from hand can gain a Gold
+    game.discard_from_hand(0, vec![0]).unwrap();
+    game.resolve(&client).unwrap();
+    let discard: Vec<_> = game.players[0].discard.iter().map(|c| c.name()).collect();
+    assert_eq!(discard, ["Tunnel", "Gold"]);
+
+    // Only the Tunnel itself is revealed, rather than a new copy of it
+    let revealed: Vec<_> = observer
+        .events()
+        .into_iter()
+        .filter_map(|event| match event {
+            GameEvent::CardsRevealed { cards, .. } => Some(cards),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(revealed.len(), 1);
+    let names: Vec<_> = revealed[0].iter().map(|c| c.name()).collect();
+    assert_eq!(names, ["Tunnel"]);
+    assert_eq!(revealed[0][0].id(), Some(CardId(7)));
+
+    // Declining to reveal it gains nothing
+    game.discard_from_hand(0, vec![0]).unwrap();
+    game.resolve(&client).unwrap();
+    assert_eq!(game.players[0].discard.len(), 3);
+
+    // Nothing is gained when it is discarded during clean-up
+    game.players[0].hand.push_back(Box::new(Tunnel));
+    game.end_turn(0, &ScriptedClient::default().yes_or_no(true))
+        .unwrap();
+    assert_eq!(
+        game.players[0]
+            .all_cards()
+            .filter(|card| card.name() == Gold.name())
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn test_tunnel_discarded_by_vassal() {
+    let mut game = Game::default();
+    game.players[0].deck.push_front(Box::new(Tunnel));
+    let client = ScriptedClient::default().yes_or_no(true);
+
+    // Vassal discards the card before anything else, so Tunnel can gain a
+    // Gold
+    play_effects(&mut game, 0, &Vassal, &client).unwrap();
+    let discard: Vec<_> = game.players[0].discard.iter().map(|c| c.name()).collect();
+    assert_eq!(discard, ["Tunnel", "Gold"]);
+}
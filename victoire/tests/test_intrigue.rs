@@ -0,0 +1,254 @@
+//! Tests for the cards in Intrigue
+
+mod common;
+
+use std::sync::Arc;
+
+use common::{cards, game_with_hand, names, play_effects, RecordingObserver, ScriptedClient};
+use victoire::cards::{base::*, dominion::*, intrigue::*};
+use victoire::events::GameEvent;
+use victoire::types::{Card, Zone};
+
+#[test]
+fn test_kingdom_cards() {
+    assert_eq!(victoire::cards::intrigue::kingdom_cards().len(), 26);
+}
+
+#[test]
+fn test_bridge() {
+    let mut game = game_with_hand(vec![]);
+    play_effects(&mut game, 0, &Bridge, &ScriptedClient::default()).unwrap();
+    play_effects(&mut game, 0, &Bridge, &ScriptedClient::default()).unwrap();
+
+    assert_eq!(game.effective_cost(&Gold, 0).coins, 4);
+    assert_eq!(game.effective_cost(&Copper, 0).coins, 0);
+    assert_eq!(game.players[0].resources.buys, 3);
+
+    // The reduction only lasts for the turn
+    game.end_turn(0, &ScriptedClient::default()).unwrap();
+    assert_eq!(game.effective_cost(&Gold, 0).coins, 6);
+}
+
+#[test]
+fn test_conspirator() {
+    let mut game = game_with_hand(vec![]);
+    let client = ScriptedClient::default();
+
+    play_effects(&mut game, 0, &Conspirator, &client).unwrap();
+    play_effects(&mut game, 0, &Conspirator, &client).unwrap();
+    assert_eq!(game.players[0].hand.len(), 0);
+
+    // The third action played this turn triggers the bonus
+    play_effects(&mut game, 0, &Conspirator, &client).unwrap();
+    let player = &game.players[0];
+    assert_eq!(player.hand.len(), 1);
+    assert_eq!(player.resources.actions, 2);
+    assert_eq!(player.resources.temp_coins, 6);
+}
+
+#[test]
+fn test_diplomat() {
+    let mut game = game_with_hand(vec![]);
+    game.players[1].hand.push_back(Box::new(Diplomat));
+
+    // Reveal Diplomat, draw 2 and discard 3, then discard down to 3 for
+    // the Militia
+    let client = ScriptedClient::default()
+        .selection(vec![0])
+        .hand(vec![0, 1, 2])
+        .hand(vec![0, 1]);
+    play_effects(&mut game, 0, &Militia, &client).unwrap();
+
+    assert_eq!(game.players[1].hand.len(), 3);
+    assert_eq!(game.players[1].discard.len(), 5);
+}
+
+#[test]
+fn test_dual_types() {
+    let player = &game_with_hand(vec![]).players[0];
+    assert_eq!(Mill.victory_points(player), 1);
+    assert_eq!(Nobles.victory_points(player), 2);
+    assert_eq!(Harem.victory_points(player), 2);
+    assert_eq!(Harem.treasure_value().coins, 2);
+
+    assert!(Nobles.is_action() && Nobles.is_victory());
+    assert!(Harem.is_treasure() && Harem.is_victory());
+}
+
+#[test]
+fn test_lurker() {
+    let mut game = game_with_hand(vec![]);
+
+    let client = ScriptedClient::default().options(vec![0]).supply(Smithy);
+    play_effects(&mut game, 0, &Lurker, &client).unwrap();
+    assert_eq!(names(&game.trash), ["Smithy"]);
+    assert_eq!(game.supply.get("Smithy").unwrap().count, 9);
+
+    let client = ScriptedClient::default().options(vec![1]).trash(vec![0]);
+    play_effects(&mut game, 0, &Lurker, &client).unwrap();
+    assert!(game.trash.is_empty());
+    assert_eq!(names(&game.players[0].discard), ["Smithy"]);
+    assert_eq!(game.players[0].resources.actions, 3);
+}
+
+#[test]
+fn test_masquerade() {
+    let mut game = game_with_hand(vec![Box::new(Gold)]);
+    game.players[0].deck = cards(vec![Box::new(Estate), Box::new(Estate)]);
+    game.players[1].hand = cards(vec![Box::new(Silver)]);
+    let observer = Arc::new(RecordingObserver::default());
+    game.add_observer(observer.clone());
+
+    // Player 0 passes Gold, player 1 passes Silver, then player 0 trashes
+    // an Estate
+    let client = ScriptedClient::default()
+        .hand(vec![0])
+        .hand(vec![0])
+        .hand(vec![0]);
+    play_effects(&mut game, 0, &Masquerade, &client).unwrap();
+
+    assert_eq!(names(&game.players[0].hand), ["Estate", "Silver"]);
+    assert_eq!(names(&game.players[1].hand), ["Gold"]);
+    assert_eq!(names(&game.trash), ["Estate"]);
+
+    // Passing is reported as a move into the other player's hand
+    let passed = observer.events().into_iter().any(|event| {
+        event
+            == GameEvent::CardMoved {
+                player_index: 0,
+                from: Zone::Hand,
+                to: Zone::Hand,
+                to_player: 1,
+                card: None,
+            }
+    });
+    assert!(passed);
+}
+
+#[test]
+fn test_minion() {
+    let mut game = game_with_hand(vec![]);
+    assert_eq!(game.players[1].hand.len(), 5);
+
+    // Choosing coins doesn't attack
+    let client = ScriptedClient::default().options(vec![0]);
+    play_effects(&mut game, 0, &Minion, &client).unwrap();
+    assert_eq!(game.players[0].resources.temp_coins, 2);
+    assert!(game.players[1].discard.is_empty());
+
+    let client = ScriptedClient::default().options(vec![1]);
+    play_effects(&mut game, 0, &Minion, &client).unwrap();
+    assert_eq!(game.players[0].hand.len(), 4);
+    assert_eq!(game.players[1].hand.len(), 4);
+    assert_eq!(game.players[1].discard.len(), 5);
+}
+
+#[test]
+fn test_pawn() {
+    let mut game = game_with_hand(vec![]);
+
+    let client = ScriptedClient::default().options(vec![2, 3]);
+    play_effects(&mut game, 0, &Pawn, &client).unwrap();
+    assert_eq!(game.players[0].resources.buys, 2);
+    assert_eq!(game.players[0].resources.temp_coins, 1);
+
+    // Choosing the same option twice is asked again
+    let client = ScriptedClient::default()
+        .options(vec![3, 3])
+        .options(vec![0, 1]);
+    play_effects(&mut game, 0, &Pawn, &client).unwrap();
+    assert_eq!(game.players[0].hand.len(), 1);
+    assert_eq!(game.players[0].resources.actions, 2);
+}
+
+#[test]
+fn test_replace() {
+    let mut game = game_with_hand(vec![Box::new(Copper), Box::new(Estate)]);
+
+    // Gaining a Victory card curses everyone else
+    let client = ScriptedClient::default().hand(vec![1]).supply(Estate);
+    play_effects(&mut game, 0, &Replace, &client).unwrap();
+    assert_eq!(names(&game.players[0].discard), ["Estate"]);
+    assert_eq!(names(&game.players[1].discard), ["BasicCurse"]);
+
+    // Gaining a Treasure puts it on the deck, and doesn't attack
+    let client = ScriptedClient::default().hand(vec![0]).supply(Copper);
+    play_effects(&mut game, 0, &Replace, &client).unwrap();
+    assert_eq!(game.players[0].deck[0].name(), "Copper");
+    assert_eq!(game.players[1].discard.len(), 1);
+}
+
+#[test]
+fn test_secret_passage() {
+    let mut game = game_with_hand(vec![Box::new(Gold)]);
+    game.players[0].deck = cards(vec![Box::new(Copper), Box::new(Estate), Box::new(Silver)]);
+
+    // The Gold goes to the bottom of the deck
+    let client = ScriptedClient::default().hand(vec![0]).yes_or_no(false);
+    play_effects(&mut game, 0, &SecretPassage, &client).unwrap();
+
+    let player = &game.players[0];
+    assert_eq!(player.resources.actions, 2);
+    assert_eq!(names(&player.hand), ["Copper", "Estate"]);
+    assert_eq!(names(&player.deck), ["Silver", "Gold"]);
+
+    // Or on top of it
+    let client = ScriptedClient::default().hand(vec![0]).yes_or_no(true);
+    play_effects(&mut game, 0, &SecretPassage, &client).unwrap();
+    assert_eq!(names(&game.players[0].hand), ["Estate", "Silver", "Gold"]);
+    assert_eq!(names(&game.players[0].deck), ["Copper"]);
+}
+
+#[test]
+fn test_steward() {
+    let mut game = game_with_hand(vec![Box::new(Copper), Box::new(Estate), Box::new(Gold)]);
+
+    let client = ScriptedClient::default().options(vec![2]).hand(vec![0, 1]);
+    play_effects(&mut game, 0, &Steward, &client).unwrap();
+
+    assert_eq!(names(&game.players[0].hand), ["Gold"]);
+    assert_eq!(game.trash.len(), 2);
+}
+
+#[test]
+fn test_swindler() {
+    let mut game = game_with_hand(vec![]);
+    game.players[1].deck.push_front(Box::new(Copper));
+
+    // Silver doesn't cost the same as Copper, so the swindler is asked again
+    let client = ScriptedClient::default().supply(Silver).supply(BasicCurse);
+    play_effects(&mut game, 0, &Swindler, &client).unwrap();
+
+    assert_eq!(names(&game.trash), ["Copper"]);
+    assert_eq!(names(&game.players[1].discard), ["BasicCurse"]);
+}
+
+#[test]
+fn test_torturer() {
+    let mut game = game_with_hand(vec![]);
+
+    let client = ScriptedClient::default().options(vec![1]);
+    play_effects(&mut game, 0, &Torturer, &client).unwrap();
+    assert_eq!(game.players[0].hand.len(), 3);
+    assert_eq!(game.players[1].hand.len(), 6);
+    assert_eq!(game.players[1].hand[5].name(), "BasicCurse");
+
+    let client = ScriptedClient::default().options(vec![0]).hand(vec![0, 5]);
+    play_effects(&mut game, 0, &Torturer, &client).unwrap();
+    assert_eq!(game.players[1].hand.len(), 4);
+    assert_eq!(game.players[1].discard[1].name(), "BasicCurse");
+}
+
+#[test]
+fn test_trading_post() {
+    let mut game = game_with_hand(vec![Box::new(Copper), Box::new(Estate)]);
+
+    let client = ScriptedClient::default().hand(vec![0, 1]);
+    play_effects(&mut game, 0, &TradingPost, &client).unwrap();
+    assert_eq!(names(&game.players[0].hand), ["Silver"]);
+
+    // With only one card to trash, no Silver is gained
+    let client = ScriptedClient::default().hand(vec![0]);
+    play_effects(&mut game, 0, &TradingPost, &client).unwrap();
+    assert!(game.players[0].hand.is_empty());
+}
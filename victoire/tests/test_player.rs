@@ -2,6 +2,7 @@
 
 use victoire::callbacks::TestClient;
 use victoire::cards::dominion::*;
+use victoire::error::Error;
 use victoire::types::{Game, Player};
 
 #[test]
@@ -32,45 +33,69 @@ fn test_player_draw() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_player_discard() {
-    let mut player = Player::new_with_default_deck(0);
+    let mut game = Game::default();
     let first_vec = vec![0, 2, 4];
-    player.discard_given_indexes(first_vec);
+    game.discard_given_indexes(0, first_vec).unwrap();
+    let player = &game.players[0];
     assert!(player.hand.len() == 2 && player.discard.len() == 3);
 
     let second_vec = vec![0];
-    player.discard_given_indexes(second_vec);
+    game.discard_given_indexes(0, second_vec).unwrap();
+    let player = &game.players[0];
     assert!(player.hand.len() == 1 && player.discard.len() == 4);
 
     let third_vec = vec![0];
-    player.discard_given_indexes(third_vec);
+    game.discard_given_indexes(0, third_vec).unwrap();
+    let player = &game.players[0];
     assert!(player.hand.is_empty() && player.discard.len() == 5);
 
     let fourth_vec = vec![0];
-    player.discard_given_indexes(fourth_vec);
+    assert!(game.discard_given_indexes(0, fourth_vec).is_err());
+    let player = &game.players[0];
     assert!(player.hand.is_empty() && player.discard.len() == 5);
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_player_trash() {
-    let mut player = Player::new_with_default_deck(0);
     let mut game = Game::default();
-    player.trash_given_indexes(vec![0, 1, 2, 3], &mut game.trash);
+    game.trash_given_indexes(0, vec![0, 1, 2, 3]).unwrap();
+    let player = &game.players[0];
     assert!(player.hand.len() == 1 && game.trash.len() == 4 && player.discard.is_empty());
 
-    player.trash_given_indexes(vec![0], &mut game.trash);
+    game.trash_given_indexes(0, vec![0]).unwrap();
+    let player = &game.players[0];
     assert!(player.hand.is_empty() && game.trash.len() == 5 && player.discard.is_empty());
 
-    player.trash_given_indexes(vec![0], &mut game.trash);
+    assert!(game.trash_given_indexes(0, vec![0]).is_err());
+    let player = &game.players[0];
     assert!(player.hand.is_empty() && game.trash.len() == 5 && player.discard.is_empty());
 }
 
+#[test]
+fn test_player_cleanup() {
+    let mut player = Player::new_with_default_deck(0);
+    for _ in 0..2 {
+        let card = player.remove_from_hand(0).unwrap();
+        player.in_play.push_back(card);
+    }
+
+    player.cleanup();
+    assert_eq!(player.hand.len(), 5);
+    assert!(player.in_play.is_empty());
+    assert_eq!(player.discard.len(), 5);
+    assert!(player.deck.is_empty());
+}
+
 #[test]
 fn test_player_play_action() {
     let mut game = Game::default();
     let callbacks = TestClient;
 
-    let _ = game.gain_to_hand(0, Box::new(Market), &callbacks);
+    let _ = game.gain_to_hand(0, Box::new(Market));
+    game.resolve(&callbacks).unwrap();
     let player1 = &mut game.players[0];
     let temp_coins_b4add = player1.resources.temp_coins;
     player1.resources.actions = 1;
@@ -94,3 +119,28 @@ fn test_player_buy() {}
 
 #[test]
 fn test_player_gain() {}
+
+#[test]
+#[allow(deprecated)]
+fn test_player_invalid_indexes() {
+    let mut game = Game::default();
+
+    assert!(matches!(
+        game.discard_given_indexes(0, vec![1, 5]),
+        Err(Error::InvalidHandIndex { index: 5 })
+    ));
+    assert_eq!(game.players[0].hand.len(), 5);
+
+    game.discard_given_indexes(0, vec![1, 1, 3]).unwrap();
+    assert_eq!(game.players[0].hand.len(), 3);
+    assert_eq!(game.players[0].discard.len(), 2);
+
+    assert!(matches!(
+        game.move_given_indexes_discard_to_hand(0, vec![2]),
+        Err(Error::InvalidDiscardIndex { index: 2 })
+    ));
+    assert!(matches!(
+        game.players[0].remove_from_hand(3),
+        Err(Error::InvalidHandIndex { index: 3 })
+    ));
+}
@@ -0,0 +1,229 @@
+//! Tests for Platinum, Colony, VP tokens and the cards in Prosperity
+
+mod common;
+
+use common::{cards, game_with_hand, names, play_effects, ScriptedClient};
+use victoire::cards::{base::*, dominion::*, prosperity::*};
+use victoire::types::{Game, Phase};
+
+#[test]
+fn test_colonies() {
+    let mut game = game_with_hand(vec![]);
+    assert!(game.supply.get("Colony").is_none());
+
+    game.generate_supply_with_colonies(Game::default_supply_list())
+        .unwrap();
+    assert_eq!(game.supply.get("Platinum").unwrap().count, 12);
+    assert_eq!(game.supply.get("Colony").unwrap().count, 8);
+    assert!(!game.is_game_over());
+
+    // Running out of Colonies ends the game
+    game.supply.get_mut("Colony").unwrap().count = 0;
+    assert!(game.is_game_over());
+}
+
+#[test]
+fn test_vp_tokens() {
+    let mut game = game_with_hand(vec![]);
+    let points = game.players[0].victory_points();
+
+    play_effects(&mut game, 0, &Monument, &ScriptedClient::default()).unwrap();
+    assert_eq!(game.players[0].tokens.victory_points, 1);
+    assert_eq!(game.players[0].victory_points(), points + 1);
+    assert_eq!(game.result().scores[0].victory_points, points + 1);
+    assert_eq!(
+        game.partial_game(1).unwrap().players[0]
+            .tokens
+            .victory_points,
+        1
+    );
+}
+
+#[test]
+fn test_bank() {
+    // Treasures are played from the end of the hand, so Bank is played last
+    let mut game = game_with_hand(vec![Box::new(Bank), Box::new(Copper), Box::new(Silver)]);
+
+    game.play_all_treasures(0, &ScriptedClient::default())
+        .unwrap();
+    assert_eq!(game.players[0].resources.coins, 6);
+}
+
+#[test]
+fn test_bishop() {
+    let mut game = game_with_hand(vec![Box::new(Gold)]);
+
+    let client = ScriptedClient::default().hand(vec![0]).hand(vec![0]);
+    play_effects(&mut game, 0, &Bishop, &client).unwrap();
+
+    assert_eq!(game.players[0].tokens.victory_points, 4);
+    assert_eq!(game.players[1].hand.len(), 4);
+    assert_eq!(game.trash.len(), 2);
+}
+
+#[test]
+fn test_forge() {
+    let mut game = game_with_hand(vec![Box::new(Estate), Box::new(Estate), Box::new(Copper)]);
+
+    // The trashed cards cost 4 in total, so Silver can't be gained
+    let client = ScriptedClient::default()
+        .hand(vec![0, 1, 2])
+        .supply(Silver)
+        .supply(Smithy);
+    play_effects(&mut game, 0, &Forge, &client).unwrap();
+
+    assert_eq!(game.trash.len(), 3);
+    assert_eq!(names(&game.players[0].discard), ["Smithy"]);
+}
+
+#[test]
+fn test_goons() {
+    let mut game = game_with_hand(vec![]);
+    let client = ScriptedClient::default().hand(vec![0, 1]);
+    play_effects(&mut game, 0, &Goons, &client).unwrap();
+    play_effects(&mut game, 0, &Goons, &client).unwrap();
+    assert_eq!(game.players[1].hand.len(), 3);
+
+    game.set_phase(0, Phase::BuyPhase).unwrap();
+    game.players[0].resources.coins_remaining = 4;
+    game.buy_card(0, Box::new(Copper), &client).unwrap();
+    game.buy_card(0, Box::new(Silver), &client).unwrap();
+    assert_eq!(game.players[0].tokens.victory_points, 4);
+}
+
+#[test]
+fn test_kings_court() {
+    let mut game = game_with_hand(vec![Box::new(Village)]);
+
+    let client = ScriptedClient::default().hand(vec![0]);
+    play_effects(&mut game, 0, &KingsCourt, &client).unwrap();
+
+    let player = &game.players[0];
+    assert_eq!(player.resources.actions, 7);
+    assert_eq!(player.hand.len(), 3);
+    assert_eq!(names(&player.in_play), ["Village"]);
+}
+
+#[test]
+fn test_mint() {
+    let mut game = game_with_hand(vec![Box::new(Silver)]);
+    let client = ScriptedClient::default().hand(vec![0]);
+    play_effects(&mut game, 0, &Mint, &client).unwrap();
+    assert_eq!(names(&game.players[0].discard), ["Silver"]);
+
+    // Buying Mint trashes the treasures in play
+    game.supply.insert(Mint, 10);
+    game.play_all_treasures(0, &client).unwrap();
+    game.set_phase(0, Phase::BuyPhase).unwrap();
+    game.players[0].resources.coins_remaining = 5;
+    game.buy_card(0, Box::new(Mint), &client).unwrap();
+    assert!(game.players[0].in_play.is_empty());
+    assert_eq!(names(&game.trash), ["Silver"]);
+}
+
+#[test]
+fn test_quarry() {
+    let mut game = game_with_hand(vec![Box::new(Quarry)]);
+    let client = ScriptedClient::default();
+    game.play_all_treasures(0, &client).unwrap();
+    assert_eq!(game.players[0].resources.coins, 1);
+
+    // Only Action cards are cheaper
+    assert_eq!(game.effective_cost(&Mint, 0).coins, 3);
+    assert_eq!(game.effective_cost(&Silver, 0).coins, 3);
+    assert_eq!(game.effective_cost(&Village, 0).coins, 1);
+}
+
+#[test]
+fn test_royal_seal() {
+    let mut game = game_with_hand(vec![Box::new(RoyalSeal)]);
+    let client = ScriptedClient::default().yes_or_no(true).yes_or_no(false);
+    game.play_all_treasures(0, &client).unwrap();
+    assert_eq!(game.players[0].resources.coins, 2);
+
+    game.gain(0, Box::new(Gold)).unwrap();
+    game.resolve(&client).unwrap();
+    game.gain(0, Box::new(Silver)).unwrap();
+    game.resolve(&client).unwrap();
+    assert_eq!(game.players[0].deck[0].name(), "Gold");
+    assert_eq!(names(&game.players[0].discard), ["Silver"]);
+}
+
+#[test]
+fn test_trader() {
+    let mut game = game_with_hand(vec![Box::new(Trader), Box::new(Gold)]);
+    game.supply.insert(Trader, 10);
+
+    // Gold costs 6, so 6 Silvers are gained
+    let client = ScriptedClient::default().hand(vec![0]);
+    game.play_action_from_hand(0, 0, &client).unwrap();
+    assert_eq!(game.players[0].discard.len(), 6);
+
+    // Only the Silvers left in the pile are gained
+    let mut game = game_with_hand(vec![Box::new(Trader), Box::new(Gold)]);
+    game.supply.insert(Silver, 2);
+    let client = ScriptedClient::default().hand(vec![0]);
+    game.play_action_from_hand(0, 0, &client).unwrap();
+    assert_eq!(names(&game.players[0].discard), ["Silver", "Silver"]);
+
+    // Only the Silvers left in the pile are gained
+    let mut game = game_with_hand(vec![Box::new(Trader), Box::new(Gold)]);
+    game.supply.insert(Silver, 2);
+    let client = ScriptedClient::default().hand(vec![0]);
+    game.play_action_from_hand(0, 0, &client).unwrap();
+    assert_eq!(names(&game.players[0].discard), ["Silver", "Silver"]);
+
+    // Bought cards can be replaced too, and the Province is never taken
+    let mut game = game_with_hand(vec![Box::new(Trader)]);
+    game.set_phase(0, Phase::BuyPhase).unwrap();
+    game.players[0].resources.coins_remaining = 8;
+    let provinces = game.supply.get("Province").unwrap().count;
+    let client = ScriptedClient::default().yes_or_no(true);
+    game.buy_card(0, Box::new(Province), &client).unwrap();
+    assert_eq!(names(&game.players[0].discard), ["Silver"]);
+    assert_eq!(game.supply.get("Province").unwrap().count, provinces);
+}
+
+#[test]
+fn test_watchtower() {
+    let mut game = game_with_hand(vec![Box::new(Watchtower)]);
+
+    let client = ScriptedClient::default()
+        .yes_or_no(true)
+        .options(vec![1])
+        .yes_or_no(true)
+        .options(vec![0])
+        .yes_or_no(false);
+    game.gain(0, Box::new(Gold)).unwrap();
+    game.resolve(&client).unwrap();
+    game.gain_to_hand(0, Box::new(BasicCurse)).unwrap();
+    game.resolve(&client).unwrap();
+    game.gain(0, Box::new(Silver)).unwrap();
+    game.resolve(&client).unwrap();
+
+    let player = &game.players[0];
+    assert_eq!(player.deck[0].name(), "Gold");
+    assert_eq!(names(&game.trash), ["BasicCurse"]);
+    assert_eq!(names(&player.discard), ["Silver"]);
+    assert_eq!(player.state.gained.len(), 3);
+
+    // Playing it draws up to 6 cards
+    game.play_action_from_hand(0, 0, &client).unwrap();
+    assert_eq!(game.players[0].hand.len(), 6);
+    assert_eq!(names(&game.players[0].in_play), ["Watchtower"]);
+}
+
+#[test]
+fn test_venture() {
+    let mut game = game_with_hand(vec![Box::new(Venture)]);
+    game.players[0].deck = cards(vec![Box::new(Estate), Box::new(Gold), Box::new(Copper)]);
+
+    game.play_all_treasures(0, &ScriptedClient::default())
+        .unwrap();
+
+    let player = &game.players[0];
+    assert_eq!(player.resources.coins, 4);
+    assert_eq!(names(&player.in_play), ["Venture", "Gold"]);
+    assert_eq!(names(&player.discard), ["Estate"]);
+    assert_eq!(names(&player.deck), ["Copper"]);
+}
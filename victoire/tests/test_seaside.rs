@@ -0,0 +1,241 @@
+//! Tests for the cards in Seaside and Duration cards in general
+
+mod common;
+
+use common::{cards, game_with_hand, names, play_effects, ScriptedClient};
+use victoire::cards::{base::*, dominion::*, seaside::*};
+use victoire::types::{Mat, Phase};
+
+#[test]
+fn test_duration_lifecycle() {
+    let mut game = game_with_hand(vec![Box::new(Wharf), Box::new(Smithy)]);
+    game.players[0].deck = cards(vec![Box::new(Copper); 20]);
+    let client = ScriptedClient::default();
+
+    game.play_action_from_hand(0, 0, &client).unwrap();
+    assert_eq!(game.players[0].resources.buys, 2);
+
+    // Wharf stays in play through cleanup, unlike the rest of the turn
+    game.end_turn(0, &client).unwrap();
+    let player = &game.players[0];
+    assert_eq!(names(&player.durations), ["Wharf"]);
+    assert!(player.in_play.is_empty());
+    assert_eq!(player.hand.len(), 5);
+    assert_eq!(
+        game.partial_game(1).unwrap().players[0].durations[0].name(),
+        "Wharf"
+    );
+
+    game.start_turn(0, &client).unwrap();
+    let player = &game.players[0];
+    assert_eq!(player.hand.len(), 7);
+    assert_eq!(player.resources.buys, 2);
+    assert!(player.durations.is_empty());
+    assert_eq!(names(&player.in_play), ["Wharf"]);
+
+    // Once its effects are done it is discarded as usual
+    game.end_turn(0, &client).unwrap();
+    assert!(game.players[0].durations.is_empty());
+    assert!(game.players[0].in_play.is_empty());
+}
+
+#[test]
+fn test_astrolabe() {
+    let mut game = game_with_hand(vec![Box::new(Astrolabe)]);
+    let client = ScriptedClient::default();
+
+    game.play_treasure(0, 0, &client).unwrap();
+    assert_eq!(game.players[0].resources.coins, 1);
+    assert_eq!(game.players[0].resources.buys, 2);
+
+    game.end_turn(0, &client).unwrap();
+    game.start_turn(0, &client).unwrap();
+    assert_eq!(game.players[0].resources.temp_coins, 1);
+    assert_eq!(game.players[0].resources.buys, 2);
+}
+
+#[test]
+fn test_haven() {
+    let mut game = game_with_hand(vec![Box::new(Haven), Box::new(Gold)]);
+
+    let client = ScriptedClient::default().hand(vec![0]);
+    game.play_action_from_hand(0, 0, &client).unwrap();
+    assert_eq!(names(game.players[0].mat(Mat::Haven)), ["Gold"]);
+
+    game.end_turn(0, &client).unwrap();
+    game.start_turn(0, &client).unwrap();
+    let player = &game.players[0];
+    assert_eq!(player.hand.len(), 6);
+    assert_eq!(player.hand[5].name(), "Gold");
+    assert!(player.mat(Mat::Haven).is_empty());
+}
+
+#[test]
+fn test_island() {
+    let mut game = game_with_hand(vec![Box::new(Island), Box::new(Estate)]);
+    let points = game.players[0].victory_points();
+
+    let client = ScriptedClient::default().hand(vec![0]);
+    game.play_action_from_hand(0, 0, &client).unwrap();
+
+    let player = &game.players[0];
+    assert_eq!(names(player.mat(Mat::Island)), ["Island", "Estate"]);
+    assert!(player.in_play.is_empty() && player.hand.is_empty());
+    assert_eq!(player.victory_points(), points);
+}
+
+#[test]
+fn test_lighthouse() {
+    let mut game = game_with_hand(vec![]);
+    game.current_turn = 1;
+    game.players[1].reset_state();
+    play_effects(&mut game, 0, &Lighthouse, &ScriptedClient::default()).unwrap();
+
+    // Militia doesn't affect player 0 until their next turn
+    let client = ScriptedClient::default();
+    game.players[0].hand = cards(vec![Box::new(Copper); 5]);
+    play_effects(&mut game, 1, &Militia, &client).unwrap();
+    assert_eq!(game.players[0].hand.len(), 5);
+
+    game.start_turn(0, &client).unwrap();
+    let client = ScriptedClient::default().hand(vec![0, 1]);
+    play_effects(&mut game, 1, &Militia, &client).unwrap();
+    assert_eq!(game.players[0].hand.len(), 3);
+}
+
+#[test]
+fn test_native_village() {
+    let mut game = game_with_hand(vec![]);
+    game.players[0].deck.push_front(Box::new(Gold));
+
+    let client = ScriptedClient::default().options(vec![0]);
+    play_effects(&mut game, 0, &NativeVillage, &client).unwrap();
+    assert_eq!(names(game.players[0].mat(Mat::NativeVillage)), ["Gold"]);
+    // Only the owner can see what's on their Native Village mat
+    let own_view = game.partial_game(0).unwrap();
+    assert_eq!(own_view.mats[&Mat::NativeVillage].len(), 1);
+    assert!(own_view.players[0].mats.is_empty());
+    let other_view = game.partial_game(1).unwrap();
+    assert_eq!(other_view.players[0].mat_sizes[&Mat::NativeVillage], 1);
+    assert!(other_view.players[0].mats.is_empty());
+
+    let client = ScriptedClient::default().options(vec![1]);
+    play_effects(&mut game, 0, &NativeVillage, &client).unwrap();
+    assert_eq!(names(&game.players[0].hand), ["Gold"]);
+    assert_eq!(game.players[0].resources.actions, 5);
+}
+
+#[test]
+fn test_smugglers() {
+    let mut game = game_with_hand(vec![]);
+    game.current_turn = 1;
+    game.gain(1, Box::new(Gold)).unwrap();
+    game.resolve(&ScriptedClient::default()).unwrap();
+    game.gain(1, Box::new(Province)).unwrap();
+    game.resolve(&ScriptedClient::default()).unwrap();
+
+    // Province costs too much, so only Gold can be smuggled
+    game.current_turn = 0;
+    let client = ScriptedClient::default().selection(vec![0]);
+    play_effects(&mut game, 0, &Smugglers, &client).unwrap();
+    assert_eq!(names(&game.players[0].discard), ["Gold"]);
+}
+
+#[test]
+fn test_tactician() {
+    let mut game = game_with_hand(vec![Box::new(Tactician), Box::new(Copper)]);
+    game.players[0].deck = cards(vec![Box::new(Copper); 20]);
+    let client = ScriptedClient::default();
+
+    game.play_action_from_hand(0, 0, &client).unwrap();
+    assert!(game.players[0].hand.is_empty());
+
+    game.end_turn(0, &client).unwrap();
+    game.start_turn(0, &client).unwrap();
+    let player = &game.players[0];
+    assert_eq!(player.hand.len(), 10);
+    assert_eq!(player.resources.actions, 2);
+    assert_eq!(player.resources.buys, 2);
+
+    // With nothing to discard, it isn't kept in play
+    game.players[0].hand.clear();
+    game.players[0].hand.push_back(Box::new(Tactician));
+    game.play_action_from_hand(0, 0, &client).unwrap();
+    game.end_turn(0, &client).unwrap();
+    assert!(game.players[0].durations.is_empty());
+}
+
+#[test]
+fn test_treasure_map() {
+    let mut game = game_with_hand(vec![Box::new(TreasureMap), Box::new(TreasureMap)]);
+
+    game.play_action_from_hand(0, 0, &ScriptedClient::default())
+        .unwrap();
+
+    let player = &game.players[0];
+    assert_eq!(names(&game.trash), ["Treasure Map", "Treasure Map"]);
+    assert_eq!(names(&player.deck)[..4], ["Gold"; 4]);
+    assert!(player.hand.is_empty() && player.in_play.is_empty());
+}
+
+#[test]
+fn test_treasury() {
+    let mut game = game_with_hand(vec![Box::new(Treasury)]);
+    game.players[0].deck = cards(vec![Box::new(Copper); 20]);
+    let client = ScriptedClient::default().yes_or_no(true);
+
+    game.play_action_from_hand(0, 0, &client).unwrap();
+    let player = &game.players[0];
+    assert_eq!(player.hand.len(), 1);
+    assert_eq!(player.resources.actions, 1);
+    assert_eq!(player.resources.temp_coins, 1);
+
+    // Without a Victory card bought it goes back on the deck, so it is drawn
+    // into the next hand
+    game.end_turn(0, &client).unwrap();
+    let player = &game.players[0];
+    assert!(player.in_play.is_empty());
+    assert!(names(&player.hand).contains(&"Treasury"));
+    assert!(!names(&player.discard).contains(&"Treasury"));
+
+    // Buying a Victory card means it is discarded as usual
+    let index = names(&game.players[0].hand)
+        .iter()
+        .position(|name| *name == "Treasury")
+        .unwrap();
+    game.players[0].hand.swap(0, index);
+    game.play_action_from_hand(0, 0, &client).unwrap();
+    game.players[0].phase = Phase::BuyPhase;
+    game.players[0].resources.coins_remaining = 2;
+    game.buy_card(0, Box::new(Estate), &client).unwrap();
+    game.end_turn(0, &client).unwrap();
+    assert!(names(&game.players[0].discard).contains(&"Treasury"));
+}
+
+#[test]
+fn test_outpost() {
+    let mut game = game_with_hand(vec![Box::new(Outpost)]);
+    game.players[0].deck = cards(vec![Box::new(Copper); 20]);
+    let client = ScriptedClient::default();
+
+    game.play_action_from_hand(0, 0, &client).unwrap();
+    game.end_turn(0, &client).unwrap();
+    assert_eq!(game.players[0].hand.len(), 3);
+    assert_eq!(names(&game.players[0].durations), ["Outpost"]);
+
+    // The extra turn comes straight after, and doesn't count as a turn taken
+    game.next_turn();
+    assert_eq!(game.current_turn, 0);
+    assert!(game.current_extra_turn(0).is_some());
+    game.start_turn(0, &client).unwrap();
+    assert_eq!(game.players[0].turns_taken, 0);
+
+    // Another Outpost can't give a third turn in a row
+    game.players[0].hand.push_back(Box::new(Outpost));
+    game.play_action_from_hand(0, 3, &client).unwrap();
+    game.end_turn(0, &client).unwrap();
+    assert_eq!(game.players[0].hand.len(), 5);
+    assert!(game.players[0].durations.is_empty());
+    game.next_turn();
+    assert_eq!(game.current_turn, 1);
+}